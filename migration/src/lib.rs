@@ -7,6 +7,12 @@ pub use sea_orm_migration::prelude::*;
 
 mod m20260604_01_create_usage_table;
 mod m20260607_01_store_metadata;
+mod m20261018_01_create_odesli_cache;
+mod m20261018_02_create_auto_share;
+mod m20261018_03_create_guild_settings;
+mod m20261018_04_create_user_preferences;
+mod m20261018_05_index_command_usage;
+mod m20261018_06_add_command_usage_provider_id;
 
 pub struct Migrator;
 
@@ -16,6 +22,12 @@ impl MigratorTrait for Migrator {
         vec![
             Box::new(m20260604_01_create_usage_table::Migration),
             Box::new(m20260607_01_store_metadata::Migration),
+            Box::new(m20261018_01_create_odesli_cache::Migration),
+            Box::new(m20261018_02_create_auto_share::Migration),
+            Box::new(m20261018_03_create_guild_settings::Migration),
+            Box::new(m20261018_04_create_user_preferences::Migration),
+            Box::new(m20261018_05_index_command_usage::Migration),
+            Box::new(m20261018_06_add_command_usage_provider_id::Migration),
         ]
     }
 }
@@ -0,0 +1,102 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(OdesliResponse::Table)
+                    .if_not_exists()
+                    .col(string(OdesliResponse::EntityUniqueId).primary_key())
+                    .col(json_binary(OdesliResponse::Response))
+                    .col(
+                        timestamp_with_time_zone(OdesliResponse::FetchedAt)
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Used by the cleanup task to find expired responses
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_odesli_response_fetched_at")
+                    .table(OdesliResponse::Table)
+                    .col(OdesliResponse::FetchedAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(OdesliResponseAlias::Table)
+                    .if_not_exists()
+                    .col(string(OdesliResponseAlias::ProviderId).primary_key())
+                    .col(string(OdesliResponseAlias::EntityUniqueId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_odesli_response_alias_entity_unique_id")
+                            .from(
+                                OdesliResponseAlias::Table,
+                                OdesliResponseAlias::EntityUniqueId,
+                            )
+                            .to(OdesliResponse::Table, OdesliResponse::EntityUniqueId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(OdesliResponseAlias::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(OdesliResponse::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum OdesliResponse {
+    Table,
+    EntityUniqueId,
+    Response,
+    FetchedAt,
+}
+
+#[derive(DeriveIden)]
+enum OdesliResponseAlias {
+    Table,
+    ProviderId,
+    EntityUniqueId,
+}
@@ -0,0 +1,81 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+fn discord_snowflake<T: IntoIden>(name: T) -> ColumnDef {
+    big_integer(name).take()
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AutoShareGuild::Table)
+                    .if_not_exists()
+                    .col(discord_snowflake(AutoShareGuild::GuildId).primary_key())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AutoShareChannel::Table)
+                    .if_not_exists()
+                    .col(discord_snowflake(AutoShareChannel::GuildId))
+                    .col(discord_snowflake(AutoShareChannel::ChannelId))
+                    .primary_key(
+                        Index::create()
+                            .col(AutoShareChannel::GuildId)
+                            .col(AutoShareChannel::ChannelId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(AutoShareChannel::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(AutoShareGuild::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum AutoShareGuild {
+    Table,
+    GuildId,
+}
+
+#[derive(DeriveIden)]
+enum AutoShareChannel {
+    Table,
+    GuildId,
+    ChannelId,
+}
@@ -0,0 +1,53 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+fn discord_snowflake<T: IntoIden>(name: T) -> ColumnDef {
+    big_integer(name).take()
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GuildSettings::Table)
+                    .if_not_exists()
+                    .col(discord_snowflake(GuildSettings::GuildId).primary_key())
+                    .col(json_binary(GuildSettings::DisabledPlatforms))
+                    .col(json_binary(GuildSettings::PlatformOrder))
+                    .col(boolean(GuildSettings::ShowPlayer).default(true))
+                    .col(boolean(GuildSettings::EphemeralReplies).default(false))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(GuildSettings::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GuildSettings {
+    Table,
+    GuildId,
+    DisabledPlatforms,
+    PlatformOrder,
+    ShowPlayer,
+    EphemeralReplies,
+}
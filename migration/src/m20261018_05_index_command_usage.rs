@@ -0,0 +1,73 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Used to look up the recent shares of a user or guild for the autocompletion
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_command_usage_user_id")
+                    .table(CommandUsage::Table)
+                    .col(CommandUsage::UserId)
+                    .col(CommandUsage::InteractionId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_command_usage_guild_id")
+                    .table(CommandUsage::Table)
+                    .col(CommandUsage::GuildId)
+                    .col(CommandUsage::InteractionId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_command_usage_guild_id")
+                    .table(CommandUsage::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_command_usage_user_id")
+                    .table(CommandUsage::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum CommandUsage {
+    Table,
+    InteractionId,
+    GuildId,
+    UserId,
+}
@@ -0,0 +1,68 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CommandUsage::Table)
+                    .add_column_if_not_exists(string_null(CommandUsage::ProviderId))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Used to look up the last share of an entity for its landing page
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_command_usage_provider_id")
+                    .table(CommandUsage::Table)
+                    .col(CommandUsage::ProviderId)
+                    .col(CommandUsage::InteractionId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_command_usage_provider_id")
+                    .table(CommandUsage::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CommandUsage::Table)
+                    .drop_column(CommandUsage::ProviderId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum CommandUsage {
+    Table,
+    InteractionId,
+    ProviderId,
+}
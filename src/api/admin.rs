@@ -0,0 +1,203 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Json;
+use axum::extract::{Query, State as AxumState};
+use axum::http::{HeaderMap, header};
+use axum::routing::MethodRouter;
+use metronomos_pulse::value::{ArcValue, PulseValue, ValueGroupEntry};
+use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+use tracing::{info, instrument};
+
+use crate::api::error::RestApiErr;
+use crate::args::Args;
+use crate::clients::odesli::provider_id::ProviderId;
+use crate::clients::odesli::{CacheEviction, CacheStats, OdesliClient, OdesliResponse};
+use crate::http_server::HttpServeRoute;
+
+/// Endpoints for inspecting and invalidating the Odesli cache, authenticated with the admin key
+#[derive(Clone, PulseValue)]
+pub struct AdminApi {
+    inner: Arc<AdminApiInner>,
+}
+
+struct AdminApiInner {
+    key: Option<Box<str>>,
+    odesli: OdesliClient,
+}
+
+impl fmt::Debug for AdminApi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdminApi")
+            .field("enabled", &self.inner.key.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl AdminApi {
+    pub fn init(args: ArcValue<Args>, odesli: OdesliClient) -> Self {
+        let inner = Arc::new(AdminApiInner {
+            key: args
+                .admin_api_key
+                .as_deref()
+                .filter(|key| !key.is_empty())
+                .map(Into::into),
+            odesli,
+        });
+
+        Self { inner }
+    }
+
+    fn authorize(&self, headers: &HeaderMap) -> Result<(), RestApiErr> {
+        let Some(expected) = &self.inner.key else {
+            return Err(RestApiErr::Disabled);
+        };
+
+        headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            // Compare in constant time, so the key can't be guessed from the response times
+            .filter(|key| bool::from(key.as_bytes().ct_eq(expected.as_bytes())))
+            .map(|_| ())
+            .ok_or(RestApiErr::Unauthorized)
+    }
+}
+
+#[derive(Deserialize)]
+struct EntryQuery {
+    /// Either a provider ID key or a link to the entity
+    key: Option<String>,
+}
+
+impl EntryQuery {
+    fn provider_id(&self) -> Result<ProviderId, RestApiErr> {
+        let key = self
+            .key
+            .as_deref()
+            .ok_or(RestApiErr::BadRequest("The key parameter is required"))?;
+
+        ProviderId::from_key_or_url(key).ok_or(RestApiErr::BadRequest(
+            "The key is neither a provider ID nor a supported link",
+        ))
+    }
+}
+
+#[derive(Deserialize)]
+struct ClearExpiredQuery {
+    /// In seconds
+    max_age: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct EntryBody<'a> {
+    key: String,
+    response: &'a OdesliResponse,
+}
+
+#[derive(Serialize)]
+struct ClearExpiredBody {
+    removed: usize,
+}
+
+#[instrument(level = "debug", skip_all)]
+async fn stats_handler(
+    AxumState(api): AxumState<AdminApi>,
+    headers: HeaderMap,
+) -> Result<Json<CacheStats>, RestApiErr> {
+    api.authorize(&headers)?;
+
+    Ok(Json(api.inner.odesli.cache_stats()))
+}
+
+#[instrument(level = "debug", skip_all)]
+async fn get_entry_handler(
+    AxumState(api): AxumState<AdminApi>,
+    headers: HeaderMap,
+    Query(query): Query<EntryQuery>,
+) -> Result<Json<serde_json::Value>, RestApiErr> {
+    api.authorize(&headers)?;
+    let provider_id = query.provider_id()?;
+
+    let response = api
+        .inner
+        .odesli
+        .inspect_cache_entry(&provider_id)
+        .ok_or(RestApiErr::NotCached)?;
+
+    let body = EntryBody {
+        key: provider_id.to_string(),
+        response: &response,
+    };
+
+    // Serialize here, since the body borrows from the response
+    serde_json::to_value(body)
+        .map(Json)
+        .map_err(|_| RestApiErr::Upstream)
+}
+
+#[instrument(level = "debug", skip_all)]
+async fn delete_entry_handler(
+    AxumState(api): AxumState<AdminApi>,
+    headers: HeaderMap,
+    Query(query): Query<EntryQuery>,
+) -> Result<Json<CacheEviction>, RestApiErr> {
+    api.authorize(&headers)?;
+    let provider_id = query.provider_id()?;
+
+    info!(%provider_id, "Evicting cache entry on admin request");
+    Ok(Json(api.inner.odesli.evict(&provider_id).await))
+}
+
+#[instrument(level = "debug", skip_all)]
+async fn clear_expired_handler(
+    AxumState(api): AxumState<AdminApi>,
+    headers: HeaderMap,
+    Query(query): Query<ClearExpiredQuery>,
+) -> Result<Json<ClearExpiredBody>, RestApiErr> {
+    api.authorize(&headers)?;
+    let max_age = query
+        .max_age
+        .ok_or(RestApiErr::BadRequest("The max_age parameter is required"))?;
+
+    info!(max_age, "Clearing expired cache entries on admin request");
+    let removed = api
+        .inner
+        .odesli
+        .clear_expired_cache_entries(Duration::from_secs(max_age));
+
+    Ok(Json(ClearExpiredBody { removed }))
+}
+
+pub(super) fn init_stats_route(api: AdminApi) -> ValueGroupEntry<HttpServeRoute> {
+    ValueGroupEntry(HttpServeRoute {
+        path: "/admin/cache",
+        router: MethodRouter::new().get(stats_handler).with_state(api),
+    })
+}
+
+pub(super) fn init_entry_route(api: AdminApi) -> ValueGroupEntry<HttpServeRoute> {
+    ValueGroupEntry(HttpServeRoute {
+        path: "/admin/cache/entry",
+        router: MethodRouter::new()
+            .get(get_entry_handler)
+            .delete(delete_entry_handler)
+            .with_state(api),
+    })
+}
+
+pub(super) fn init_clear_expired_route(api: AdminApi) -> ValueGroupEntry<HttpServeRoute> {
+    ValueGroupEntry(HttpServeRoute {
+        path: "/admin/cache/clear-expired",
+        router: MethodRouter::new()
+            .post(clear_expired_handler)
+            .with_state(api),
+    })
+}
@@ -0,0 +1,100 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::time::Duration;
+
+use axum::Json;
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+
+use crate::clients::odesli::{ApiClientErr, ApiErr};
+
+#[derive(Debug)]
+pub enum RestApiErr {
+    /// No API keys are configured
+    Disabled,
+    Unauthorized,
+    RateLimited(Duration),
+    BadRequest(&'static str),
+    NotFound,
+    /// The entry is not in the cache
+    NotCached,
+    /// The resolvers are rate limited themselves
+    Unavailable,
+    Upstream,
+    ColourExtraction,
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: &'static str,
+}
+
+impl RestApiErr {
+    fn status(&self) -> StatusCode {
+        match self {
+            RestApiErr::Disabled | RestApiErr::NotFound | RestApiErr::NotCached => {
+                StatusCode::NOT_FOUND
+            }
+            RestApiErr::Unauthorized => StatusCode::UNAUTHORIZED,
+            RestApiErr::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            RestApiErr::BadRequest(_) => StatusCode::BAD_REQUEST,
+            RestApiErr::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            RestApiErr::Upstream => StatusCode::BAD_GATEWAY,
+            RestApiErr::ColourExtraction => StatusCode::UNPROCESSABLE_ENTITY,
+        }
+    }
+
+    pub(super) fn message(&self) -> &'static str {
+        match self {
+            RestApiErr::Disabled => "The API is not enabled",
+            RestApiErr::Unauthorized => "Missing or invalid API key",
+            RestApiErr::RateLimited(_) => "Rate limit exceeded",
+            RestApiErr::BadRequest(message) => message,
+            RestApiErr::NotFound => "No song or album was found for the link",
+            RestApiErr::NotCached => "The entry is not cached",
+            RestApiErr::Unavailable => "The link resolvers are currently rate limited",
+            RestApiErr::Upstream => "Failed to resolve the link",
+            RestApiErr::ColourExtraction => "Failed to extract the colour of the image",
+        }
+    }
+}
+
+impl From<ApiErr> for RestApiErr {
+    fn from(err: ApiErr) -> Self {
+        match err {
+            ApiErr::ClientError(ApiClientErr::UnknownEntity | ApiClientErr::InvalidEntityType) => {
+                RestApiErr::NotFound
+            }
+            ApiErr::RateLimitExceeded
+            | ApiErr::RateLimitBudgetExhausted
+            | ApiErr::RateLimitDeadlineExceeded => RestApiErr::Unavailable,
+            _ => RestApiErr::Upstream,
+        }
+    }
+}
+
+impl IntoResponse for RestApiErr {
+    fn into_response(self) -> Response {
+        let mut response = (
+            self.status(),
+            Json(ErrorBody {
+                error: self.message(),
+            }),
+        )
+            .into_response();
+
+        if let RestApiErr::RateLimited(retry_after) = self {
+            // Round up, so clients don't retry right before the window resets
+            let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+        }
+
+        response
+    }
+}
@@ -0,0 +1,367 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Json;
+use axum::extract::{Query, State as AxumState};
+use axum::http::{HeaderMap, header};
+use axum::routing::MethodRouter;
+use metronomos::builder::RuntimeBuilder;
+use metronomos_pulse::builder::ProvideError;
+use metronomos_pulse::value::{ArcValue, PulseValue, ValueGroupEntry};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+use url::{Host, Url};
+
+use crate::api::error::RestApiErr;
+use crate::api::ratelimit::KeyRateLimiter;
+use crate::args::Args;
+use crate::clients::colour::{ImageClient, RGBPixel};
+use crate::clients::expander::LinkExpander;
+use crate::clients::odesli::provider_id::canonicalize_url;
+use crate::clients::odesli::{EntityData, OdesliResponse};
+use crate::clients::resolver::ResolverClient;
+use crate::http_server::HttpServeRoute;
+use crate::interactions::is_supported_domain;
+
+mod admin;
+mod error;
+mod ratelimit;
+
+/// How long a resolve request waits for a rate limit token before failing as unavailable
+const RESOLVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The hosts the thumbnails returned by Odesli and the platform APIs are served from.
+///
+/// The colour endpoint only fetches images from these (and their subdomains), so it can't be
+/// used to reach internal services.
+const THUMBNAIL_HOSTS: &[&str] = &[
+    "scdn.co",
+    "spotifycdn.com",
+    "mzstatic.com",
+    "dzcdn.net",
+    "ytimg.com",
+    "ggpht.com",
+    "googleusercontent.com",
+    "sndcdn.com",
+    "resources.tidal.com",
+    "media-amazon.com",
+    "ssl-images-amazon.com",
+    "p-cdn.com",
+    "napster.com",
+    "bcbits.com",
+    "audius.co",
+    "audiomack.com",
+    "anghcdn.co",
+    "yandex.net",
+    "boomplaymusic.com",
+];
+
+/// JSON API exposing the link resolution and colour extraction of the bot to other tools.
+///
+/// Every request has to authenticate with one of the configured keys as a bearer token, the API
+/// is disabled if no keys are configured.
+#[derive(Clone, PulseValue)]
+pub struct RestApi {
+    inner: Arc<RestApiInner>,
+}
+
+struct RestApiInner {
+    keys: HashSet<Box<str>>,
+    ratelimiter: KeyRateLimiter,
+    resolver: ResolverClient,
+    expander: LinkExpander,
+    image: ImageClient,
+}
+
+impl fmt::Debug for RestApi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RestApi")
+            .field("keys", &self.inner.keys.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl RestApi {
+    pub fn init(
+        args: ArcValue<Args>,
+        resolver: ResolverClient,
+        expander: LinkExpander,
+        image: ImageClient,
+    ) -> Self {
+        let inner = Arc::new(RestApiInner {
+            keys: args
+                .rest_api_keys
+                .iter()
+                .filter(|key| !key.is_empty())
+                .map(|key| key.as_str().into())
+                .collect(),
+            ratelimiter: KeyRateLimiter::new(args.rest_api_rate_limit),
+            resolver,
+            expander,
+            image,
+        });
+
+        Self { inner }
+    }
+
+    /// Checks the API key of the request and counts the request against its rate limit
+    fn authorize(&self, headers: &HeaderMap) -> Result<(), RestApiErr> {
+        if self.inner.keys.is_empty() {
+            return Err(RestApiErr::Disabled);
+        }
+
+        let key = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .filter(|key| self.inner.keys.contains(*key))
+            .ok_or(RestApiErr::Unauthorized)?;
+
+        self.inner
+            .ratelimiter
+            .check(key)
+            .map_err(RestApiErr::RateLimited)
+    }
+
+    /// The dominant colour of the thumbnail of a resolved entity
+    async fn dominant_colour(&self, url: &String) -> Option<RGBPixel> {
+        self.inner
+            .image
+            .get_dominant_colour_from_url(url, Default::default(), None)
+            .await
+            .ok()
+    }
+}
+
+#[derive(Serialize)]
+struct ColourBody {
+    hex: String,
+    red: u8,
+    green: u8,
+    blue: u8,
+}
+
+impl From<RGBPixel> for ColourBody {
+    fn from(pixel: RGBPixel) -> Self {
+        Self {
+            hex: format!("#{:06x}", pixel.to_hex()),
+            red: pixel.red,
+            green: pixel.green,
+            blue: pixel.blue,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResolveBody<'a> {
+    /// Whether the response was served from the cache
+    cached: bool,
+    /// The data of the entity, picked from the highest priority platform
+    entity: EntityData,
+    colour: Option<ColourBody>,
+    #[serde(flatten)]
+    response: &'a OdesliResponse,
+}
+
+#[derive(Deserialize)]
+struct ResolveQuery {
+    url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ColourQuery {
+    image: Option<String>,
+}
+
+/// Parses the URL passed to the API, only allowing web links
+fn parse_url(url: Option<&str>, missing: &'static str) -> Result<Url, RestApiErr> {
+    let url = url.ok_or(RestApiErr::BadRequest(missing))?;
+    match Url::parse(url) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(url),
+        _ => Err(RestApiErr::BadRequest("The URL is invalid")),
+    }
+}
+
+/// Parses the image URL passed to the colour endpoint, only allowing HTTPS links to the hosts
+/// thumbnails are served from
+fn thumbnail_url(url: Option<&str>) -> Result<Url, RestApiErr> {
+    let url = parse_url(url, "The image parameter is required")?;
+
+    // IP addresses are parsed into their own host variants, so they are never allowed
+    let allowed = match url.host() {
+        Some(Host::Domain(domain)) => THUMBNAIL_HOSTS.iter().any(|host| {
+            domain == *host
+                || domain
+                    .strip_suffix(host)
+                    .is_some_and(|sub| sub.ends_with('.'))
+        }),
+        _ => false,
+    };
+    if url.scheme() != "https" || url.port().is_some() || !allowed {
+        return Err(RestApiErr::BadRequest(
+            "The image is not hosted by a supported platform",
+        ));
+    }
+
+    Ok(url)
+}
+
+/// Normalizes the expanded link like the commands do, rejecting links to unsupported platforms
+fn supported_link(url: &Url) -> Result<Url, RestApiErr> {
+    if !is_supported_domain(url) {
+        return Err(RestApiErr::BadRequest(
+            "The link is not from a supported platform",
+        ));
+    }
+
+    Ok(canonicalize_url(url))
+}
+
+#[instrument(level = "debug", skip_all)]
+async fn resolve_handler(
+    AxumState(api): AxumState<RestApi>,
+    headers: HeaderMap,
+    Query(query): Query<ResolveQuery>,
+) -> Result<Json<serde_json::Value>, RestApiErr> {
+    api.authorize(&headers)?;
+    let url = parse_url(query.url.as_deref(), "The url parameter is required")?;
+    let url = supported_link(&api.inner.expander.expand(&url).await)?;
+
+    debug!(link = %url, "Resolving link for the API");
+    // Fail with a 503 instead of keeping the client waiting while Odesli is rate limited
+    let response = api.inner.resolver.resolve(&url, RESOLVE_TIMEOUT).await?;
+    let entity = response.get_data();
+    let colour = match &entity.thumbnail_url {
+        Some(url) => api.dominant_colour(url).await,
+        None => None,
+    };
+
+    let body = ResolveBody {
+        cached: response.is_cached,
+        entity,
+        colour: colour.map(ColourBody::from),
+        response: &response,
+    };
+
+    // Serialize here, since the body borrows from the response
+    serde_json::to_value(body)
+        .map(Json)
+        .map_err(|_| RestApiErr::Upstream)
+}
+
+#[instrument(level = "debug", skip_all)]
+async fn colour_handler(
+    AxumState(api): AxumState<RestApi>,
+    headers: HeaderMap,
+    Query(query): Query<ColourQuery>,
+) -> Result<Json<ColourBody>, RestApiErr> {
+    api.authorize(&headers)?;
+    let url = thumbnail_url(query.image.as_deref())?;
+
+    api.inner
+        .image
+        .get_dominant_colour_from_requested_url(&url.to_string())
+        .await
+        .map(|colour| Json(colour.into()))
+        .map_err(|()| RestApiErr::ColourExtraction)
+}
+
+fn init_resolve_route(api: RestApi) -> ValueGroupEntry<HttpServeRoute> {
+    ValueGroupEntry(HttpServeRoute {
+        path: "/api/v1/resolve",
+        router: MethodRouter::new().get(resolve_handler).with_state(api),
+    })
+}
+
+fn init_colour_route(api: RestApi) -> ValueGroupEntry<HttpServeRoute> {
+    ValueGroupEntry(HttpServeRoute {
+        path: "/api/v1/colour",
+        router: MethodRouter::new().get(colour_handler).with_state(api),
+    })
+}
+
+pub fn provide_api(b: &mut RuntimeBuilder) -> Result<(), ProvideError> {
+    b.provide(RestApi::init)?;
+    b.provide(init_resolve_route)?;
+    b.provide(init_colour_route)?;
+
+    b.provide(admin::AdminApi::init)?;
+    b.provide(admin::init_stats_route)?;
+    b.provide(admin::init_entry_route)?;
+    b.provide(admin::init_clear_expired_route)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(url: &str) -> Result<String, &'static str> {
+        supported_link(&Url::parse(url).unwrap())
+            .map(String::from)
+            .map_err(|err| err.message())
+    }
+
+    #[test]
+    fn links_are_canonicalized() {
+        assert_eq!(
+            link("https://open.spotify.com/track/4cOdK2wGLETKBW3PvgPWqT?si=abc"),
+            Ok("https://open.spotify.com/track/4cOdK2wGLETKBW3PvgPWqT".to_string())
+        );
+        assert_eq!(
+            link("https://tidal.com/browse/track/180783437?u"),
+            Ok("https://tidal.com/track/180783437".to_string())
+        );
+    }
+
+    #[test]
+    fn unsupported_links_are_rejected() {
+        for url in [
+            "https://example.com/track/1",
+            "https://spotify.link/abc",
+            "http://127.0.0.1/track/1",
+        ] {
+            assert_eq!(
+                link(url),
+                Err("The link is not from a supported platform"),
+                "{}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn only_thumbnail_hosts_are_fetched() {
+        for url in [
+            "https://i.scdn.co/image/ab67616d0000b273255e131abc1410833be95673",
+            "https://is1-ssl.mzstatic.com/image/thumb/Music/cover.jpg/640x640bb.jpg",
+            "https://cdn-images.dzcdn.net/images/cover/1000x1000-000000-80-0-0.jpg",
+            "https://resources.tidal.com/images/cover/640x640.jpg",
+        ] {
+            assert!(thumbnail_url(Some(url)).is_ok(), "{}", url);
+        }
+
+        for url in [
+            "http://i.scdn.co/image/abc",
+            "https://i.scdn.co:8443/image/abc",
+            "https://127.0.0.1/image.jpg",
+            "https://169.254.169.254/latest/meta-data",
+            "https://[::1]/image.jpg",
+            "https://localhost/image.jpg",
+            "https://scdn.co.example.com/image.jpg",
+            "https://notscdn.co/image.jpg",
+            "https://tidal.com/image.jpg",
+        ] {
+            assert!(thumbnail_url(Some(url)).is_err(), "{}", url);
+        }
+    }
+}
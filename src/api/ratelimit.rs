@@ -0,0 +1,51 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+struct Window {
+    start: Instant,
+    count: u32,
+}
+
+/// Limits the number of requests each API key can make per minute, using a fixed window
+pub(super) struct KeyRateLimiter {
+    limit: u32,
+    windows: DashMap<Box<str>, Window>,
+}
+
+impl KeyRateLimiter {
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            windows: DashMap::new(),
+        }
+    }
+
+    /// Counts a request for the key, returns how long to wait if the key exceeded its limit
+    pub fn check(&self, key: &str) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut window = self.windows.entry(key.into()).or_insert(Window {
+            start: now,
+            count: 0,
+        });
+
+        if now.duration_since(window.start) >= WINDOW {
+            window.start = now;
+            window.count = 0;
+        }
+
+        if window.count >= self.limit {
+            return Err(WINDOW.saturating_sub(now.duration_since(window.start)));
+        }
+
+        window.count += 1;
+        Ok(())
+    }
+}
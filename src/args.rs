@@ -5,6 +5,7 @@
 use std::path::PathBuf;
 
 use clap::{Parser, ValueEnum};
+use url::Url;
 
 #[derive(Debug, Parser)]
 pub struct Args {
@@ -21,10 +22,86 @@ pub struct Args {
     /// The hourly limit for the Odesli API
     #[clap(long = "odesli-hourly-limit", env = "ODESLI_HOURLY_LIMIT")]
     pub odesli_hourly_limit: Option<u32>,
+    /// How long (in hours) Odesli responses are kept in the persistent database cache
+    #[clap(
+        long = "odesli-cache-ttl",
+        env = "ODESLI_CACHE_TTL",
+        default_value_t = 168
+    )]
+    pub odesli_cache_ttl: u64,
+    /// The approximate size (in MiB) the in-memory Odesli cache can grow to
+    #[clap(
+        long = "odesli-cache-size",
+        env = "ODESLI_CACHE_SIZE",
+        default_value_t = 64
+    )]
+    pub odesli_cache_size: usize,
+    /// How long (in minutes) links Odesli couldn't resolve are remembered
+    #[clap(
+        long = "odesli-negative-cache-ttl",
+        env = "ODESLI_NEGATIVE_CACHE_TTL",
+        default_value_t = 30
+    )]
+    pub odesli_negative_cache_ttl: u64,
+    /// The services links are resolved with, in the order they are tried in
+    #[clap(
+        long = "link-resolvers",
+        env = "LINK_RESOLVERS",
+        value_delimiter = ',',
+        default_value = "odesli,native"
+    )]
+    pub link_resolvers: Vec<LinkResolverKind>,
+    /// How often requests to Odesli and for thumbnails are attempted if they fail with a
+    /// transient error
+    #[clap(long, env = "REQUEST_ATTEMPTS", default_value_t = 3)]
+    pub request_attempts: u32,
+
+    /// The client id for the Spotify Web API, used to expand playlists
+    #[clap(long, env = "SPOTIFY_CLIENT_ID")]
+    pub spotify_client_id: Option<String>,
+    /// The client secret for the Spotify Web API
+    #[clap(long, env = "SPOTIFY_CLIENT_SECRET", hide_env_values = true)]
+    pub spotify_client_secret: Option<String>,
+    /// The client id for the Tidal API, used to fill in Tidal links missing from Odesli
+    #[clap(long, env = "TIDAL_CLIENT_ID")]
+    pub tidal_client_id: Option<String>,
+    /// The client secret for the Tidal API
+    #[clap(long, env = "TIDAL_CLIENT_SECRET", hide_env_values = true)]
+    pub tidal_client_secret: Option<String>,
+    /// The developer token for the Apple Music API, used to expand playlists
+    #[clap(long, env = "APPLE_MUSIC_TOKEN", hide_env_values = true)]
+    pub apple_music_token: Option<String>,
+
+    /// Whether the privileged message content intent is enabled for the bot, without it
+    /// auto-share only sees links in messages mentioning the bot and Find Links can't page
+    /// beyond the first links of a message
+    #[clap(long, env = "MESSAGE_CONTENT_INTENT")]
+    pub message_content_intent: bool,
+    /// The minimum time (in seconds) between two automatic replies in the same channel
+    #[clap(long, env = "AUTO_SHARE_COOLDOWN", default_value_t = 30)]
+    pub auto_share_cooldown: u64,
 
     /// The port the metrics server will listen on
     #[clap(long, env = "METRICS_PORT", default_value_t = 8481)]
     pub metrics_port: u16,
+    /// The public URL the http server is reachable at, cards link to the landing pages served
+    /// there instead of song.link when set
+    #[clap(long, env = "PUBLIC_URL")]
+    pub public_url: Option<Url>,
+    /// The keys that can be used to access the REST API, the API is disabled if none are set
+    #[clap(
+        long,
+        env = "REST_API_KEYS",
+        value_delimiter = ',',
+        hide_env_values = true
+    )]
+    pub rest_api_keys: Vec<String>,
+    /// The number of requests each REST API key can make per minute
+    #[clap(long, env = "REST_API_RATE_LIMIT", default_value_t = 60)]
+    pub rest_api_rate_limit: u32,
+    /// The key for the admin endpoints of the http server, they are disabled if it isn't set
+    #[clap(long, env = "ADMIN_API_KEY", hide_env_values = true)]
+    pub admin_api_key: Option<String>,
 
     /// The database url to send the metrics to
     #[clap(long, env = "DATABASE_URL", hide_env_values = true)]
@@ -43,6 +120,15 @@ pub struct Args {
     pub color_config: Option<PathBuf>,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
+pub enum LinkResolverKind {
+    /// The Odesli (song.link) API
+    Odesli,
+    /// The APIs of Spotify, Deezer and Apple Music, matching songs by ISRC and albums by UPC.
+    /// Requires the credentials of the platforms the links are from
+    Native,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
 pub enum LogFormat {
     /// Logfmt with ANSI color codes.
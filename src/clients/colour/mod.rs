@@ -11,13 +11,17 @@ use std::sync::Arc;
 use image::DynamicImage;
 use image::imageops::FilterType;
 use metronomos_pulse::value::{ArcValue, PulseValue};
-use tracing::{Instrument, debug, debug_span, instrument};
+use reqwest::Response;
+use tokio::time::Instant;
+use tracing::{Instrument, debug, debug_span, instrument, warn};
 use url::Host;
 
+use crate::args::Args;
+use crate::clients::retry::RetryPolicy;
 use crate::color_config::ColorConfig;
 use crate::constants::colour_consts;
 use crate::metrics::MetricsStore;
-use crate::metrics::labels::{Method, ThirdPartyLabels};
+use crate::metrics::labels::{Method, ThirdPartyApi, ThirdPartyLabels};
 use crate::util::EmptyResult;
 use crate::util::error::expect_warn;
 use crate::util::metric_utils::{HasHistogramFamilyExt, TimeFutureExt, UnpackErr};
@@ -71,6 +75,7 @@ pub struct ImageClient {
 struct ImageClientInner {
     client: reqwest::Client,
     options: ColorConfig,
+    retry_policy: RetryPolicy,
     metrics: MetricsStore,
 }
 
@@ -85,12 +90,14 @@ impl fmt::Debug for ImageClient {
 impl ImageClient {
     pub fn init(
         client: reqwest::Client,
+        args: ArcValue<Args>,
         options: ArcValue<ColorConfig>,
         metrics: MetricsStore,
     ) -> Self {
         let inner = Arc::new(ImageClientInner {
             client,
-            options: options.deref().clone(),
+            options: *options.deref(),
+            retry_policy: RetryPolicy::with_max_attempts(args.request_attempts),
             metrics,
         });
 
@@ -138,32 +145,48 @@ impl ImageClient {
             .ok_or(())
     }
 
+    /// Fetches the image and extracts its dominant colour, no retry of the request is started
+    /// that would end after the deadline
     #[instrument(level = "debug", skip_all)]
     pub async fn get_dominant_colour_from_url(
         &self,
         url: &String,
         options_override: OptionsOverride,
+        deadline: Option<Instant>,
     ) -> EmptyResult<RGBPixel> {
-        let image = self.fetch_image(url).await?;
+        let image = self.fetch_image(url, deadline).await?;
         self.get_dominant_colour(&image, options_override)
     }
 
+    /// Like [`Self::get_dominant_colour_from_url`] for images requested by API clients, whose
+    /// requests are all reported under the same metrics label
     #[instrument(level = "debug", skip_all)]
-    pub async fn fetch_image(&self, url: &String) -> EmptyResult<DynamicImage> {
-        debug!(url, "Fetching image");
+    pub async fn get_dominant_colour_from_requested_url(
+        &self,
+        url: &String,
+    ) -> EmptyResult<RGBPixel> {
+        let image = self
+            .fetch_image_labelled(url, None, Some(REQUESTED_IMAGE_LABEL))
+            .await?;
+        self.get_dominant_colour(&image, Default::default())
+    }
 
-        let req = self
-            .inner
-            .client
-            .get(url)
-            .build()
-            .map_err(expect_warn!("Failed to build thumbnail request"))?;
-
-        let metrics_url = format!(
-            "{}://{}",
-            req.url().scheme(),
-            req.url().host().unwrap_or(Host::Domain("unknown.host"))
-        );
+    /// A single attempt of the thumbnail request, error statuses are returned as errors
+    async fn request_image(
+        &self,
+        url: &String,
+        metrics_label: Option<&'static str>,
+    ) -> Result<Response, reqwest::Error> {
+        let req = self.inner.client.get(url).build()?;
+
+        let metrics_url = match metrics_label {
+            Some(label) => Cow::Borrowed(label),
+            None => Cow::Owned(format!(
+                "{}://{}",
+                req.url().scheme(),
+                req.url().host().unwrap_or(Host::Domain("unknown.host"))
+            )),
+        };
 
         let (resp, diff) = self
             .inner
@@ -172,27 +195,53 @@ impl ImageClient {
             .instrument(debug_span!("http_request"))
             .time()
             .await
-            .unpack_err()
-            .map_err(expect_warn!("Failed to fetch thumbnail"))?;
+            .unpack_err()?;
 
         self.inner.metrics.observe_duration(
             ThirdPartyLabels {
                 method: Method::GET,
-                url: Cow::from(metrics_url),
+                url: metrics_url,
                 status: resp.status().into(),
             },
             diff,
         );
 
-        const EMPTY: &[u8] = &[];
-        let bytes = resp
-            .bytes()
+        resp.error_for_status()
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    pub async fn fetch_image(
+        &self,
+        url: &String,
+        deadline: Option<Instant>,
+    ) -> EmptyResult<DynamicImage> {
+        self.fetch_image_labelled(url, deadline, None).await
+    }
+
+    async fn fetch_image_labelled(
+        &self,
+        url: &String,
+        deadline: Option<Instant>,
+        metrics_label: Option<&'static str>,
+    ) -> EmptyResult<DynamicImage> {
+        debug!(url, "Fetching image");
+
+        let resp = self
+            .inner
+            .retry_policy
+            .run(
+                ThirdPartyApi::Thumbnail,
+                deadline,
+                &self.inner.metrics,
+                || self.request_image(url, metrics_label),
+            )
             .await
-            .map_err(expect_warn!("Failed to read thumbnail bytes"));
-        let mut img =
-            image::load_from_memory(bytes.as_deref().unwrap_or(EMPTY)).map_err(expect_warn!(
-                "Failed to parse image, url may have pointed to a file that wasn't an image"
-            ))?;
+            .map_err(expect_warn!("Failed to fetch thumbnail"))?;
+
+        let bytes = read_capped(resp, colour_consts::MAX_IMAGE_BYTES).await?;
+        let mut img = image::load_from_memory(&bytes).map_err(expect_warn!(
+            "Failed to parse image, url may have pointed to a file that wasn't an image"
+        ))?;
 
         if (colour_consts::MAX_IMAGE_SIZE < img.width())
             | (colour_consts::MAX_IMAGE_SIZE < img.height())
@@ -208,3 +257,35 @@ impl ImageClient {
         Ok(img)
     }
 }
+
+/// The metrics label of images requested by API clients, since their hosts are arbitrary
+const REQUESTED_IMAGE_LABEL: &str = "requested://image";
+
+/// Reads the body of the response, failing as soon as it is larger than the limit
+async fn read_capped(mut resp: Response, limit: usize) -> EmptyResult<Vec<u8>> {
+    if resp
+        .content_length()
+        .is_some_and(|length| length > limit as u64)
+    {
+        warn!(
+            length = resp.content_length(),
+            "Thumbnail is too large, not reading it"
+        );
+        return Err(());
+    }
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = resp
+        .chunk()
+        .await
+        .map_err(expect_warn!("Failed to read thumbnail bytes"))?
+    {
+        if bytes.len() + chunk.len() > limit {
+            warn!("Thumbnail is too large, stopped reading it");
+            return Err(());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(bytes)
+}
@@ -7,6 +7,7 @@ use std::sync::Arc;
 use tracing::{error, info, instrument};
 use twilight_gateway::{ConfigBuilder as ShardConfigBuilder, Shard, create_iterator};
 use twilight_model::channel::message::AllowedMentions;
+use twilight_model::gateway::Intents;
 use twilight_model::id::Id;
 use twilight_model::id::marker::ApplicationMarker;
 
@@ -23,6 +24,7 @@ pub struct DiscordClient {
 struct DiscordClientInner {
     client: twilight_http::Client,
     bot_id: Id<ApplicationMarker>,
+    intents: Intents,
 }
 
 impl fmt::Debug for DiscordClient {
@@ -30,6 +32,7 @@ impl fmt::Debug for DiscordClient {
         f.debug_struct("DiscordClient")
             .field("client", &self.inner.client)
             .field("bot_id", &self.inner.bot_id)
+            .field("intents", &self.inner.intents)
             .finish()
     }
 }
@@ -60,10 +63,16 @@ impl DiscordClient {
             user.id
         );
 
+        let mut intents = cluster_consts::GATEWAY_INTENTS;
+        if args.message_content_intent {
+            intents |= cluster_consts::MESSAGE_CONTENT_INTENT;
+        }
+
         Ok(Self {
             inner: Arc::new(DiscordClientInner {
                 client,
                 bot_id: user.id.cast(),
+                intents,
             }),
         })
     }
@@ -105,7 +114,7 @@ impl DiscordClient {
                 .token()
                 .expect("Token should be set")
                 .to_string(),
-            cluster_consts::GATEWAY_INTENTS,
+            self.inner.intents,
         )
         .presence(cluster_consts::presence())
         .build();
@@ -0,0 +1,505 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use metronomos_pulse::value::PulseValue;
+use reqwest::header::LOCATION;
+use tokio::time::timeout;
+use tracing::{Instrument, debug, debug_span, instrument};
+use url::Url;
+
+use crate::clients::odesli::provider_id::ProviderId;
+use crate::metrics::MetricsStore;
+use crate::metrics::labels::{Method, ThirdPartyLabels};
+use crate::util::metric_utils::{HasHistogramFamilyExt, TimeFutureExt, UnpackErr};
+
+/// Link shorteners and Odesli pages, only links on these domains are requested to expand them
+const SHORT_LINK_DOMAINS: &[&str] = &[
+    "spotify.link",
+    "on.soundcloud.com",
+    "link.deezer.com",
+    "amzn.to",
+    "song.link",
+    "album.link",
+    "odesli.co",
+];
+/// The number of redirects followed before giving up
+const MAX_HOPS: usize = 5;
+/// Links in commands are expanded before the interaction is acknowledged, which Discord only
+/// allows 3 seconds for
+const EXPAND_TIMEOUT: Duration = Duration::from_secs(2);
+const CACHE_TTL: Duration = Duration::from_hours(6);
+/// The number of expanded links kept before the cache is cleared
+const CACHE_CAPACITY: usize = 10_000;
+
+#[derive(Debug)]
+pub enum ExpandErr {
+    Reqwest(reqwest::Error),
+    /// The redirect pointed to something that isn't a web link
+    InvalidLocation,
+    /// The link was still on a short link domain after the maximum number of redirects
+    TooManyHops,
+    Timeout,
+}
+
+impl From<reqwest::Error> for ExpandErr {
+    fn from(err: reqwest::Error) -> Self {
+        ExpandErr::Reqwest(err)
+    }
+}
+
+impl fmt::Display for ExpandErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpandErr::Reqwest(err) => write!(f, "Reqwest error: {}", err),
+            ExpandErr::InvalidLocation => write!(f, "Redirect location is not a web link"),
+            ExpandErr::TooManyHops => write!(f, "Too many redirects"),
+            ExpandErr::Timeout => write!(f, "Expanding the link timed out"),
+        }
+    }
+}
+
+impl std::error::Error for ExpandErr {}
+
+/// Returns whether the link is on a domain the expander follows redirects for
+pub fn is_short_link(url: &Url) -> bool {
+    url.domain()
+        .map(|domain| domain.strip_prefix("www.").unwrap_or(domain))
+        .is_some_and(|domain| SHORT_LINK_DOMAINS.contains(&domain))
+}
+
+/// Expands short links to the links of the platforms they point to, by following their redirects
+#[derive(Clone, PulseValue)]
+pub struct LinkExpander {
+    inner: Arc<LinkExpanderInner>,
+}
+
+struct LinkExpanderInner {
+    /// Has to be configured to not follow redirects, so each hop can be checked
+    client: reqwest::Client,
+    cache: DashMap<Url, (Url, Instant)>,
+    metrics: MetricsStore,
+}
+
+impl fmt::Debug for LinkExpander {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinkExpander")
+            .field("client", &self.inner.client)
+            .field("cached", &self.inner.cache.len())
+            .finish()
+    }
+}
+
+impl LinkExpander {
+    pub fn init(client: reqwest::Client, metrics: MetricsStore) -> Self {
+        let inner = Arc::new(LinkExpanderInner {
+            client,
+            cache: DashMap::new(),
+            metrics,
+        });
+
+        Self { inner }
+    }
+
+    /// Expands the link if it is a short link, any other link is returned as is.
+    ///
+    /// If the expansion fails, the original link is returned, so it can still be validated as
+    /// usual.
+    #[instrument(level = "debug", skip(self), fields(link = %url))]
+    pub async fn expand(&self, url: &Url) -> Url {
+        if !is_short_link(url) {
+            return url.clone();
+        }
+
+        if let Some(entry) = self.inner.cache.get(url)
+            && entry.1.elapsed() < CACHE_TTL
+        {
+            debug!("Expanded link cache hit");
+            return entry.0.clone();
+        }
+
+        let expanded = match timeout(EXPAND_TIMEOUT, self.follow_redirects(url))
+            .await
+            .unwrap_or(Err(ExpandErr::Timeout))
+        {
+            Ok(expanded) => expanded,
+            Err(e) => {
+                debug!("Failed to expand link: {}", e);
+                return url.clone();
+            }
+        };
+        debug!(expanded = %expanded, "Expanded short link");
+
+        self.store(url.clone(), expanded.clone());
+        expanded
+    }
+
+    /// Follows the redirects until the link leaves the short link domains or reaches an Odesli
+    /// page we can take the platform link from
+    async fn follow_redirects(&self, url: &Url) -> Result<Url, ExpandErr> {
+        let mut current = url.clone();
+
+        for _ in 0..MAX_HOPS {
+            if !is_short_link(&current) {
+                return Ok(current);
+            }
+            if let Some(target) = odesli_page_target(&current) {
+                return Ok(target);
+            }
+
+            let Some(location) = self.redirect_location(&current).await? else {
+                // Not a redirect, e.g. an Odesli page
+                return Ok(current);
+            };
+
+            current = current
+                .join(&location)
+                .map_err(|_| ExpandErr::InvalidLocation)?;
+            if !matches!(current.scheme(), "http" | "https") {
+                return Err(ExpandErr::InvalidLocation);
+            }
+        }
+
+        match is_short_link(&current) {
+            true => Err(ExpandErr::TooManyHops),
+            false => Ok(current),
+        }
+    }
+
+    /// Requests the link, returning where it redirects to if it does
+    async fn redirect_location(&self, url: &Url) -> Result<Option<String>, ExpandErr> {
+        let metrics_url = format!("{}://{}", url.scheme(), url.host_str().unwrap_or_default());
+
+        let (resp, diff) = self
+            .inner
+            .client
+            .get(url.clone())
+            .send()
+            .instrument(debug_span!("http_request"))
+            .time()
+            .await
+            .unpack_err()?;
+        self.inner.metrics.observe_duration(
+            ThirdPartyLabels {
+                method: Method::GET,
+                url: Cow::from(metrics_url),
+                status: resp.status().into(),
+            },
+            diff,
+        );
+
+        if !resp.status().is_redirection() {
+            return Ok(None);
+        }
+
+        resp.headers()
+            .get(LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .map(|location| Some(location.to_string()))
+            .ok_or(ExpandErr::InvalidLocation)
+    }
+
+    fn store(&self, url: Url, expanded: Url) {
+        if self.inner.cache.len() >= CACHE_CAPACITY {
+            self.inner
+                .cache
+                .retain(|_, (_, stored)| stored.elapsed() < CACHE_TTL);
+        }
+        if self.inner.cache.len() >= CACHE_CAPACITY {
+            debug!("Expanded link cache is full, clearing it");
+            self.inner.cache.clear();
+        }
+
+        self.inner.cache.insert(url, (expanded, Instant::now()));
+    }
+}
+
+/// Returns the platform link an Odesli page is for.
+///
+/// Pages can either embed the original link in their path, e.g.
+/// `https://song.link/https://open.spotify.com/track/...`, or refer to the entity by its platform
+/// and ID, e.g. `https://song.link/s/<id>` or `https://album.link/us/i/<id>`. Custom pages of
+/// artists can't be resolved without the Odesli API, so they are left alone.
+fn odesli_page_target(url: &Url) -> Option<Url> {
+    let domain = url.domain()?;
+    let is_album = match domain.strip_prefix("www.").unwrap_or(domain) {
+        "song.link" | "odesli.co" => false,
+        "album.link" => true,
+        _ => return None,
+    };
+
+    if let Ok(embedded) = Url::parse(url.path().trim_start_matches('/')) {
+        return (matches!(embedded.scheme(), "http" | "https") && !is_short_link(&embedded))
+            .then_some(embedded);
+    }
+
+    let segments = url
+        .path_segments()?
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    let (platform, id) = match segments.as_slice() {
+        [platform, id] => (platform, id),
+        [country, platform, id] if country.len() == 2 => (platform, id),
+        _ => return None,
+    };
+
+    let provider = match platform.to_lowercase().as_str() {
+        "s" | "spotify" => "spotify",
+        "i" | "itunes" | "applemusic" => "apple_music",
+        "y" | "youtube" => "youtube",
+        "deezer" => "deezer",
+        "tidal" => "tidal",
+        _ => return None,
+    };
+    let kind = match (provider, is_album) {
+        (_, true) => "album",
+        ("youtube", false) => "video",
+        (_, false) => "track",
+    };
+
+    let provider_id: ProviderId = format!("{}:{}:{}", provider, kind, id).parse().ok()?;
+    Some(provider_id.to_canonical_url())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::Router;
+    use axum::extract::{Request, State};
+    use axum::http::{StatusCode, header};
+    use axum::response::{IntoResponse, Response};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// Serves the redirects of all short link domains, the client resolves them to this server
+    struct RedirectServer {
+        addr: SocketAddr,
+        requests: Arc<AtomicUsize>,
+    }
+
+    #[derive(Clone)]
+    struct ServerState {
+        /// Where each `host/path` redirects to, any other link is answered with a page
+        redirects: Arc<HashMap<String, String>>,
+        requests: Arc<AtomicUsize>,
+    }
+
+    async fn redirect(State(state): State<ServerState>, req: Request) -> Response {
+        state.requests.fetch_add(1, Ordering::Relaxed);
+        let host = req
+            .headers()
+            .get(header::HOST)
+            .and_then(|host| host.to_str().ok())
+            .unwrap_or_default();
+
+        match state.redirects.get(&format!("{}{}", host, req.uri())) {
+            Some(location) => (
+                StatusCode::MOVED_PERMANENTLY,
+                [(header::LOCATION, location.clone())],
+            )
+                .into_response(),
+            None => (StatusCode::OK, "<html></html>").into_response(),
+        }
+    }
+
+    impl RedirectServer {
+        async fn start(redirects: &[(&str, &str)]) -> Self {
+            let requests = Arc::new(AtomicUsize::new(0));
+            let state = ServerState {
+                redirects: Arc::new(
+                    redirects
+                        .iter()
+                        .map(|(from, to)| (from.to_string(), to.to_string()))
+                        .collect(),
+                ),
+                requests: requests.clone(),
+            };
+            let router = Router::new().fallback(redirect).with_state(state);
+
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(async move { axum::serve(listener, router).await });
+
+            Self { addr, requests }
+        }
+
+        /// An expander whose requests to the short link domains end up at this server
+        fn expander(&self) -> LinkExpander {
+            let client = SHORT_LINK_DOMAINS
+                .iter()
+                .fold(reqwest::Client::builder(), |builder, domain| {
+                    builder.resolve(domain, self.addr)
+                })
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .unwrap();
+
+            LinkExpander::init(client, MetricsStore::new())
+        }
+
+        fn requests(&self) -> usize {
+            self.requests.load(Ordering::Relaxed)
+        }
+    }
+
+    async fn expand(expander: &LinkExpander, url: &str) -> String {
+        expander.expand(&Url::parse(url).unwrap()).await.to_string()
+    }
+
+    const SPOTIFY_TRACK: &str = "https://open.spotify.com/track/4cOdK2wGLETKBW3PvgPWqT";
+
+    #[tokio::test]
+    async fn follows_redirects_to_the_platform() {
+        let server = RedirectServer::start(&[
+            ("spotify.link/abc", "http://link.deezer.com/s/def"),
+            (
+                "link.deezer.com/s/def",
+                "https://www.deezer.com/track/781592622",
+            ),
+        ])
+        .await;
+        let expander = server.expander();
+
+        assert_eq!(
+            expand(&expander, "http://spotify.link/abc").await,
+            "https://www.deezer.com/track/781592622"
+        );
+        assert_eq!(server.requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn resolves_relative_redirects() {
+        let server = RedirectServer::start(&[
+            ("amzn.to/abc", "/def"),
+            ("amzn.to/def", "https://music.amazon.com/albums/B00000"),
+        ])
+        .await;
+
+        assert_eq!(
+            expand(&server.expander(), "http://amzn.to/abc").await,
+            "https://music.amazon.com/albums/B00000"
+        );
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_too_many_hops() {
+        let server = RedirectServer::start(&[
+            ("spotify.link/a", "http://spotify.link/b"),
+            ("spotify.link/b", "http://spotify.link/a"),
+        ])
+        .await;
+
+        assert_eq!(
+            expand(&server.expander(), "http://spotify.link/a").await,
+            "http://spotify.link/a"
+        );
+        assert_eq!(server.requests(), MAX_HOPS);
+    }
+
+    #[tokio::test]
+    async fn rejects_redirects_that_arent_web_links() {
+        let server = RedirectServer::start(&[("spotify.link/abc", "spotify:track:abc")]).await;
+
+        assert_eq!(
+            expand(&server.expander(), "http://spotify.link/abc").await,
+            "http://spotify.link/abc"
+        );
+    }
+
+    #[tokio::test]
+    async fn caches_expanded_links() {
+        let server = RedirectServer::start(&[("spotify.link/abc", SPOTIFY_TRACK)]).await;
+        let expander = server.expander();
+
+        assert_eq!(
+            expand(&expander, "http://spotify.link/abc").await,
+            SPOTIFY_TRACK
+        );
+        assert_eq!(
+            expand(&expander, "http://spotify.link/abc").await,
+            SPOTIFY_TRACK
+        );
+        assert_eq!(server.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn only_requests_short_links() {
+        let server = RedirectServer::start(&[]).await;
+
+        assert_eq!(
+            expand(&server.expander(), SPOTIFY_TRACK).await,
+            SPOTIFY_TRACK
+        );
+        assert_eq!(server.requests(), 0);
+    }
+
+    #[tokio::test]
+    async fn takes_the_platform_link_from_odesli_pages() {
+        let server = RedirectServer::start(&[(
+            "spotify.link/abc",
+            "http://song.link/s/4cOdK2wGLETKBW3PvgPWqT",
+        )])
+        .await;
+        let expander = server.expander();
+
+        let cases = [
+            ("http://spotify.link/abc", SPOTIFY_TRACK),
+            (
+                "https://song.link/https://open.spotify.com/track/4cOdK2wGLETKBW3PvgPWqT",
+                SPOTIFY_TRACK,
+            ),
+            ("https://song.link/s/4cOdK2wGLETKBW3PvgPWqT", SPOTIFY_TRACK),
+            (
+                "https://album.link/s/6XhjNHCyCDyyGJRM5mg40G",
+                "https://open.spotify.com/album/6XhjNHCyCDyyGJRM5mg40G",
+            ),
+            (
+                "https://song.link/i/1559523359",
+                "https://music.apple.com/us/song/1559523359",
+            ),
+            (
+                "https://album.link/us/i/1559523357",
+                "https://music.apple.com/us/album/1559523357",
+            ),
+            (
+                "https://odesli.co/deezer/781592622",
+                "https://www.deezer.com/track/781592622",
+            ),
+            (
+                "https://song.link/y/dQw4w9WgXcQ",
+                "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            ),
+        ];
+
+        for (url, expected) in cases {
+            assert_eq!(expand(&expander, url).await, expected, "{}", url);
+        }
+        // Only the short link had to be requested
+        assert_eq!(server.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn leaves_other_odesli_pages_alone() {
+        let server = RedirectServer::start(&[]).await;
+        let expander = server.expander();
+
+        for url in [
+            "http://song.link/rickastley/never-gonna-give-you-up",
+            "http://song.link/x/4cOdK2wGLETKBW3PvgPWqT",
+            "http://album.link/i/not-an-id",
+        ] {
+            assert_eq!(expand(&expander, url).await, url);
+        }
+    }
+}
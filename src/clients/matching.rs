@@ -0,0 +1,543 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::fmt;
+use std::sync::Arc;
+
+use futures_util::future::{BoxFuture, join_all};
+use tracing::{debug, instrument};
+use url::Url;
+
+use crate::clients::odesli::provider_id::ProviderId;
+use crate::clients::odesli::{APIProvider, Entity, Links, OdesliResponse, Platform};
+use crate::clients::platforms::{
+    EntityKind, MATCHABLE_PLATFORMS, PlatformApiErr, PlatformEntity, PlatformsClient,
+};
+
+/// Where the metadata used for matching comes from, [`PlatformsClient`] queries the platform
+/// APIs while other implementations can answer from recorded responses.
+pub trait MetadataSource: Send + Sync {
+    /// Whether the metadata of the entity can be looked up
+    fn supports_entity(&self, id: &ProviderId) -> bool;
+
+    /// Whether entities can be looked up by their ISRC/UPC on the platform
+    fn supports_code_lookup(&self, platform: &Platform) -> bool;
+
+    fn fetch_entity<'a>(
+        &'a self,
+        id: &'a ProviderId,
+    ) -> BoxFuture<'a, Result<PlatformEntity, PlatformApiErr>>;
+
+    fn find_by_code<'a>(
+        &'a self,
+        platform: &'a Platform,
+        kind: EntityKind,
+        code: &'a str,
+    ) -> BoxFuture<'a, Result<PlatformEntity, PlatformApiErr>>;
+}
+
+impl MetadataSource for PlatformsClient {
+    fn supports_entity(&self, id: &ProviderId) -> bool {
+        PlatformsClient::supports_entity(self, id)
+    }
+
+    fn supports_code_lookup(&self, platform: &Platform) -> bool {
+        PlatformsClient::supports_code_lookup(self, platform)
+    }
+
+    fn fetch_entity<'a>(
+        &'a self,
+        id: &'a ProviderId,
+    ) -> BoxFuture<'a, Result<PlatformEntity, PlatformApiErr>> {
+        Box::pin(PlatformsClient::fetch_entity(self, id))
+    }
+
+    fn find_by_code<'a>(
+        &'a self,
+        platform: &'a Platform,
+        kind: EntityKind,
+        code: &'a str,
+    ) -> BoxFuture<'a, Result<PlatformEntity, PlatformApiErr>> {
+        Box::pin(PlatformsClient::find_by_code(self, platform, kind, code))
+    }
+}
+
+/// Matches songs by their ISRC and albums by their UPC across platforms, filling in the
+/// platforms missing from a response.
+#[derive(Clone)]
+pub struct Matcher {
+    source: Arc<dyn MetadataSource>,
+}
+
+impl fmt::Debug for Matcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Matcher").finish_non_exhaustive()
+    }
+}
+
+impl Matcher {
+    pub fn new(source: impl MetadataSource + 'static) -> Self {
+        Self::with_source(Arc::new(source))
+    }
+
+    pub fn with_source(source: Arc<dyn MetadataSource>) -> Self {
+        Self { source }
+    }
+
+    /// Looks up the ISRC/UPC of the response and adds the platforms it is missing
+    #[instrument(level = "debug", skip_all, fields(entity = response.entity_unique_id))]
+    pub async fn fill_gaps(&self, response: &mut OdesliResponse) {
+        if self.missing_platforms(response).next().is_none() {
+            return;
+        }
+
+        let Some(source) = self.find_source(response).await else {
+            debug!("No platform the ISRC/UPC could be looked up on, not filling gaps");
+            return;
+        };
+        let Some(code) = source.code.clone() else {
+            debug!("Entity has no ISRC/UPC, not filling gaps");
+            return;
+        };
+
+        record_code(response, &source);
+        self.add_matches(response, source.kind, &code).await;
+    }
+
+    /// Looks up the entity with the ISRC/UPC on the platforms missing from the response and adds
+    /// the ones that were found
+    pub async fn add_matches(&self, response: &mut OdesliResponse, kind: EntityKind, code: &str) {
+        let lookups = self
+            .missing_platforms(response)
+            .map(|platform| self.source.find_by_code(platform, kind, code));
+
+        let matches = join_all(lookups).await;
+        let mut added = 0;
+        for result in matches {
+            match result {
+                Ok(entity) => {
+                    insert_entity(response, entity);
+                    added += 1;
+                }
+                Err(e) => debug!("No match found on a platform: {}", e),
+            }
+        }
+        debug!("Matched the entity on {} more platforms", added);
+    }
+
+    fn missing_platforms<'a>(
+        &'a self,
+        response: &'a OdesliResponse,
+    ) -> impl Iterator<Item = &'static Platform> + 'a {
+        MATCHABLE_PLATFORMS.iter().filter(|platform| {
+            !response.links_by_platform.contains_key(platform)
+                && self.source.supports_code_lookup(platform)
+        })
+    }
+
+    /// Fetches the metadata of the first linked entity we can look up the ISRC/UPC of
+    async fn find_source(&self, response: &OdesliResponse) -> Option<PlatformEntity> {
+        let kind = response
+            .entities_by_unique_id
+            .get(&response.entity_unique_id)
+            .and_then(|entity| EntityKind::from_odesli(&entity.kind))?;
+
+        let candidates = MATCHABLE_PLATFORMS
+            .iter()
+            .filter_map(|platform| response.links_by_platform.get(platform))
+            .filter_map(|links| ProviderId::parse_url(&Url::parse(&links.url).ok()?).ok())
+            .filter(|id| self.source.supports_entity(id));
+
+        for id in candidates {
+            match self.source.fetch_entity(&id).await {
+                Ok(entity) if entity.kind == kind => return Some(entity),
+                Ok(_) => debug!(?id, "Linked entity has a different type, skipping"),
+                Err(e) => debug!(?id, "Failed to fetch linked entity: {}", e),
+            }
+        }
+
+        None
+    }
+}
+
+/// Stores the ISRC/UPC on the entity it was looked up from
+fn record_code(response: &mut OdesliResponse, source: &PlatformEntity) {
+    let entity = response
+        .entities_by_unique_id
+        .values_mut()
+        .find(|entity| entity.platforms.contains(&source.platform) && entity.id == source.id);
+
+    if let Some(entity) = entity {
+        match source.kind {
+            EntityKind::Song => entity.isrc = source.code.clone(),
+            EntityKind::Album => entity.upc = source.code.clone(),
+        }
+    }
+}
+
+fn api_provider(platform: &Platform) -> APIProvider {
+    match platform {
+        Platform::AppleMusic | Platform::iTunes => APIProvider::iTunes,
+        Platform::Deezer => APIProvider::Deezer,
+        Platform::Spotify => APIProvider::Spotify,
+        Platform::Tidal => APIProvider::Tidal,
+        other => APIProvider::Other(other.to_string()),
+    }
+}
+
+/// Adds the entity and the link to it to the response, returning its unique ID.
+///
+/// The unique ID follows the format of Odesli, e.g. `SPOTIFY_SONG::<id>`.
+pub fn insert_entity(response: &mut OdesliResponse, entity: PlatformEntity) -> String {
+    let provider = api_provider(&entity.platform);
+    let unique_id = format!(
+        "{}_{}::{}",
+        provider.to_string().to_uppercase(),
+        entity.kind.as_str().to_uppercase(),
+        entity.id
+    );
+
+    let (isrc, upc) = match entity.kind {
+        EntityKind::Song => (entity.code, None),
+        EntityKind::Album => (None, entity.code),
+    };
+
+    response.links_by_platform.insert(
+        entity.platform.clone(),
+        Links {
+            entity_unique_id: unique_id.clone(),
+            url: entity.url,
+            native_app_uri_mobile: None,
+            native_app_uri_desktop: None,
+        },
+    );
+    response.entities_by_unique_id.insert(
+        unique_id.clone(),
+        Entity {
+            id: entity.id,
+            kind: entity.kind.as_str().to_string(),
+            title: Some(entity.title),
+            artist_name: entity.artist_name,
+            thumbnail_url: entity.thumbnail_url,
+            thumbnail_width: None,
+            thumbnail_height: None,
+            api_provider: provider,
+            platforms: vec![entity.platform],
+            isrc,
+            upc,
+        },
+    );
+
+    unique_id
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::clients::platforms::recorded_entities;
+
+    /// A response recorded from Odesli, linking Spotify, Apple Music and YouTube but neither
+    /// Deezer nor Tidal
+    const ODESLI_SONG: &str = include_str!("fixtures/odesli_song.json");
+
+    const SPOTIFY_SONG: &str = "https://open.spotify.com/track/4cOdK2wGLETKBW3PvgPWqT";
+    const SPOTIFY_ALBUM: &str = "https://open.spotify.com/album/6XhjNHCyCDyyGJRM5mg40G";
+    const ISRC: &str = "GBARL9300135";
+    const UPC: &str = "4050538669950";
+
+    /// Answers from the recorded entities, logging every request
+    struct FixtureSource {
+        entities: Vec<PlatformEntity>,
+        /// The platforms entities can be fetched from and looked up on by their ISRC/UPC
+        platforms: Vec<Platform>,
+        fetches: Mutex<Vec<ProviderId>>,
+        lookups: Mutex<Vec<Platform>>,
+    }
+
+    impl FixtureSource {
+        fn new(platforms: &[Platform]) -> Self {
+            let entities = recorded_entities();
+
+            Self {
+                entities,
+                platforms: platforms.to_vec(),
+                fetches: Mutex::default(),
+                lookups: Mutex::default(),
+            }
+        }
+
+        fn all() -> Self {
+            Self::new(MATCHABLE_PLATFORMS)
+        }
+
+        /// Drops the recorded entity, as if the platform no longer knew it
+        fn without(mut self, url: &str) -> Self {
+            self.entities.retain(|entity| entity.url != url);
+            self
+        }
+
+        /// Drops the ISRC/UPC of all entities, as if the platforms didn't return them
+        fn without_codes(mut self) -> Self {
+            for entity in &mut self.entities {
+                entity.code = None;
+            }
+            self
+        }
+
+        fn fetches(&self) -> Vec<ProviderId> {
+            self.fetches.lock().unwrap().clone()
+        }
+
+        fn lookups(&self) -> Vec<Platform> {
+            self.lookups.lock().unwrap().clone()
+        }
+    }
+
+    impl MetadataSource for FixtureSource {
+        fn supports_entity(&self, id: &ProviderId) -> bool {
+            self.platforms.contains(&id.platform())
+        }
+
+        fn supports_code_lookup(&self, platform: &Platform) -> bool {
+            self.platforms.contains(platform)
+        }
+
+        fn fetch_entity<'a>(
+            &'a self,
+            id: &'a ProviderId,
+        ) -> BoxFuture<'a, Result<PlatformEntity, PlatformApiErr>> {
+            self.fetches.lock().unwrap().push(id.clone());
+            let entity = self
+                .entities
+                .iter()
+                .find(|entity| provider_id(&entity.url) == *id)
+                .cloned()
+                .ok_or(PlatformApiErr::NotFound);
+
+            Box::pin(std::future::ready(entity))
+        }
+
+        fn find_by_code<'a>(
+            &'a self,
+            platform: &'a Platform,
+            kind: EntityKind,
+            code: &'a str,
+        ) -> BoxFuture<'a, Result<PlatformEntity, PlatformApiErr>> {
+            self.lookups.lock().unwrap().push(platform.clone());
+            let entity = self
+                .entities
+                .iter()
+                .find(|entity| {
+                    entity.platform == *platform
+                        && entity.kind == kind
+                        && entity.code.as_deref() == Some(code)
+                })
+                .cloned()
+                .ok_or(PlatformApiErr::NotFound);
+
+            Box::pin(std::future::ready(entity))
+        }
+    }
+
+    fn provider_id(url: &str) -> ProviderId {
+        ProviderId::parse_url(&Url::parse(url).unwrap()).unwrap()
+    }
+
+    fn odesli_song() -> OdesliResponse {
+        serde_json::from_str(ODESLI_SONG).expect("The fixture should be valid")
+    }
+
+    fn matcher(source: FixtureSource) -> (Matcher, Arc<FixtureSource>) {
+        let source = Arc::new(source);
+        (Matcher::with_source(source.clone()), source)
+    }
+
+    fn link<'a>(response: &'a OdesliResponse, platform: &Platform) -> Option<&'a str> {
+        response
+            .links_by_platform
+            .get(platform)
+            .map(|links| links.url.as_str())
+    }
+
+    fn entity<'a>(response: &'a OdesliResponse, platform: &Platform) -> &'a Entity {
+        let links = &response.links_by_platform[platform];
+        &response.entities_by_unique_id[&links.entity_unique_id]
+    }
+
+    #[tokio::test]
+    async fn fills_the_platforms_missing_from_odesli() {
+        let (matcher, source) = matcher(FixtureSource::all());
+        let mut response = odesli_song();
+        let apple_music = link(&response, &Platform::AppleMusic).map(str::to_string);
+
+        matcher.fill_gaps(&mut response).await;
+
+        assert_eq!(
+            link(&response, &Platform::Deezer),
+            Some("https://www.deezer.com/track/781592622")
+        );
+        assert_eq!(
+            link(&response, &Platform::Tidal),
+            Some("https://tidal.com/browse/track/180783437")
+        );
+        assert_eq!(
+            response.links_by_platform[&Platform::Deezer].entity_unique_id,
+            "DEEZER_SONG::781592622"
+        );
+        assert_eq!(
+            entity(&response, &Platform::Deezer).isrc.as_deref(),
+            Some(ISRC)
+        );
+        // The ISRC is recorded on the entity it was looked up from
+        assert_eq!(
+            entity(&response, &Platform::Spotify).isrc.as_deref(),
+            Some(ISRC)
+        );
+
+        // Links returned by Odesli are kept, only the missing platforms are looked up
+        assert_eq!(
+            link(&response, &Platform::AppleMusic),
+            apple_music.as_deref()
+        );
+        assert_eq!(source.fetches(), [provider_id(SPOTIFY_SONG)]);
+        let mut lookups = source.lookups();
+        lookups.sort_by_key(Platform::to_string);
+        assert_eq!(lookups, [Platform::Deezer, Platform::Tidal]);
+    }
+
+    #[tokio::test]
+    async fn complete_responses_are_not_looked_up() {
+        let mut response = odesli_song();
+        matcher(FixtureSource::all())
+            .0
+            .fill_gaps(&mut response)
+            .await;
+        let links = response.links_by_platform.len();
+
+        let (matcher, source) = matcher(FixtureSource::all());
+        matcher.fill_gaps(&mut response).await;
+
+        assert_eq!(response.links_by_platform.len(), links);
+        assert!(source.fetches().is_empty());
+        assert!(source.lookups().is_empty());
+    }
+
+    #[tokio::test]
+    async fn only_looks_up_supported_platforms() {
+        let (matcher, source) = matcher(FixtureSource::new(&[Platform::Spotify, Platform::Deezer]));
+        let mut response = odesli_song();
+
+        matcher.fill_gaps(&mut response).await;
+
+        assert!(link(&response, &Platform::Deezer).is_some());
+        assert!(link(&response, &Platform::Tidal).is_none());
+        assert_eq!(source.lookups(), [Platform::Deezer]);
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_the_next_linked_entity() {
+        let (matcher, source) = matcher(FixtureSource::all().without(SPOTIFY_SONG));
+        let mut response = odesli_song();
+
+        matcher.fill_gaps(&mut response).await;
+
+        assert!(link(&response, &Platform::Deezer).is_some());
+        assert_eq!(entity(&response, &Platform::Spotify).isrc, None);
+        assert_eq!(
+            entity(&response, &Platform::AppleMusic).isrc.as_deref(),
+            Some(ISRC)
+        );
+        assert_eq!(source.fetches().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn skips_linked_entities_of_another_kind() {
+        let (matcher, source) = matcher(FixtureSource::all());
+        let mut response = odesli_song();
+        // Odesli sometimes links the album of a song on a platform
+        response
+            .links_by_platform
+            .get_mut(&Platform::Spotify)
+            .unwrap()
+            .url = SPOTIFY_ALBUM.to_string();
+
+        matcher.fill_gaps(&mut response).await;
+
+        assert_eq!(
+            link(&response, &Platform::Deezer),
+            Some("https://www.deezer.com/track/781592622")
+        );
+        assert_eq!(
+            source.fetches(),
+            [
+                provider_id(SPOTIFY_ALBUM),
+                provider_id("https://music.apple.com/us/song/1559523359")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn entities_without_a_code_are_not_matched() {
+        let (matcher, source) = matcher(FixtureSource::all().without_codes());
+        let mut response = odesli_song();
+        let links = response.links_by_platform.len();
+
+        matcher.fill_gaps(&mut response).await;
+
+        assert_eq!(response.links_by_platform.len(), links);
+        assert!(source.lookups().is_empty());
+    }
+
+    #[tokio::test]
+    async fn responses_without_a_supported_platform_are_not_matched() {
+        let (matcher, source) = matcher(FixtureSource::new(&[Platform::Tidal]));
+        let mut response = odesli_song();
+
+        matcher.fill_gaps(&mut response).await;
+
+        assert!(link(&response, &Platform::Tidal).is_none());
+        assert!(source.fetches().is_empty());
+        assert!(source.lookups().is_empty());
+    }
+
+    #[tokio::test]
+    async fn albums_are_matched_by_their_upc() {
+        let (matcher, source) = matcher(FixtureSource::all());
+        let album = source
+            .entities
+            .iter()
+            .find(|entity| entity.url == SPOTIFY_ALBUM)
+            .cloned()
+            .unwrap();
+        let mut response = OdesliResponse {
+            entity_unique_id: String::new(),
+            user_country: "US".to_string(),
+            page_url: SPOTIFY_ALBUM.to_string(),
+            links_by_platform: HashMap::new(),
+            entities_by_unique_id: HashMap::new(),
+        };
+        response.entity_unique_id = insert_entity(&mut response, album);
+
+        matcher
+            .add_matches(&mut response, EntityKind::Album, UPC)
+            .await;
+
+        assert_eq!(
+            link(&response, &Platform::Deezer),
+            Some("https://www.deezer.com/album/216016282")
+        );
+        let deezer = entity(&response, &Platform::Deezer);
+        assert_eq!(
+            (deezer.isrc.as_deref(), deezer.upc.as_deref()),
+            (None, Some(UPC))
+        );
+        // Platforms the album wasn't found on are left out
+        assert!(link(&response, &Platform::Tidal).is_none());
+        assert!(link(&response, &Platform::AppleMusic).is_none());
+    }
+}
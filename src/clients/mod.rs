@@ -9,7 +9,13 @@ use crate::util::error::expect_err;
 
 pub mod colour;
 pub mod discord;
+pub mod expander;
+pub mod matching;
 pub mod odesli;
+pub mod platforms;
+pub mod resolver;
+pub mod retry;
+pub mod search;
 
 #[instrument(skip_all)]
 pub fn init_http_client() -> Result<reqwest::Client, BuildDependencyError> {
@@ -28,7 +34,11 @@ pub fn provide_clients(b: &mut RuntimeBuilder) -> Result<(), ProvideError> {
     b.provide_async(discord::DiscordClient::init)?;
 
     b.provide(colour::ImageClient::init)?;
+    b.provide(expander::LinkExpander::init)?;
+    b.provide(platforms::PlatformsClient::init)?;
     b.provide(odesli::OdesliClient::init)?;
+    b.provide(resolver::ResolverClient::init)?;
+    b.provide(search::SearchClient::init)?;
 
     Ok(())
 }
@@ -44,6 +44,8 @@ pub struct OdesliResponse {
     pub entities_by_unique_id: HashMap<String, Entity>,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EntityData {
     pub kind: Option<String>,
     pub title: Option<String>,
@@ -52,14 +54,6 @@ pub struct EntityData {
 }
 
 impl OdesliResponse {
-    pub fn links(&self) -> Vec<(String, String)> {
-        self.links_by_platform
-            .iter()
-            .filter(|(platform, _)| platform.is_enabled())
-            .map(|(p, l)| (p.to_string(), l.url.clone()))
-            .collect()
-    }
-
     pub fn get_data(&self) -> EntityData {
         let mut res = self
             .entities_by_unique_id
@@ -173,6 +167,14 @@ pub struct Entity {
     /// `["appleMusic", "itunes"]` since both those platforms/links are derived
     /// from this single entity
     pub platforms: Vec<Platform>,
+
+    /// The ISRC of the song, never returned by Odesli but added when the entity was matched with
+    /// the platform APIs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isrc: Option<String>,
+    /// The UPC of the album, see [`Self::isrc`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upc: Option<String>,
 }
 
 // For some reason song_link returns bandcamp links as ids so aaaaaaaaa
@@ -221,6 +223,46 @@ pub enum Platform {
 }
 
 impl Platform {
+    /// All platforms known to be returned by Odesli
+    pub const KNOWN: &'static [Platform] = &[
+        Self::Spotify,
+        Self::iTunes,
+        Self::AppleMusic,
+        Self::YouTube,
+        Self::YouTubeMusic,
+        Self::Google,
+        Self::GoogleStore,
+        Self::Pandora,
+        Self::Deezer,
+        Self::Tidal,
+        Self::AmazonStore,
+        Self::AmazonMusic,
+        Self::Soundcloud,
+        Self::Napster,
+        Self::Spinrilla,
+        Self::Audius,
+        Self::Audiomack,
+        Self::Anghami,
+        Self::Yandex,
+        Self::BoomPlay,
+    ];
+
+    /// Finds the known platform with the given display name, ignoring case and whitespace
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalize = |s: &str| {
+            s.chars()
+                .filter(|c| !c.is_whitespace())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        };
+
+        let name = normalize(name);
+        Self::KNOWN
+            .iter()
+            .find(|platform| normalize(&platform.to_string()) == name)
+            .cloned()
+    }
+
     pub fn is_enabled(&self) -> bool {
         match self {
             Self::AmazonStore => false, // Links currently don't work for some reason, so exclude them for now
@@ -4,25 +4,71 @@
  */
 
 use core::fmt;
+use std::collections::{BTreeMap, HashMap};
+use std::mem::size_of;
 use std::ops::Deref;
 use std::sync::Arc;
-use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use dashmap::DashMap;
-use tracing::warn;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tracing::{debug, warn};
 use url::Url;
 
-use crate::clients::odesli::OdesliResponse;
 use crate::clients::odesli::provider_id::ProviderId;
+use crate::clients::odesli::{Entity, Links, OdesliResponse, Platform};
+use crate::metrics::MetricsStore;
+use crate::metrics::labels::{
+    CacheEvictionLabels, CacheLookupLabels, CacheLookupResult, EvictionReason,
+};
 
 pub(super) struct DataCacheEntry {
     response: OdesliResponse,
+    /// The provider IDs the entry is stored under
+    keys: Vec<ProviderId>,
+    /// Whether the response was resolved with the platform APIs instead of Odesli
+    native: bool,
+    /// Approximate number of bytes the entry takes up
+    size: usize,
+    /// Position of the entry in the LRU order, only modified while holding the lock of the cache
+    tick: AtomicU64,
     last_access: AtomicU64,
 }
 
+/// In-memory cache of Odesli responses, bounded by their approximate size in bytes.
+///
+/// Each response is stored under the provider IDs of all platforms it links to. When the cache
+/// grows over its capacity, the least recently used responses are evicted together with all of
+/// their aliases.
 pub(super) struct OdesliCache {
-    cache: DashMap<ProviderId, Arc<DataCacheEntry>>,
+    state: Mutex<CacheState>,
+    capacity: usize,
+    metrics: MetricsStore,
+}
+
+struct CacheState {
+    aliases: HashMap<ProviderId, Arc<DataCacheEntry>>,
+    /// The entries ordered from least to most recently used, keyed by their tick
+    lru: BTreeMap<u64, Arc<DataCacheEntry>>,
+    next_tick: u64,
+    size: usize,
+}
+
+/// Statistics about the contents of the in-memory cache
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    /// The number of provider IDs responses can be looked up by
+    pub keys: usize,
+    /// The number of distinct responses
+    pub responses: usize,
+    /// The approximate size of the cached responses in bytes
+    pub size: usize,
+    /// The size in bytes above which responses are evicted
+    pub capacity: usize,
+    /// How long ago (in seconds) the least recently used response was accessed
+    pub oldest_access_secs: Option<u64>,
 }
 
 pub struct OdesliClientResponse {
@@ -31,50 +77,126 @@ pub struct OdesliClientResponse {
     inner: Arc<DataCacheEntry>,
 }
 
+impl CacheState {
+    /// Inserts the entry, returns false if it wasn't inserted as it would replace a response from
+    /// Odesli with a native one
+    fn insert(&mut self, entry: &Arc<DataCacheEntry>) -> bool {
+        // Replace the previous response for the entity as a whole, so no stale aliases of it are
+        // left behind
+        let previous = entry
+            .keys
+            .iter()
+            .filter_map(|key| self.aliases.get(key).cloned())
+            .collect::<Vec<_>>();
+        if entry.native && previous.iter().any(|previous| !previous.native) {
+            return false;
+        }
+
+        for previous in previous {
+            self.remove(&previous);
+        }
+
+        for key in &entry.keys {
+            self.aliases.insert(key.clone(), entry.clone());
+        }
+        self.size += entry.size;
+        self.touch(entry);
+        true
+    }
+
+    fn touch(&mut self, entry: &Arc<DataCacheEntry>) {
+        let tick = self.next_tick;
+        self.next_tick += 1;
+
+        // New entries start at tick 0, which is never handed out
+        let old_tick = entry.tick.swap(tick, Ordering::Relaxed);
+        if old_tick != 0 {
+            self.lru.remove(&old_tick);
+        }
+        self.lru.insert(tick, entry.clone());
+        entry
+            .last_access
+            .store(OdesliCache::current_timestamp(), Ordering::Relaxed);
+    }
+
+    /// Removes the entry and all of its aliases, returns the number of removed keys
+    fn remove(&mut self, entry: &Arc<DataCacheEntry>) -> usize {
+        if self
+            .lru
+            .remove(&entry.tick.load(Ordering::Relaxed))
+            .is_none()
+        {
+            return 0;
+        }
+        self.size -= entry.size;
+
+        let mut removed = 0;
+        for key in &entry.keys {
+            // Only remove the alias if it wasn't taken over by a newer entry
+            if self
+                .aliases
+                .get(key)
+                .is_some_and(|other| Arc::ptr_eq(other, entry))
+            {
+                self.aliases.remove(key);
+                removed += 1;
+            }
+        }
+
+        removed
+    }
+}
+
 impl OdesliCache {
-    pub fn new() -> Self {
+    pub fn new(capacity: usize, metrics: MetricsStore) -> Self {
         OdesliCache {
-            cache: DashMap::new(),
+            state: Mutex::new(CacheState {
+                aliases: HashMap::new(),
+                lru: BTreeMap::new(),
+                next_tick: 1,
+                size: 0,
+            }),
+            capacity,
+            metrics,
         }
     }
 
     pub fn store_response(&self, response: OdesliResponse) -> OdesliClientResponse {
+        self.store(response, false)
+    }
+
+    /// Stores a response resolved with the platform APIs, it never replaces a response from
+    /// Odesli and is ignored by [`Self::get_response`]
+    pub fn store_native_response(&self, response: OdesliResponse) -> OdesliClientResponse {
+        self.store(response, true)
+    }
+
+    fn store(&self, response: OdesliResponse, native: bool) -> OdesliClientResponse {
+        let mut keys = Vec::new();
+        for pid in provider_ids(&response) {
+            if !keys.contains(&pid) {
+                keys.push(pid);
+            }
+        }
+
+        let size = approximate_size(&response, &keys);
         let entry = Arc::new(DataCacheEntry {
             response,
+            keys,
+            native,
+            size,
+            tick: AtomicU64::new(0),
             last_access: AtomicU64::new(Self::current_timestamp()),
         });
 
-        let provider_ids = entry
-            .response
-            .links_by_platform
-            .iter()
-            .filter_map(|(platform, links)| {
-                if platform.is_enabled() {
-                    Some(links)
-                } else {
-                    None
-                }
-            })
-            .filter_map(|links| {
-                let url = match Url::parse(&links.url) {
-                    Ok(u) => u,
-                    Err(e) => {
-                        warn!("Failed to parse URL {}: {}", links.url, e);
-                        return None;
-                    }
-                };
-
-                match ProviderId::parse_url(&url) {
-                    Ok(pid) => Some(pid),
-                    Err(e) => {
-                        warn!("Failed to extract provider ID from URL: {}", e);
-                        None
-                    }
-                }
-            });
-
-        for pid in provider_ids {
-            self.cache.insert(pid, entry.clone());
+        if !entry.keys.is_empty() {
+            let mut state = self.state.lock();
+            if state.insert(&entry) {
+                self.evict_over_capacity(&mut state);
+                self.update_gauges(&state);
+            } else {
+                debug!("Not caching native response over the response from Odesli");
+            }
         }
 
         OdesliClientResponse {
@@ -83,27 +205,150 @@ impl OdesliCache {
         }
     }
 
+    /// Looks up the response from Odesli, native responses are treated as a miss so Odesli is
+    /// asked again once it is available
     pub fn get_response(&self, provider_id: &ProviderId) -> Option<OdesliClientResponse> {
-        if let Some(entry) = self.cache.get(provider_id) {
-            entry.last_access.store(
-                Self::current_timestamp(),
-                std::sync::atomic::Ordering::Relaxed,
-            );
-            Some(OdesliClientResponse {
+        self.lookup(provider_id, false)
+    }
+
+    /// Looks up the response, including the ones resolved with the platform APIs
+    pub fn get_any_response(&self, provider_id: &ProviderId) -> Option<OdesliClientResponse> {
+        self.lookup(provider_id, true)
+    }
+
+    fn lookup(
+        &self,
+        provider_id: &ProviderId,
+        include_native: bool,
+    ) -> Option<OdesliClientResponse> {
+        let mut state = self.state.lock();
+        let entry = state
+            .aliases
+            .get(provider_id)
+            .filter(|entry| include_native || !entry.native)
+            .cloned();
+
+        let result = match &entry {
+            Some(_) => CacheLookupResult::Hit,
+            None => CacheLookupResult::Miss,
+        };
+        self.metrics
+            .odesli_cache_lookups()
+            .get_or_create(&CacheLookupLabels { result })
+            .inc();
+
+        let entry = entry?;
+        state.touch(&entry);
+
+        Some(OdesliClientResponse {
+            is_cached: true,
+            inner: entry,
+        })
+    }
+
+    /// Looks up the response without marking it as accessed, including native responses
+    pub fn peek(&self, provider_id: &ProviderId) -> Option<OdesliClientResponse> {
+        self.state
+            .lock()
+            .aliases
+            .get(provider_id)
+            .map(|entry| OdesliClientResponse {
                 is_cached: true,
                 inner: entry.clone(),
             })
-        } else {
-            None
+    }
+
+    /// Removes the response from the cache under all of its provider IDs, returns the number of
+    /// removed keys
+    pub fn remove(&self, provider_id: &ProviderId) -> usize {
+        let mut state = self.state.lock();
+        let Some(entry) = state.aliases.get(provider_id).cloned() else {
+            return 0;
+        };
+
+        let removed = state.remove(&entry);
+        self.record_evictions(EvictionReason::Manual, 1);
+        self.update_gauges(&state);
+
+        removed
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let now = Self::current_timestamp();
+        let state = self.state.lock();
+
+        CacheStats {
+            keys: state.aliases.len(),
+            responses: state.lru.len(),
+            size: state.size,
+            capacity: self.capacity,
+            oldest_access_secs: state
+                .lru
+                .values()
+                .next()
+                .map(|entry| now.saturating_sub(entry.last_access.load(Ordering::Relaxed))),
         }
     }
 
-    pub fn clear_expired(&self, max_age: Duration) {
-        let max_last_access = Self::current_timestamp() - max_age.as_secs();
+    /// Removes all responses not accessed within `max_age`, returns the number of removed keys
+    pub fn clear_expired(&self, max_age: Duration) -> usize {
+        let max_last_access = Self::current_timestamp().saturating_sub(max_age.as_secs());
+        let mut state = self.state.lock();
 
-        self.cache.retain(|_, entry| {
-            max_last_access <= entry.last_access.load(std::sync::atomic::Ordering::Relaxed)
-        });
+        let mut removed = 0;
+        let mut evicted = 0;
+        // The entries are ordered by their last access, so stop at the first one still valid
+        while let Some(entry) = state.lru.values().next().cloned() {
+            if max_last_access <= entry.last_access.load(Ordering::Relaxed) {
+                break;
+            }
+            removed += state.remove(&entry);
+            evicted += 1;
+        }
+
+        self.record_evictions(EvictionReason::Expired, evicted);
+        self.update_gauges(&state);
+
+        removed
+    }
+
+    /// Evicts the least recently used responses until the cache fits its capacity, always keeping
+    /// the most recently used one
+    fn evict_over_capacity(&self, state: &mut CacheState) {
+        let mut evicted = 0;
+        while state.size > self.capacity && state.lru.len() > 1 {
+            let Some(entry) = state.lru.values().next().cloned() else {
+                break;
+            };
+            state.remove(&entry);
+            evicted += 1;
+        }
+
+        if evicted > 0 {
+            debug!(
+                evicted,
+                "Evicted responses from the cache to fit its capacity"
+            );
+            self.record_evictions(EvictionReason::Capacity, evicted);
+        }
+    }
+
+    fn record_evictions(&self, reason: EvictionReason, count: u64) {
+        if count == 0 {
+            return;
+        }
+
+        self.metrics
+            .odesli_cache_evictions()
+            .get_or_create(&CacheEvictionLabels { reason })
+            .inc_by(count);
+    }
+
+    fn update_gauges(&self, state: &CacheState) {
+        self.metrics
+            .odesli_cache_responses()
+            .set(state.lru.len() as u64);
+        self.metrics.odesli_cache_size().set(state.size as u64);
     }
 
     fn current_timestamp() -> u64 {
@@ -114,7 +359,120 @@ impl OdesliCache {
     }
 }
 
+fn option_size(value: &Option<String>) -> usize {
+    value.as_ref().map_or(0, String::capacity)
+}
+
+/// Approximates the number of bytes the response takes up in the cache, including its aliases
+fn approximate_size(response: &OdesliResponse, keys: &[ProviderId]) -> usize {
+    let links: usize = response
+        .links_by_platform
+        .values()
+        .map(|links| {
+            size_of::<Links>()
+                + links.entity_unique_id.capacity()
+                + links.url.capacity()
+                + option_size(&links.native_app_uri_mobile)
+                + option_size(&links.native_app_uri_desktop)
+        })
+        .sum();
+
+    let entities: usize = response
+        .entities_by_unique_id
+        .iter()
+        .map(|(key, entity)| {
+            size_of::<Entity>()
+                + key.capacity()
+                + entity.id.capacity()
+                + entity.kind.capacity()
+                + option_size(&entity.title)
+                + option_size(&entity.artist_name)
+                + option_size(&entity.thumbnail_url)
+                + option_size(&entity.isrc)
+                + option_size(&entity.upc)
+                + entity.platforms.capacity() * size_of::<Platform>()
+        })
+        .sum();
+
+    // Each alias is stored as a key of the map and in the entry, the length of the key string is a
+    // good enough estimate for the heap allocations of the IDs
+    let aliases: usize = keys
+        .iter()
+        .map(|key| 2 * (size_of::<ProviderId>() + key.to_string().len()))
+        .sum();
+
+    size_of::<DataCacheEntry>()
+        + response.entity_unique_id.capacity()
+        + response.user_country.capacity()
+        + response.page_url.capacity()
+        + links
+        + entities
+        + aliases
+}
+
+/// Extracts the provider IDs of all enabled platforms the response links to.
+pub(super) fn provider_ids(response: &OdesliResponse) -> impl Iterator<Item = ProviderId> + '_ {
+    enabled_links(response).filter_map(link_provider_id)
+}
+
+fn enabled_links(response: &OdesliResponse) -> impl Iterator<Item = &Links> + '_ {
+    response
+        .links_by_platform
+        .iter()
+        .filter_map(|(platform, links)| {
+            if platform.is_enabled() {
+                Some(links)
+            } else {
+                None
+            }
+        })
+}
+
+fn link_provider_id(links: &Links) -> Option<ProviderId> {
+    let url = match Url::parse(&links.url) {
+        Ok(u) => u,
+        Err(e) => {
+            warn!("Failed to parse URL {}: {}", links.url, e);
+            return None;
+        }
+    };
+
+    match ProviderId::parse_url(&url) {
+        Ok(pid) => Some(pid),
+        Err(e) => {
+            warn!("Failed to extract provider ID from URL: {}", e);
+            None
+        }
+    }
+}
+
 impl OdesliClientResponse {
+    /// A provider ID the response can be looked up by in the cache.
+    ///
+    /// The links are stored in a hash map, so to return the same ID for every response of the
+    /// entity the links of the entity the response is for are preferred and ties are broken by
+    /// the smallest key.
+    pub fn provider_id(&self) -> Option<ProviderId> {
+        let smallest = |ids: Vec<ProviderId>| {
+            ids.into_iter()
+                .map(|id| (id.to_string(), id))
+                .min_by(|(a, _), (b, _)| a.cmp(b))
+                .map(|(_, id)| id)
+        };
+
+        let own = enabled_links(self)
+            .filter(|links| links.entity_unique_id == self.entity_unique_id)
+            .filter_map(link_provider_id)
+            .collect();
+
+        smallest(own).or_else(|| smallest(provider_ids(self).collect()))
+    }
+
+    /// Whether the response was resolved with the platform APIs instead of Odesli
+    pub fn is_native(&self) -> bool {
+        self.inner.native
+    }
+
     pub fn duplicate(&self) -> Self {
         OdesliClientResponse {
             // Duplicating the response dictates that it is cached.
@@ -137,3 +495,276 @@ impl fmt::Debug for OdesliClientResponse {
         fmt::Debug::fmt(self.deref(), f)
     }
 }
+
+#[cfg(test)]
+impl OdesliClientResponse {
+    /// Wraps the response without storing it in any cache
+    pub fn uncached(response: OdesliResponse) -> Self {
+        OdesliClientResponse {
+            is_cached: false,
+            inner: Arc::new(DataCacheEntry {
+                response,
+                keys: Vec::new(),
+                native: false,
+                size: 0,
+                tick: AtomicU64::new(0),
+                last_access: AtomicU64::new(0),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::matching::insert_entity;
+    use crate::clients::platforms::{EntityKind, PlatformEntity};
+
+    const SPOTIFY_URL: &str = "https://open.spotify.com/track/4cOdK2wGLETKBW3PvgPWqT";
+    const DEEZER_URL: &str = "https://www.deezer.com/track/3135556";
+
+    fn response(title: &str, links: &[(Platform, &str)]) -> OdesliResponse {
+        let mut response = OdesliResponse {
+            entity_unique_id: String::new(),
+            user_country: "US".to_string(),
+            page_url: String::new(),
+            links_by_platform: HashMap::new(),
+            entities_by_unique_id: HashMap::new(),
+        };
+        for (platform, url) in links {
+            response.entity_unique_id = insert_entity(
+                &mut response,
+                PlatformEntity {
+                    platform: platform.clone(),
+                    id: url.rsplit('/').next().unwrap().to_string(),
+                    kind: EntityKind::Song,
+                    title: title.to_string(),
+                    artist_name: None,
+                    thumbnail_url: None,
+                    url: url.to_string(),
+                    code: None,
+                },
+            );
+        }
+
+        response
+    }
+
+    fn provider_id(url: &str) -> ProviderId {
+        ProviderId::parse_url(&Url::parse(url).unwrap()).unwrap()
+    }
+
+    fn cached_title(response: Option<OdesliClientResponse>) -> Option<String> {
+        response.map(|response| response.get_data().title.unwrap())
+    }
+
+    #[test]
+    fn native_responses_are_only_returned_to_cache_lookups() {
+        let cache = OdesliCache::new(usize::MAX, MetricsStore::new());
+        let stored =
+            cache.store_native_response(response("Native", &[(Platform::Deezer, DEEZER_URL)]));
+        assert!(stored.is_native());
+
+        let id = provider_id(DEEZER_URL);
+        assert!(cache.get_response(&id).is_none());
+        assert_eq!(
+            cached_title(cache.get_any_response(&id)),
+            Some("Native".to_string())
+        );
+    }
+
+    #[test]
+    fn native_responses_dont_replace_odesli_responses() {
+        let cache = OdesliCache::new(usize::MAX, MetricsStore::new());
+        cache.store_response(response("Odesli", &[(Platform::Spotify, SPOTIFY_URL)]));
+        cache.store_native_response(response(
+            "Native",
+            &[
+                (Platform::Spotify, SPOTIFY_URL),
+                (Platform::Deezer, DEEZER_URL),
+            ],
+        ));
+
+        let spotify = provider_id(SPOTIFY_URL);
+        assert_eq!(
+            cached_title(cache.get_response(&spotify)),
+            Some("Odesli".to_string())
+        );
+        assert_eq!(
+            cached_title(cache.get_any_response(&spotify)),
+            Some("Odesli".to_string())
+        );
+        assert!(cache.get_any_response(&provider_id(DEEZER_URL)).is_none());
+        assert_eq!(cache.stats().responses, 1);
+    }
+
+    #[test]
+    fn odesli_responses_replace_native_responses() {
+        let cache = OdesliCache::new(usize::MAX, MetricsStore::new());
+        cache.store_native_response(response(
+            "Native",
+            &[
+                (Platform::Spotify, SPOTIFY_URL),
+                (Platform::Deezer, DEEZER_URL),
+            ],
+        ));
+        cache.store_response(response("Odesli", &[(Platform::Spotify, SPOTIFY_URL)]));
+
+        assert_eq!(
+            cached_title(cache.get_response(&provider_id(SPOTIFY_URL))),
+            Some("Odesli".to_string())
+        );
+        // The native response is replaced as a whole, including its other aliases
+        assert!(cache.get_any_response(&provider_id(DEEZER_URL)).is_none());
+        assert_eq!(cache.stats().responses, 1);
+    }
+
+    #[test]
+    fn provider_id_prefers_the_entity_of_the_response() {
+        let spotify_last = response(
+            "Song",
+            &[
+                (Platform::Deezer, DEEZER_URL),
+                (Platform::Spotify, SPOTIFY_URL),
+            ],
+        );
+        assert_eq!(
+            OdesliClientResponse::uncached(spotify_last.clone()).provider_id(),
+            Some(provider_id(SPOTIFY_URL))
+        );
+
+        // Without a link to the entity the response is for, the smallest key is used
+        let mut unknown = spotify_last;
+        unknown.entity_unique_id = "UNKNOWN".to_string();
+        assert_eq!(
+            OdesliClientResponse::uncached(unknown).provider_id(),
+            Some(provider_id(DEEZER_URL))
+        );
+    }
+
+    fn song(n: u32) -> (OdesliResponse, ProviderId) {
+        let url = format!("https://open.spotify.com/track/{:0>22}", n);
+        (
+            response("Song", &[(Platform::Spotify, &url)]),
+            provider_id(&url),
+        )
+    }
+
+    fn lookups(metrics: &MetricsStore, result: CacheLookupResult) -> u64 {
+        metrics
+            .odesli_cache_lookups()
+            .get_or_create(&CacheLookupLabels { result })
+            .get()
+    }
+
+    fn evictions(metrics: &MetricsStore, reason: EvictionReason) -> u64 {
+        metrics
+            .odesli_cache_evictions()
+            .get_or_create(&CacheEvictionLabels { reason })
+            .get()
+    }
+
+    /// A cache with room for exactly `entries` responses returned by [`song`]
+    fn cache_for(entries: usize, metrics: &MetricsStore) -> OdesliCache {
+        let size = approximate_size(&song(0).0, &[song(0).1]);
+        OdesliCache::new(entries * size, metrics.clone())
+    }
+
+    #[test]
+    fn evicts_the_least_recently_used_responses() {
+        let metrics = MetricsStore::new();
+        let cache = cache_for(2, &metrics);
+        let [(a, a_id), (b, b_id), (c, c_id), (d, d_id)] = [1, 2, 3, 4].map(song);
+
+        cache.store_response(a);
+        cache.store_response(b);
+        // Looking up a response marks it as used, peeking doesn't
+        assert!(cache.get_response(&a_id).is_some());
+        cache.store_response(c);
+        assert!(cache.peek(&b_id).is_none());
+        assert!(cache.peek(&a_id).is_some());
+
+        cache.store_response(d);
+        assert!(cache.peek(&a_id).is_none());
+        assert!(cache.peek(&c_id).is_some());
+        assert!(cache.peek(&d_id).is_some());
+
+        let stats = cache.stats();
+        assert_eq!(stats.responses, 2);
+        assert_eq!(stats.keys, 2);
+        assert!(stats.size <= stats.capacity);
+        assert_eq!(evictions(&metrics, EvictionReason::Capacity), 2);
+    }
+
+    #[test]
+    fn evicts_all_aliases_of_a_response() {
+        let metrics = MetricsStore::new();
+        let both = response(
+            "Song",
+            &[
+                (Platform::Spotify, SPOTIFY_URL),
+                (Platform::Deezer, DEEZER_URL),
+            ],
+        );
+        let size = approximate_size(&both, &provider_ids(&both).collect::<Vec<_>>());
+        let cache = OdesliCache::new(size, metrics.clone());
+
+        cache.store_response(both);
+        assert_eq!(cache.stats().keys, 2);
+
+        let (other, other_id) = song(1);
+        cache.store_response(other);
+        assert!(cache.peek(&provider_id(SPOTIFY_URL)).is_none());
+        assert!(cache.peek(&provider_id(DEEZER_URL)).is_none());
+        assert!(cache.peek(&other_id).is_some());
+
+        let stats = cache.stats();
+        assert_eq!((stats.responses, stats.keys), (1, 1));
+        assert_eq!(evictions(&metrics, EvictionReason::Capacity), 1);
+    }
+
+    #[test]
+    fn clear_expired_stops_at_the_first_live_response() {
+        let metrics = MetricsStore::new();
+        let cache = cache_for(3, &metrics);
+        let [(a, a_id), (b, b_id), (c, c_id)] = [1, 2, 3].map(song);
+        cache.store_response(a);
+        cache.store_response(b);
+        cache.store_response(c);
+
+        // The last response is expired too, but comes after a live one in the LRU order
+        for id in [&a_id, &c_id] {
+            let entry = cache.peek(id).unwrap();
+            entry.inner.last_access.store(0, Ordering::Relaxed);
+        }
+
+        assert_eq!(cache.clear_expired(Duration::from_secs(3600)), 1);
+        assert!(cache.peek(&a_id).is_none());
+        assert!(cache.peek(&b_id).is_some());
+        assert!(cache.peek(&c_id).is_some());
+        assert_eq!(cache.stats().responses, 2);
+        assert_eq!(evictions(&metrics, EvictionReason::Expired), 1);
+    }
+
+    #[test]
+    fn counts_hits_misses_and_removals() {
+        let metrics = MetricsStore::new();
+        let cache = cache_for(2, &metrics);
+        let [(a, a_id), (_, b_id)] = [1, 2].map(song);
+        cache.store_response(a);
+
+        assert!(cache.get_response(&a_id).is_some());
+        assert!(cache.get_any_response(&a_id).is_some());
+        assert!(cache.get_response(&b_id).is_none());
+        // Peeking is not counted as a lookup
+        assert!(cache.peek(&b_id).is_none());
+        assert_eq!(lookups(&metrics, CacheLookupResult::Hit), 2);
+        assert_eq!(lookups(&metrics, CacheLookupResult::Miss), 1);
+
+        assert_eq!(cache.remove(&a_id), 1);
+        assert_eq!(cache.remove(&a_id), 0);
+        assert_eq!(evictions(&metrics, EvictionReason::Manual), 1);
+        assert_eq!(cache.stats().responses, 0);
+        assert_eq!(cache.stats().size, 0);
+    }
+}
@@ -10,19 +10,28 @@ use reqwest::{Response, StatusCode};
 use serde::Deserialize;
 use tracing::debug;
 
+use crate::clients::platforms::PlatformApiErr;
+use crate::clients::retry::Retryable;
+
 #[derive(Debug)]
 pub enum ApiErr {
     Reqwest(reqwest::Error),
     ClientError(ApiClientErr),
     RateLimitExceeded,
+    /// No rate limit token was available and the request was not allowed to wait for one
+    RateLimitBudgetExhausted,
+    /// No rate limit token could be acquired before the deadline of the request
+    RateLimitDeadlineExceeded,
     UnexpectedClientError(String),
     UnexpectedResponseStatus {
         status_code: StatusCode,
         text: String,
     },
+    /// The link was resolved with the platform APIs instead of Odesli, which failed
+    Platform(PlatformApiErr),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ApiClientErr {
     InvalidEntityType,
     UnknownEntity,
@@ -41,6 +50,10 @@ impl fmt::Display for ApiErr {
             ApiErr::Reqwest(err) => write!(f, "Reqwest error: {}", err),
             ApiErr::ClientError(client_err) => write!(f, "Client error: {}", client_err),
             ApiErr::RateLimitExceeded => write!(f, "Rate limit exceeded"),
+            ApiErr::RateLimitBudgetExhausted => write!(f, "Rate limit budget exhausted"),
+            ApiErr::RateLimitDeadlineExceeded => {
+                write!(f, "Rate limit token not available before the deadline")
+            }
             ApiErr::UnexpectedClientError(details) => {
                 write!(f, "Unexpected client error response: {}", details)
             }
@@ -49,12 +62,42 @@ impl fmt::Display for ApiErr {
                 "Unexpected response status {}, with body: \"{}\"",
                 status_code, text
             ),
+            ApiErr::Platform(err) => write!(f, "Platform API error: {}", err),
         }
     }
 }
 
 impl Error for ApiErr {}
 
+impl ApiErr {
+    /// Whether the resolver couldn't answer at all, rather than answering with an error.
+    ///
+    /// This is the case if the service is down or rate limited us, or if the platform APIs can't
+    /// resolve the link. Running out of our own rate limit budget is not, the caller decides how
+    /// to handle that.
+    pub fn is_unavailable(&self) -> bool {
+        match self {
+            ApiErr::Reqwest(_) | ApiErr::RateLimitExceeded | ApiErr::Platform(_) => true,
+            ApiErr::UnexpectedResponseStatus { status_code, .. } => status_code.is_server_error(),
+            ApiErr::ClientError(_)
+            | ApiErr::UnexpectedClientError(_)
+            | ApiErr::RateLimitBudgetExhausted
+            | ApiErr::RateLimitDeadlineExceeded => false,
+        }
+    }
+}
+
+impl Retryable for ApiErr {
+    fn is_retryable(&self) -> bool {
+        match self {
+            ApiErr::Reqwest(err) => err.is_retryable(),
+            ApiErr::UnexpectedResponseStatus { status_code, .. } => status_code.is_server_error(),
+            // 429s are handled by the rate limiter, everything else won't change on a retry
+            _ => false,
+        }
+    }
+}
+
 impl ApiClientErr {
     fn from_code(code: &str) -> Self {
         match code {
@@ -111,3 +154,48 @@ impl<'de> Deserialize<'de> for ApiClientErr {
         Ok(ApiClientErr::from_code(err_response.code))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(code: u16) -> ApiErr {
+        ApiErr::UnexpectedResponseStatus {
+            status_code: StatusCode::from_u16(code).unwrap(),
+            text: String::new(),
+        }
+    }
+
+    #[test]
+    fn only_server_errors_are_retryable() {
+        let cases = [
+            (status(500), true),
+            (status(502), true),
+            (status(503), true),
+            (status(404), false),
+            (status(418), false),
+            (ApiErr::RateLimitExceeded, false),
+            (ApiErr::RateLimitBudgetExhausted, false),
+            (ApiErr::RateLimitDeadlineExceeded, false),
+            (ApiErr::ClientError(ApiClientErr::UnknownEntity), false),
+            (ApiErr::ClientError(ApiClientErr::InvalidEntityType), false),
+            (ApiErr::UnexpectedClientError(String::new()), false),
+            (ApiErr::Platform(PlatformApiErr::NotFound), false),
+        ];
+
+        for (err, retryable) in cases {
+            assert_eq!(err.is_retryable(), retryable, "{}", err);
+        }
+    }
+
+    #[tokio::test]
+    async fn connection_errors_are_retryable() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+
+        let err = ApiErr::from(reqwest::get(url).await.unwrap_err());
+
+        assert!(err.is_retryable());
+    }
+}
@@ -5,37 +5,59 @@
 
 use std::borrow::Cow;
 use std::fmt;
+use std::ops::Deref;
 use std::sync::Arc;
 use std::time::Duration;
 
 use metronomos_pulse::value::{ArcValue, PulseValue};
+use reqwest::header::{self, HeaderMap};
 use reqwest::{RequestBuilder, StatusCode};
+use serde::Serialize;
 use tokio::time::MissedTickBehavior;
 use tracing::{Instrument, debug, field, instrument};
 use url::Url;
 
 use crate::args::Args;
-use crate::clients::odesli::cache::OdesliCache;
+use crate::clients::matching::Matcher;
+use crate::clients::odesli::cache::{OdesliCache, provider_ids};
 use crate::clients::odesli::endpoints::OdesliEndpoints;
+use crate::clients::odesli::negative_cache::{NegativeCache, NegativeCacheKey};
 use crate::clients::odesli::provider_id::ProviderId;
-use crate::clients::odesli::ratelimiter::OdesliRateLimiter;
+use crate::clients::odesli::ratelimiter::{AcquireErr, OdesliRateLimiter};
 use crate::clients::odesli::shared_queue::SharedQueue;
+use crate::clients::platforms::PlatformsClient;
+use crate::clients::retry::RetryPolicy;
+use crate::db::{Database, PersistedOdesliResponse};
 use crate::metrics::MetricsStore;
-use crate::metrics::labels::{ThirdPartyLabels, ThirdPartyRateLimitLabels};
+use crate::metrics::labels::{ThirdPartyApi, ThirdPartyLabels, ThirdPartyRateLimitLabels};
 use crate::util::metric_utils::{HasHistogramFamilyExt, TimeFutureExt, UnpackErr};
 
 mod api_type;
 mod cache;
 mod endpoints;
 mod error;
+mod negative_cache;
 pub mod provider_id;
 mod ratelimiter;
 mod shared_queue;
 
 pub use api_type::*;
-pub use cache::OdesliClientResponse;
+pub use cache::{CacheStats, OdesliClientResponse};
 pub use error::{ApiClientErr, ApiErr};
 use metronomos::lifecycle::{Lifecycle, LifecycleContext};
+pub use ratelimiter::{AcquirePolicy, RequestPriority};
+
+/// The most links the negative cache remembers at once
+const NEGATIVE_CACHE_CAPACITY: usize = 10_000;
+
+/// What was removed when evicting a response from the caches
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct CacheEviction {
+    /// The number of provider IDs removed from the in-memory cache
+    pub keys: usize,
+    /// Whether the response was removed from the persistent cache
+    pub persisted: bool,
+}
 
 #[derive(Clone, PulseValue)]
 pub struct OdesliClient {
@@ -48,14 +70,25 @@ struct OdesliClientInner {
     ratelimiter: OdesliRateLimiter,
     shared_queue: SharedQueue<ProviderId, OdesliClientResponse>,
     cache: OdesliCache,
+    negative_cache: NegativeCache,
+    retry_policy: RetryPolicy,
+    database: Database,
+    persistent_cache_ttl: Duration,
+    matcher: Option<Matcher>,
     metrics: MetricsStore,
 }
 
 pub struct OdesliClientBuilder {
     client: reqwest::Client,
+    database: Database,
     metrics: MetricsStore,
     api_key: Option<Box<str>>,
     hourly_limit: Option<u32>,
+    persistent_cache_ttl: Option<Duration>,
+    cache_capacity: Option<usize>,
+    negative_cache_ttl: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    matcher: Option<Matcher>,
 }
 
 impl fmt::Debug for OdesliClient {
@@ -68,12 +101,18 @@ impl fmt::Debug for OdesliClient {
 }
 
 impl OdesliClientBuilder {
-    fn new(client: reqwest::Client, metrics: MetricsStore) -> Self {
+    fn new(client: reqwest::Client, database: Database, metrics: MetricsStore) -> Self {
         Self {
             client,
+            database,
             metrics,
             api_key: None,
             hourly_limit: None,
+            persistent_cache_ttl: None,
+            cache_capacity: None,
+            negative_cache_ttl: None,
+            retry_policy: None,
+            matcher: None,
         }
     }
 
@@ -87,10 +126,41 @@ impl OdesliClientBuilder {
         self
     }
 
+    /// Sets how long responses stay valid in the persistent (database backed) cache
+    pub fn with_persistent_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.persistent_cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Sets the approximate size (in bytes) the in-memory cache can grow to before the least
+    /// recently used responses are evicted
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Sets how long links Odesli couldn't resolve are remembered, so they aren't requested again
+    pub fn with_negative_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.negative_cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Sets how requests failing with a transient error are retried
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Fills in the platforms missing from API responses by matching their ISRC/UPC
+    pub fn with_matcher(mut self, matcher: Matcher) -> Self {
+        self.matcher = Some(matcher);
+        self
+    }
+
     pub fn build(self) -> OdesliClient {
         let ratelimiter = OdesliRateLimiter::new(
             self.hourly_limit.unwrap_or(60) as usize,
-            self.metrics.odesli_rate_limit_tokens().clone(),
+            self.metrics.clone(),
         );
 
         let inner = OdesliClientInner {
@@ -98,7 +168,21 @@ impl OdesliClientBuilder {
             api_key: self.api_key,
             ratelimiter,
             shared_queue: SharedQueue::new(),
-            cache: OdesliCache::new(),
+            cache: OdesliCache::new(
+                self.cache_capacity.unwrap_or(64 * 1024 * 1024),
+                self.metrics.clone(),
+            ),
+            negative_cache: NegativeCache::new(
+                self.negative_cache_ttl.unwrap_or(Duration::from_mins(30)),
+                NEGATIVE_CACHE_CAPACITY,
+                self.metrics.clone(),
+            ),
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            database: self.database,
+            persistent_cache_ttl: self
+                .persistent_cache_ttl
+                .unwrap_or(Duration::from_hours(24 * 7)),
+            matcher: self.matcher,
             metrics: self.metrics,
         };
 
@@ -113,11 +197,18 @@ impl OdesliClient {
         lifecycle: Lifecycle,
         client: reqwest::Client,
         args: ArcValue<Args>,
+        database: Database,
+        platforms: PlatformsClient,
         metrics: MetricsStore,
     ) -> Self {
-        let res = Self::builder(client, metrics)
+        let res = Self::builder(client, database, metrics)
             .with_api_key(args.odesli_api_key.as_deref())
             .with_hourly_limit(args.odesli_hourly_limit)
+            .with_persistent_cache_ttl(Duration::from_hours(args.odesli_cache_ttl))
+            .with_cache_capacity(args.odesli_cache_size * 1024 * 1024)
+            .with_negative_cache_ttl(Duration::from_mins(args.odesli_negative_cache_ttl))
+            .with_retry_policy(RetryPolicy::with_max_attempts(args.request_attempts))
+            .with_matcher(Matcher::new(platforms))
             .build();
 
         let cloned = res.clone();
@@ -126,12 +217,45 @@ impl OdesliClient {
         res
     }
 
-    pub fn builder(client: reqwest::Client, metrics: MetricsStore) -> OdesliClientBuilder {
-        OdesliClientBuilder::new(client, metrics)
+    pub fn builder(
+        client: reqwest::Client,
+        database: Database,
+        metrics: MetricsStore,
+    ) -> OdesliClientBuilder {
+        OdesliClientBuilder::new(client, database, metrics)
+    }
+
+    /// Removes all responses not accessed within `max_age` from the in-memory cache, returns the
+    /// number of removed keys
+    pub fn clear_expired_cache_entries(&self, max_age: Duration) -> usize {
+        self.inner.cache.clear_expired(max_age)
+    }
+
+    pub fn cache_stats(&self) -> CacheStats {
+        self.inner.cache.stats()
     }
 
-    pub fn clear_expired_cache_entries(&self, max_age: Duration) {
-        self.inner.cache.clear_expired(max_age);
+    /// Looks up the response in the in-memory cache, without marking it as accessed
+    pub fn inspect_cache_entry(&self, provider_id: &ProviderId) -> Option<OdesliClientResponse> {
+        self.inner.cache.peek(provider_id)
+    }
+
+    /// Removes the response from the in-memory and persistent caches, so the next request for it
+    /// is sent to the API again
+    #[instrument(level = "debug", skip(self))]
+    pub async fn evict(&self, provider_id: &ProviderId) -> CacheEviction {
+        let keys = self.inner.cache.remove(provider_id);
+        self.inner
+            .negative_cache
+            .remove(&NegativeCacheKey::Provider(provider_id.clone()));
+        let persisted = self
+            .inner
+            .database
+            .delete_odesli_response(&provider_id.to_string())
+            .await;
+        debug!(keys, persisted, "Evicted response from the cache");
+
+        CacheEviction { keys, persisted }
     }
 
     pub async fn cache_cleanup_task(self, ctx: LifecycleContext) {
@@ -147,6 +271,11 @@ impl OdesliClient {
 
             debug!("Running Odesli cache cleanup task");
             self.clear_expired_cache_entries(max_age);
+            self.inner.negative_cache.clear_expired();
+            self.inner
+                .database
+                .clear_expired_odesli_responses(self.inner.persistent_cache_ttl)
+                .await;
         }
     }
 
@@ -161,8 +290,18 @@ impl OdesliClient {
         req
     }
 
+    /// Fetches the links for the URL from the cache or the API, the policy decides how to wait
+    /// for the rate limit if the data isn't cached.
+    ///
+    /// Fails with [`ApiErr::RateLimitBudgetExhausted`] if the policy doesn't allow waiting and no
+    /// token is available, or with [`ApiErr::RateLimitDeadlineExceeded`] if no token could be
+    /// acquired before its deadline.
     #[instrument(level = "debug", skip_all)]
-    pub async fn fetch(&self, url: &Url) -> Result<OdesliClientResponse, ApiErr> {
+    pub async fn fetch_with_policy(
+        &self,
+        url: &Url,
+        policy: AcquirePolicy,
+    ) -> Result<OdesliClientResponse, ApiErr> {
         let provider_id = match ProviderId::parse_url(url) {
             Ok(provider_id) => provider_id,
             Err(e) => {
@@ -171,7 +310,7 @@ impl OdesliClient {
                     e
                 );
 
-                return self.fetch_inner(url).await;
+                return self.fetch_api(url, None, policy).await;
             }
         };
 
@@ -188,23 +327,107 @@ impl OdesliClient {
             .run_shared(
                 provider_id.clone(),
                 || async {
-                    // Check the cache again in case another request has already fetched the data
-                    if let Some(cached) = self.inner.cache.get_response(&provider_id) {
+                    // Check the cache again in case another request has already fetched the data,
+                    // without counting another lookup
+                    if let Some(cached) = self.inner.cache.peek(&provider_id)
+                        && !cached.is_native()
+                    {
                         return Ok(cached);
                     }
-                    self.fetch_inner(url).await
+                    if let Some(persisted) = self.fetch_persisted(&provider_id).await {
+                        return Ok(persisted);
+                    }
+                    self.fetch_api(url, Some(&provider_id), policy).await
                 },
                 |result| result.duplicate(),
             )
             .await
     }
 
-    /// fetch without caching, used internally by fetch
-    async fn fetch_inner(&self, url: &Url) -> Result<OdesliClientResponse, ApiErr> {
+    /// Looks up the response in the in-memory and persistent caches, without querying the API.
+    ///
+    /// Unlike [`Self::fetch_with_policy`] this also returns responses resolved with the platform
+    /// APIs.
+    #[instrument(level = "debug", skip(self))]
+    pub async fn fetch_cached(&self, provider_id: &ProviderId) -> Option<OdesliClientResponse> {
+        if let Some(cached) = self.inner.cache.get_any_response(provider_id) {
+            debug!("Cache hit for provider");
+            return Some(cached);
+        }
+
+        self.fetch_persisted(provider_id).await
+    }
+
+    /// Stores a response that was resolved without the API in the in-memory cache, so the buttons
+    /// and landing pages can look it up by its provider IDs.
+    ///
+    /// It is neither persisted nor does it replace a response from Odesli, and it is not used to
+    /// answer [`Self::fetch_with_policy`].
+    pub fn store_native_response(&self, response: OdesliResponse) -> OdesliClientResponse {
+        self.inner.cache.store_native_response(response)
+    }
+
+    /// Looks up the response in the persistent cache, promoting it to the in-memory cache on a hit
+    async fn fetch_persisted(&self, provider_id: &ProviderId) -> Option<OdesliClientResponse> {
+        let response = self
+            .inner
+            .database
+            .load_odesli_response(&provider_id.to_string(), self.inner.persistent_cache_ttl)
+            .await?;
+        debug!("Persistent cache hit for provider");
+
+        let mut client_response = self.inner.cache.store_response(response);
+        client_response.is_cached = true;
+
+        Some(client_response)
+    }
+
+    /// Requests the link from the API, unless the API failed to resolve it recently
+    async fn fetch_api(
+        &self,
+        url: &Url,
+        provider_id: Option<&ProviderId>,
+        policy: AcquirePolicy,
+    ) -> Result<OdesliClientResponse, ApiErr> {
+        let key = NegativeCacheKey::new(url, provider_id);
+        if let Some(err) = self.inner.negative_cache.get(&key) {
+            debug!("Negative cache hit, skipping API request: {}", err);
+            return Err(ApiErr::ClientError(err));
+        }
+
+        // Every attempt acquires its own token, so retries are subject to the rate limit as well
+        let result = self
+            .inner
+            .retry_policy
+            .run(
+                ThirdPartyApi::Odesli,
+                policy.deadline(),
+                &self.inner.metrics,
+                || self.fetch_inner(url, policy),
+            )
+            .await;
+        if let Err(ApiErr::ClientError(err)) = &result {
+            self.inner.negative_cache.insert(key, err);
+        }
+
+        result
+    }
+
+    /// A single attempt of the request, without caching or retries
+    async fn fetch_inner(
+        &self,
+        url: &Url,
+        policy: AcquirePolicy,
+    ) -> Result<OdesliClientResponse, ApiErr> {
         let req_data = OdesliEndpoints::links(url);
 
         // Wait for the rate limiter to allow us to make the request
-        let ((), diff) = self.inner.ratelimiter.acquire().time().await;
+        let (acquired, diff) = self.inner.ratelimiter.acquire_with(policy).time().await;
+        match acquired {
+            Ok(()) => {}
+            Err(AcquireErr::Unavailable) => return Err(ApiErr::RateLimitBudgetExhausted),
+            Err(AcquireErr::DeadlineExceeded) => return Err(ApiErr::RateLimitDeadlineExceeded),
+        }
         self.inner.metrics.observe_duration(
             ThirdPartyRateLimitLabels {
                 method: req_data.method().into(),
@@ -240,6 +463,9 @@ impl OdesliClient {
                 ));
             }
             StatusCode::TOO_MANY_REQUESTS => {
+                self.inner
+                    .ratelimiter
+                    .on_rate_limited(retry_after(resp.headers()));
                 return Err(ApiErr::RateLimitExceeded);
             }
             _ => {
@@ -254,31 +480,51 @@ impl OdesliClient {
 
         let mut api_response = resp.json::<OdesliResponse>().await?;
         fix_platform_links(&mut api_response);
+        if let Some(matcher) = &self.inner.matcher {
+            matcher.fill_gaps(&mut api_response).await;
+        }
         let client_response = self.inner.cache.store_response(api_response);
 
+        self.inner
+            .database
+            .spawn_save_odesli_response(PersistedOdesliResponse {
+                response: client_response.deref().clone(),
+                provider_ids: provider_ids(&client_response)
+                    .map(|pid| pid.to_string())
+                    .collect(),
+            });
+
         Ok(client_response)
     }
 }
 
-// Fixes the links for some platforms, so they work properly
+/// Reads the delay from the `Retry-After` header, HTTP dates are not supported and leave the
+/// backoff to the rate limiter
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Replaces the links of the platforms with their canonical form, so they work properly. Odesli
+/// links to Apple Music go through the geo redirect and carry tracking parameters for example.
 fn fix_platform_links(resp: &mut OdesliResponse) {
-    if let Some(links) = resp.links_by_platform.get_mut(&Platform::AppleMusic) {
-        let new = links.url.replace("geo.music.apple.com", "music.apple.com");
-        let mut new_iter = new.split('?');
-
-        let new = new_iter
-            .next()
-            .expect("A split should always return something");
-        if let Some(query) = new_iter.next() {
-            let song_id = query.split('&').find(|s| s.starts_with("i="));
-            if let Some(song_id) = song_id {
-                links.url = format!("{}?{}", new, song_id);
-            } else {
-                // Just return the album link
-                links.url = new.to_string();
-            }
-        } else {
-            links.url = new.to_string();
+    for (platform, links) in resp.links_by_platform.iter_mut() {
+        let Some(provider_id) = Url::parse(&links.url)
+            .ok()
+            .and_then(|url| ProviderId::parse_url(&url).ok())
+        else {
+            continue;
+        };
+
+        // YouTube videos are linked for both YouTube and YouTube Music, keep their domains apart
+        if provider_id.platform() == *platform {
+            links.url = provider_id.to_canonical_url().to_string();
         }
     }
 }
@@ -0,0 +1,203 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use url::Url;
+
+use crate::clients::odesli::ApiClientErr;
+use crate::clients::odesli::provider_id::ProviderId;
+use crate::metrics::MetricsStore;
+
+/// What a negative cache entry is looked up by, links the provider ID can't be parsed from are
+/// cached by their normalized URL instead
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) enum NegativeCacheKey {
+    Provider(ProviderId),
+    Url(String),
+}
+
+impl NegativeCacheKey {
+    pub fn new(url: &Url, provider_id: Option<&ProviderId>) -> Self {
+        match provider_id {
+            Some(provider_id) => Self::Provider(provider_id.clone()),
+            None => {
+                // The scheme and host are already lowercased by the parser
+                let mut url = url.clone();
+                url.set_fragment(None);
+                let normalized = url.as_str().trim_end_matches('/').to_string();
+                Self::Url(normalized)
+            }
+        }
+    }
+}
+
+struct NegativeCacheEntry {
+    err: ApiClientErr,
+    expires_at: Instant,
+}
+
+/// Remembers the links Odesli couldn't resolve for a while, so retrying them doesn't use up rate
+/// limit tokens.
+///
+/// The links are chosen by users, so the number of entries is bounded as well.
+pub(super) struct NegativeCache {
+    entries: DashMap<NegativeCacheKey, NegativeCacheEntry>,
+    ttl: Duration,
+    capacity: usize,
+    metrics: MetricsStore,
+}
+
+impl NegativeCache {
+    pub fn new(ttl: Duration, capacity: usize, metrics: MetricsStore) -> Self {
+        Self {
+            entries: DashMap::new(),
+            ttl,
+            capacity,
+            metrics,
+        }
+    }
+
+    /// Looks up the cached error, counting the API request saved by it
+    pub fn get(&self, key: &NegativeCacheKey) -> Option<ApiClientErr> {
+        let entry = self.entries.get(key)?;
+        if entry.expires_at > Instant::now() {
+            self.metrics.odesli_negative_cache_hits().inc();
+            return Some(entry.err.clone());
+        }
+
+        drop(entry);
+        self.entries
+            .remove_if(key, |_, entry| entry.expires_at <= Instant::now());
+        None
+    }
+
+    /// Caches the error if it won't change when retrying the request
+    pub fn insert(&self, key: NegativeCacheKey, err: &ApiClientErr) {
+        if !matches!(
+            err,
+            ApiClientErr::UnknownEntity | ApiClientErr::InvalidEntityType
+        ) {
+            return;
+        }
+
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            self.make_room();
+        }
+
+        self.entries.insert(
+            key,
+            NegativeCacheEntry {
+                err: err.clone(),
+                expires_at: Instant::now() + self.ttl,
+            },
+        );
+    }
+
+    pub fn remove(&self, key: &NegativeCacheKey) -> bool {
+        self.entries.remove(key).is_some()
+    }
+
+    pub fn clear_expired(&self) {
+        let now = Instant::now();
+        self.entries.retain(|_, entry| entry.expires_at > now);
+    }
+
+    /// Removes the expired entries, or the one expiring first if none are expired.
+    ///
+    /// Entries are only inserted after a request to the API, so the scan is bounded by its rate
+    /// limit.
+    fn make_room(&self) {
+        self.clear_expired();
+        if self.entries.len() < self.capacity {
+            return;
+        }
+
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|entry| entry.expires_at)
+            .map(|entry| entry.key().clone());
+        if let Some(oldest) = oldest {
+            self.entries.remove(&oldest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(id: u32) -> NegativeCacheKey {
+        NegativeCacheKey::Url(format!("https://example.com/{}", id))
+    }
+
+    fn hits(metrics: &MetricsStore) -> u64 {
+        metrics.odesli_negative_cache_hits().get()
+    }
+
+    #[test]
+    fn only_permanent_errors_are_cached() {
+        let metrics = MetricsStore::new();
+        let cache = NegativeCache::new(Duration::from_secs(60), 16, metrics.clone());
+
+        cache.insert(key(1), &ApiClientErr::UnknownEntity);
+        cache.insert(key(2), &ApiClientErr::InvalidEntityType);
+        cache.insert(key(3), &ApiClientErr::UnknownCode("500".to_string()));
+
+        assert!(matches!(
+            cache.get(&key(1)),
+            Some(ApiClientErr::UnknownEntity)
+        ));
+        assert!(matches!(
+            cache.get(&key(2)),
+            Some(ApiClientErr::InvalidEntityType)
+        ));
+        assert!(cache.get(&key(3)).is_none());
+        assert!(cache.get(&key(4)).is_none());
+        assert_eq!(hits(&metrics), 2);
+
+        assert!(cache.remove(&key(1)));
+        assert!(cache.get(&key(1)).is_none());
+        assert_eq!(hits(&metrics), 2);
+    }
+
+    #[test]
+    fn entries_expire_after_the_ttl() {
+        let metrics = MetricsStore::new();
+        let cache = NegativeCache::new(Duration::ZERO, 16, metrics.clone());
+
+        cache.insert(key(1), &ApiClientErr::UnknownEntity);
+        assert!(cache.get(&key(1)).is_none());
+        // Expired entries are removed when they are looked up
+        assert_eq!(cache.entries.len(), 0);
+
+        cache.insert(key(2), &ApiClientErr::UnknownEntity);
+        cache.clear_expired();
+        assert_eq!(cache.entries.len(), 0);
+        assert_eq!(hits(&metrics), 0);
+    }
+
+    #[test]
+    fn stays_within_its_capacity() {
+        let cache = NegativeCache::new(Duration::from_secs(60), 4, MetricsStore::new());
+
+        for id in 0..10 {
+            cache.insert(key(id), &ApiClientErr::UnknownEntity);
+            assert!(cache.entries.len() <= 4);
+        }
+        // Replacing an entry doesn't evict another one
+        cache.insert(key(9), &ApiClientErr::InvalidEntityType);
+        assert_eq!(cache.entries.len(), 4);
+        assert!(matches!(
+            cache.get(&key(9)),
+            Some(ApiClientErr::InvalidEntityType)
+        ));
+    }
+}
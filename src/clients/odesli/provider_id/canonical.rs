@@ -0,0 +1,489 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use url::Url;
+
+use super::{
+    AmazonMusicId, AnghamiId, AppleMusicId, AudiomackId, AudiusId, BandcampId, BoomPlayId,
+    DeezerId, NapsterId, PandoraId, ProviderId, SoundCloudId, SpinrillaId, SpotifyId, TidalId,
+    YandexId, YouTubeId,
+};
+
+/// Query parameters only used to track who shared a link and where it was shared
+const TRACKING_PARAMETERS: &[&str] = &["si", "feature", "fbclid", "gclid", "igshid"];
+
+macro_rules! track_album_url {
+    ($ty:ident, $id:expr, $track:literal, $album:literal $(, $variant:ident => $fmt:literal)*) => {
+        match $id {
+            $ty::Track(id) => format!($track, id),
+            $ty::Album(id) => format!($album, id),
+            $( $ty::$variant(id) => format!($fmt, id), )*
+        }
+    };
+}
+
+/// Splits slug based IDs into the artist and the slug
+fn split_slug(id: &str) -> (&str, &str) {
+    id.split_once('/').unwrap_or(("", id))
+}
+
+impl ProviderId {
+    /// Builds the canonical link to the entity, parsing it results in the same provider ID.
+    ///
+    /// Apple Music links use the US storefront, since the storefront isn't part of the ID, see
+    /// [`canonicalize_url`] to keep the storefront of a link.
+    pub fn to_canonical_url(&self) -> Url {
+        Url::parse(&self.canonical_link()).expect("Canonical links should always be valid")
+    }
+
+    /// Whether the canonical link is valid and parses back to the same ID, which holds for every
+    /// ID parsed from a link
+    pub(super) fn round_trips(&self) -> bool {
+        Url::parse(&self.canonical_link())
+            .ok()
+            .and_then(|url| Self::parse_url(&url).ok())
+            .is_some_and(|parsed| parsed == *self)
+    }
+
+    fn canonical_link(&self) -> String {
+        match self {
+            Self::AmazonMusic(AmazonMusicId::Album(id)) => {
+                format!("https://music.amazon.com/albums/{}", id)
+            }
+            Self::AmazonMusic(AmazonMusicId::Artist(id)) => {
+                format!("https://music.amazon.com/artists/{}", id)
+            }
+            Self::AmazonMusic(AmazonMusicId::Track { album_id, track_id }) => format!(
+                "https://music.amazon.com/albums/{}?trackAsin={}",
+                album_id, track_id
+            ),
+            Self::Anghami(id) => track_album_url!(
+                AnghamiId,
+                id,
+                "https://play.anghami.com/song/{}",
+                "https://play.anghami.com/album/{}"
+            ),
+            Self::AppleMusic(id) => track_album_url!(
+                AppleMusicId,
+                id,
+                "https://music.apple.com/us/song/{}",
+                "https://music.apple.com/us/album/{}",
+                Artist => "https://music.apple.com/us/artist/{}"
+            ),
+            Self::Audiomack(AudiomackId::Track(id)) => {
+                let (artist, slug) = split_slug(id);
+                format!("https://audiomack.com/{}/song/{}", artist, slug)
+            }
+            Self::Audiomack(AudiomackId::Album(id)) => {
+                let (artist, slug) = split_slug(id);
+                format!("https://audiomack.com/{}/album/{}", artist, slug)
+            }
+            Self::Audius(AudiusId::Track(id)) => format!("https://audius.co/{}", id),
+            Self::Audius(AudiusId::Album(id)) => {
+                let (artist, slug) = split_slug(id);
+                format!("https://audius.co/{}/album/{}", artist, slug)
+            }
+            Self::Bandcamp(BandcampId::Track(id)) => {
+                let (artist, slug) = split_slug(id);
+                format!("https://{}.bandcamp.com/track/{}", artist, slug)
+            }
+            Self::Bandcamp(BandcampId::Album(id)) => {
+                let (artist, slug) = split_slug(id);
+                format!("https://{}.bandcamp.com/album/{}", artist, slug)
+            }
+            Self::BoomPlay(id) => track_album_url!(
+                BoomPlayId,
+                id,
+                "https://www.boomplay.com/songs/{}",
+                "https://www.boomplay.com/albums/{}"
+            ),
+            Self::Deezer(id) => track_album_url!(
+                DeezerId,
+                id,
+                "https://www.deezer.com/track/{}",
+                "https://www.deezer.com/album/{}",
+                Artist => "https://www.deezer.com/artist/{}"
+            ),
+            Self::Napster(id) => track_album_url!(
+                NapsterId,
+                id,
+                "https://play.napster.com/track/tra.{}",
+                "https://play.napster.com/album/alb.{}"
+            ),
+            Self::Pandora(id) => track_album_url!(
+                PandoraId,
+                id,
+                "https://www.pandora.com/TR:{}",
+                "https://www.pandora.com/AL:{}"
+            ),
+            Self::SoundCloud(SoundCloudId::Track(id)) => format!("https://soundcloud.com/{}", id),
+            Self::SoundCloud(SoundCloudId::Album(id)) => {
+                let (user, slug) = split_slug(id);
+                format!("https://soundcloud.com/{}/sets/{}", user, slug)
+            }
+            Self::Spinrilla(id) => track_album_url!(
+                SpinrillaId,
+                id,
+                "https://spinrilla.com/songs/{}",
+                "https://spinrilla.com/mixtapes/{}"
+            ),
+            Self::Spotify(id) => track_album_url!(
+                SpotifyId,
+                id,
+                "https://open.spotify.com/track/{}",
+                "https://open.spotify.com/album/{}",
+                Artist => "https://open.spotify.com/artist/{}"
+            ),
+            Self::Tidal(id) => track_album_url!(
+                TidalId,
+                id,
+                "https://tidal.com/track/{}",
+                "https://tidal.com/album/{}",
+                Artist => "https://tidal.com/artist/{}"
+            ),
+            Self::Yandex(id) => track_album_url!(
+                YandexId,
+                id,
+                "https://music.yandex.ru/track/{}",
+                "https://music.yandex.ru/album/{}"
+            ),
+            Self::YouTube(YouTubeId::Video(id)) => {
+                format!("https://www.youtube.com/watch?v={}", id)
+            }
+            Self::YouTube(YouTubeId::Album(id)) => {
+                format!("https://music.youtube.com/playlist?list={}", id)
+            }
+            Self::YouTube(YouTubeId::Artist(id)) => {
+                format!("https://music.youtube.com/channel/{}", id)
+            }
+        }
+    }
+}
+
+/// The storefront of an Apple Music link, the first segment of the path, e.g. `de`
+fn apple_music_storefront(url: &Url) -> Option<&str> {
+    url.path_segments()?.next().filter(|storefront| {
+        storefront.len() == 2 && storefront.chars().all(|c| c.is_ascii_alphabetic())
+    })
+}
+
+/// Normalizes a link before it is shown to users or stored.
+///
+/// Links to entities are replaced with their canonical link, the tracking parameters are stripped
+/// from any other link.
+pub fn canonicalize_url(url: &Url) -> Url {
+    if let Ok(provider_id) = ProviderId::parse_url(url) {
+        let mut canonical = provider_id.to_canonical_url();
+        // Entities can be unavailable in other storefronts, keep the one of the user
+        if let ProviderId::AppleMusic(_) = provider_id
+            && let Some(storefront) = apple_music_storefront(url)
+        {
+            let path = canonical
+                .path()
+                .replacen("/us/", &format!("/{}/", storefront), 1);
+            canonical.set_path(&path);
+        }
+        // Videos are the same on YouTube and YouTube Music, keep the one the link was for
+        if matches!(provider_id, ProviderId::YouTube(YouTubeId::Video(_)))
+            && url.domain() == Some("music.youtube.com")
+        {
+            canonical
+                .set_host(Some("music.youtube.com"))
+                .expect("The host should be valid");
+        }
+        return canonical;
+    }
+
+    let mut url = url.clone();
+    let query = url
+        .query_pairs()
+        .filter(|(key, _)| !TRACKING_PARAMETERS.contains(&key.as_ref()) && !key.starts_with("utm_"))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect::<Vec<_>>();
+
+    if query.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(query);
+    }
+
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use proptest::strategy::{BoxedStrategy, Union};
+
+    use super::*;
+
+    /// Path segments as they appear in links, in mixed case and with characters that have to be
+    /// percent-encoded or change the meaning of the link
+    fn segment() -> impl Strategy<Value = String> {
+        "[A-Za-z0-9][A-Za-z0-9._~!$&'()*+,;=:@%?#/ \u{e9}\u{1f3b5}-]{0,16}"
+    }
+
+    /// User names, including the top level pages of SoundCloud and Audius
+    fn user() -> impl Strategy<Value = String> {
+        prop_oneof![
+            segment(),
+            prop::sample::select(vec![
+                "search", "discover", "you", "stream", "trending", "explore", "feed", "signin",
+                "Charts", "UPLOAD",
+            ])
+            .prop_map(String::from),
+        ]
+    }
+
+    /// Slugs, including the pages of SoundCloud and Audius users which aren't tracks
+    fn slug() -> impl Strategy<Value = String> {
+        prop_oneof![
+            segment(),
+            prop::sample::select(vec!["sets", "tracks", "albums", "likes", "Reposts"])
+                .prop_map(String::from),
+        ]
+    }
+
+    fn link<S: Strategy + 'static>(
+        strategy: S,
+        format: impl Fn(S::Value) -> String + 'static,
+    ) -> BoxedStrategy<String> {
+        strategy.prop_map(format).boxed()
+    }
+
+    /// Links to every kind of entity of every provider
+    fn links() -> impl Strategy<Value = String> {
+        let amazon = "B0[0-9A-Z]{8}";
+        let base62 = "[0-9A-Za-z]{22}";
+        let youtube = "[0-9A-Za-z_-]{11}";
+
+        Union::new([
+            link(amazon, |id| {
+                format!("https://music.amazon.com/albums/{}", id)
+            }),
+            link((amazon, amazon), |(album, track)| {
+                format!(
+                    "https://music.amazon.com/albums/{}?trackAsin={}",
+                    album, track
+                )
+            }),
+            link(amazon, |id| {
+                format!("https://music.amazon.com/artists/{}/rick-astley", id)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://play.anghami.com/song/{}", id)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://play.anghami.com/album/{}", id)
+            }),
+            link(("[a-z]{2}", any::<u64>()), |(storefront, id)| {
+                format!("https://music.apple.com/{}/album/name/{}", storefront, id)
+            }),
+            link((any::<u64>(), any::<u64>()), |(album, track)| {
+                format!("https://music.apple.com/us/album/{}?i={}", album, track)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://music.apple.com/us/artist/name/{}", id)
+            }),
+            link((user(), segment()), |(artist, slug)| {
+                format!("https://audiomack.com/{}/song/{}", artist, slug)
+            }),
+            link((user(), segment()), |(artist, slug)| {
+                format!("https://audiomack.com/{}/album/{}", artist, slug)
+            }),
+            link((user(), slug()), |(artist, slug)| {
+                format!("https://audius.co/{}/{}", artist, slug)
+            }),
+            link((user(), segment()), |(artist, slug)| {
+                format!("https://audius.co/{}/album/{}", artist, slug)
+            }),
+            link(("[A-Za-z0-9-]{1,12}", segment()), |(artist, slug)| {
+                format!("https://{}.bandcamp.com/track/{}", artist, slug)
+            }),
+            link(("[A-Za-z0-9-]{1,12}", segment()), |(artist, slug)| {
+                format!("https://{}.bandcamp.com/album/{}", artist, slug)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://www.boomplay.com/songs/{}", id)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://www.boomplay.com/albums/{}", id)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://www.deezer.com/en/track/{}", id)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://www.deezer.com/album/{}", id)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://www.deezer.com/artist/{}", id)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://play.napster.com/track/tra.{}", id)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://play.napster.com/album/alb.{}", id)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://www.pandora.com/TR:{}", id)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://www.pandora.com/AL:{}", id)
+            }),
+            link((user(), slug()), |(user, slug)| {
+                format!("https://soundcloud.com/{}/{}", user, slug)
+            }),
+            link((user(), segment()), |(user, slug)| {
+                format!("https://soundcloud.com/{}/sets/{}", user, slug)
+            }),
+            link(segment(), |slug| {
+                format!("https://spinrilla.com/songs/{}", slug)
+            }),
+            link(segment(), |slug| {
+                format!("https://spinrilla.com/mixtapes/{}", slug)
+            }),
+            link(base62, |id| {
+                format!("https://open.spotify.com/track/{}", id)
+            }),
+            link(base62, |id| {
+                format!("https://open.spotify.com/intl-de/album/{}", id)
+            }),
+            link(base62, |id| {
+                format!("https://open.spotify.com/artist/{}", id)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://tidal.com/browse/track/{}", id)
+            }),
+            link(any::<u64>(), |id| format!("https://tidal.com/album/{}", id)),
+            link(any::<u64>(), |id| {
+                format!("https://tidal.com/artist/{}", id)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://music.yandex.ru/track/{}", id)
+            }),
+            link(any::<u64>(), |id| {
+                format!("https://music.yandex.ru/album/{}", id)
+            }),
+            link(youtube, |id| {
+                format!("https://www.youtube.com/watch?v={}", id)
+            }),
+            link(youtube, |id| format!("https://youtu.be/{}", id)),
+            link("[0-9A-Za-z_-]{33}", |id| {
+                format!("https://music.youtube.com/playlist?list=OLAK5uy_{}", id)
+            }),
+            link("[0-9A-Za-z_-]{22}", |id| {
+                format!("https://music.youtube.com/channel/UC{}", id)
+            }),
+        ])
+    }
+
+    /// Keys of the providers with free-form IDs, with arbitrary IDs
+    fn keys() -> impl Strategy<Value = String> {
+        let kinds = prop::sample::select(vec![
+            "amazon_music:album",
+            "amazon_music:artist",
+            "amazon_music:track",
+            "audiomack:track",
+            "audius:album",
+            "audius:track",
+            "bandcamp:track",
+            "deezer:track",
+            "soundcloud:album",
+            "soundcloud:track",
+            "spinrilla:album",
+            "spotify:track",
+            "youtube:video",
+        ]);
+        let id = prop_oneof![
+            (user(), slug()).prop_map(|(artist, slug)| format!("{}/{}", artist, slug)),
+            segment(),
+            any::<u64>().prop_map(|id| id.to_string()),
+        ];
+
+        (kinds, id).prop_map(|(kind, id)| format!("{}:{}", kind, id))
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(2048))]
+
+        #[test]
+        fn canonical_urls_round_trip(link in links()) {
+            let url = Url::parse(&link).unwrap();
+            // Links to reserved pages aren't entities
+            let Ok(id) = ProviderId::parse_url(&url) else {
+                return Ok(());
+            };
+            let canonical = id.to_canonical_url();
+
+            prop_assert_eq!(ProviderId::parse_url(&canonical).ok(), Some(id.clone()));
+            prop_assert_eq!(canonicalize_url(&canonical), canonical);
+            prop_assert_eq!(id.to_string().parse::<ProviderId>(), Ok(id));
+        }
+
+        #[test]
+        fn parsed_keys_round_trip(key in keys()) {
+            // Only keys of entities that can be linked to are accepted
+            let Ok(id) = key.parse::<ProviderId>() else {
+                return Ok(());
+            };
+            let canonical = id.to_canonical_url();
+
+            prop_assert_eq!(ProviderId::parse_url(&canonical).ok(), Some(id.clone()));
+            prop_assert_eq!(id.to_string().parse::<ProviderId>(), Ok(id));
+        }
+
+        #[test]
+        fn slug_keys_are_case_insensitive(
+            artist in "[a-z][a-z0-9-]{2,12}",
+            slug in "[a-z0-9][a-z0-9-]{0,16}",
+        ) {
+            let key = format!("soundcloud:track:{}/{}", artist, slug);
+            let mixed_case = format!("soundcloud:track:{}/{}", artist.to_uppercase(), slug);
+            let id = key.parse::<ProviderId>();
+
+            prop_assert!(id.is_ok(), "{}", key);
+            prop_assert_eq!(mixed_case.parse::<ProviderId>(), id);
+        }
+    }
+
+    #[test]
+    fn keeps_apple_music_storefront() {
+        let cases = [
+            (
+                "https://music.apple.com/de/album/whenever-you-need-somebody/1559523357?i=1559523359",
+                "https://music.apple.com/de/song/1559523359",
+            ),
+            (
+                "https://music.apple.com/jp/album/whenever-you-need-somebody/1559523357",
+                "https://music.apple.com/jp/album/1559523357",
+            ),
+            (
+                "https://geo.music.apple.com/gb/artist/rick-astley/669771?app=music",
+                "https://music.apple.com/gb/artist/669771",
+            ),
+        ];
+
+        for (link, expected) in cases {
+            let canonical = canonicalize_url(&Url::parse(link).unwrap());
+            assert_eq!(canonical.as_str(), expected);
+            assert_eq!(
+                ProviderId::parse_url(&canonical).ok(),
+                ProviderId::parse_url(&Url::parse(link).unwrap()).ok()
+            );
+        }
+    }
+
+    #[test]
+    fn strips_tracking_parameters() {
+        let url = Url::parse("https://example.com/page?utm_source=x&si=y&id=1").unwrap();
+        assert_eq!(
+            canonicalize_url(&url).as_str(),
+            "https://example.com/page?id=1"
+        );
+
+        let url = Url::parse("https://example.com/page?fbclid=abc").unwrap();
+        assert_eq!(canonicalize_url(&url).as_str(), "https://example.com/page");
+    }
+}
@@ -3,21 +3,33 @@
  * All Rights Reserved
  */
 
+use std::fmt;
+use std::str::FromStr;
+
+use url::Url;
+
+use crate::clients::odesli::Platform;
+
+mod canonical;
 mod parse;
 
+pub use canonical::canonicalize_url;
+
 macro_rules! create_provider_id {
-    ($name:ident, $ty:ty) => {
-        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    ($name:ident, $ty:ty, NoCopy $(, $extra:ident)*) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
         pub enum $name {
             Album($ty),
             Track($ty),
+            $( $extra($ty), )*
         }
     };
-    ($name:ident, $ty:ty, NoCopy) => {
-        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    ($name:ident, $ty:ty $(, $extra:ident)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub enum $name {
             Album($ty),
             Track($ty),
+            $( $extra($ty), )*
         }
     };
 }
@@ -25,33 +37,227 @@ macro_rules! create_provider_id {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AmazonMusicId {
     Album(String),
+    Artist(String),
     Track { album_id: String, track_id: String },
 }
 
 create_provider_id!(AnghamiId, u64);
-create_provider_id!(AppleMusicId, u64);
+create_provider_id!(AppleMusicId, u64, Artist);
 create_provider_id!(BoomPlayId, u64);
-create_provider_id!(DeezerId, u64);
+create_provider_id!(DeezerId, u64, Artist);
 create_provider_id!(NapsterId, u64);
 create_provider_id!(PandoraId, u64);
-create_provider_id!(SpotifyId, String, NoCopy);
-create_provider_id!(TidalId, u64);
+create_provider_id!(SpotifyId, String, NoCopy, Artist);
+create_provider_id!(TidalId, u64, Artist);
 create_provider_id!(YandexId, u64);
 
+// The following platforms identify entities by slugs, which are only unique per artist, so the
+// IDs have the form `<artist>/<slug>`
+create_provider_id!(AudiusId, String, NoCopy);
+create_provider_id!(AudiomackId, String, NoCopy);
+create_provider_id!(BandcampId, String, NoCopy);
+create_provider_id!(SoundCloudId, String, NoCopy);
+// Spinrilla slugs are unique on their own
+create_provider_id!(SpinrillaId, String, NoCopy);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct YouTubeId(String);
+pub enum YouTubeId {
+    Video(String),
+    /// A YouTube Music album, which is linked as a playlist
+    Album(String),
+    /// A YouTube Music artist channel
+    Artist(String),
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ProviderId {
     AmazonMusic(AmazonMusicId),
     Anghami(AnghamiId),
     AppleMusic(AppleMusicId),
+    Audiomack(AudiomackId),
+    Audius(AudiusId),
+    Bandcamp(BandcampId),
     BoomPlay(BoomPlayId),
     Deezer(DeezerId),
     Napster(NapsterId),
     Pandora(PandoraId),
+    SoundCloud(SoundCloudId),
+    Spinrilla(SpinrillaId),
     Spotify(SpotifyId),
     Tidal(TidalId),
     Yandex(YandexId),
     YouTube(YouTubeId),
 }
+
+impl ProviderId {
+    /// The platform the ID belongs to, YouTube IDs are attributed to YouTube Music for albums and
+    /// artists
+    pub fn platform(&self) -> Platform {
+        match self {
+            Self::AmazonMusic(_) => Platform::AmazonMusic,
+            Self::Anghami(_) => Platform::Anghami,
+            Self::AppleMusic(_) => Platform::AppleMusic,
+            Self::Audiomack(_) => Platform::Audiomack,
+            Self::Audius(_) => Platform::Audius,
+            // Odesli doesn't support Bandcamp, so there is no dedicated platform for it
+            Self::Bandcamp(_) => Platform::Other("bandcamp".to_string()),
+            Self::BoomPlay(_) => Platform::BoomPlay,
+            Self::Deezer(_) => Platform::Deezer,
+            Self::Napster(_) => Platform::Napster,
+            Self::Pandora(_) => Platform::Pandora,
+            Self::SoundCloud(_) => Platform::Soundcloud,
+            Self::Spinrilla(_) => Platform::Spinrilla,
+            Self::Spotify(_) => Platform::Spotify,
+            Self::Tidal(_) => Platform::Tidal,
+            Self::Yandex(_) => Platform::Yandex,
+            Self::YouTube(YouTubeId::Video(_)) => Platform::YouTube,
+            Self::YouTube(YouTubeId::Album(_) | YouTubeId::Artist(_)) => Platform::YouTubeMusic,
+        }
+    }
+
+    /// Parses either a key created by the [`fmt::Display`] implementation or a link to the entity
+    pub fn from_key_or_url(input: &str) -> Option<Self> {
+        let input = input.trim();
+        input
+            .parse()
+            .ok()
+            .or_else(|| Self::parse_url(&Url::parse(input).ok()?).ok())
+    }
+
+    pub fn is_artist(&self) -> bool {
+        matches!(
+            self,
+            Self::AmazonMusic(AmazonMusicId::Artist(_))
+                | Self::AppleMusic(AppleMusicId::Artist(_))
+                | Self::Deezer(DeezerId::Artist(_))
+                | Self::Spotify(SpotifyId::Artist(_))
+                | Self::Tidal(TidalId::Artist(_))
+                | Self::YouTube(YouTubeId::Artist(_))
+        )
+    }
+}
+
+macro_rules! write_track_album {
+    ($f:expr, $provider:literal, $ty:ident, $id:expr $(, $variant:ident => $kind:literal)*) => {
+        match $id {
+            $ty::Album(id) => write!($f, concat!($provider, ":album:{}"), id),
+            $ty::Track(id) => write!($f, concat!($provider, ":track:{}"), id),
+            $( $ty::$variant(id) => write!($f, concat!($provider, ":", $kind, ":{}"), id), )*
+        }
+    };
+}
+
+/// Formats the provider ID as a stable key, e.g. `spotify:track:<id>`.
+///
+/// The format is used to persist provider IDs, so changing it invalidates all stored keys.
+impl fmt::Display for ProviderId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AmazonMusic(AmazonMusicId::Album(id)) => write!(f, "amazon_music:album:{}", id),
+            Self::AmazonMusic(AmazonMusicId::Artist(id)) => {
+                write!(f, "amazon_music:artist:{}", id)
+            }
+            Self::AmazonMusic(AmazonMusicId::Track { album_id, track_id }) => {
+                write!(f, "amazon_music:track:{}:{}", album_id, track_id)
+            }
+            Self::Anghami(id) => write_track_album!(f, "anghami", AnghamiId, id),
+            Self::AppleMusic(id) => {
+                write_track_album!(f, "apple_music", AppleMusicId, id, Artist => "artist")
+            }
+            Self::Audiomack(id) => write_track_album!(f, "audiomack", AudiomackId, id),
+            Self::Audius(id) => write_track_album!(f, "audius", AudiusId, id),
+            Self::Bandcamp(id) => write_track_album!(f, "bandcamp", BandcampId, id),
+            Self::BoomPlay(id) => write_track_album!(f, "boomplay", BoomPlayId, id),
+            Self::Deezer(id) => write_track_album!(f, "deezer", DeezerId, id, Artist => "artist"),
+            Self::Napster(id) => write_track_album!(f, "napster", NapsterId, id),
+            Self::Pandora(id) => write_track_album!(f, "pandora", PandoraId, id),
+            Self::SoundCloud(id) => write_track_album!(f, "soundcloud", SoundCloudId, id),
+            Self::Spinrilla(id) => write_track_album!(f, "spinrilla", SpinrillaId, id),
+            Self::Spotify(id) => {
+                write_track_album!(f, "spotify", SpotifyId, id, Artist => "artist")
+            }
+            Self::Tidal(id) => write_track_album!(f, "tidal", TidalId, id, Artist => "artist"),
+            Self::Yandex(id) => write_track_album!(f, "yandex", YandexId, id),
+            Self::YouTube(YouTubeId::Video(id)) => write!(f, "youtube:video:{}", id),
+            Self::YouTube(YouTubeId::Album(id)) => write!(f, "youtube:album:{}", id),
+            Self::YouTube(YouTubeId::Artist(id)) => write!(f, "youtube:artist:{}", id),
+        }
+    }
+}
+
+macro_rules! parse_track_album {
+    ($ty:ident, $kind:expr, $id:expr $(, $key:literal => $variant:ident)*) => {
+        match $kind {
+            "album" => $ty::Album($id.parse().map_err(|_| ())?),
+            "track" => $ty::Track($id.parse().map_err(|_| ())?),
+            $( $key => $ty::$variant($id.parse().map_err(|_| ())?), )*
+            _ => return Err(()),
+        }
+    };
+}
+
+/// Parses a key created by the [`fmt::Display`] implementation.
+///
+/// Slugs are lowercased like when parsing a link, keys whose canonical link doesn't parse back to
+/// the same ID are rejected, e.g. reserved SoundCloud user names or characters that can't be part
+/// of a link.
+impl FromStr for ProviderId {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, ':');
+        let (Some(provider), Some(kind), Some(id)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(());
+        };
+
+        let res = match provider {
+            "amazon_music" => Self::AmazonMusic(match kind {
+                "album" => AmazonMusicId::Album(id.to_string()),
+                "artist" => AmazonMusicId::Artist(id.to_string()),
+                "track" => {
+                    let (album_id, track_id) = id.split_once(':').ok_or(())?;
+                    AmazonMusicId::Track {
+                        album_id: album_id.to_string(),
+                        track_id: track_id.to_string(),
+                    }
+                }
+                _ => return Err(()),
+            }),
+            "anghami" => Self::Anghami(parse_track_album!(AnghamiId, kind, id)),
+            "apple_music" => Self::AppleMusic(parse_track_album!(
+                AppleMusicId, kind, id, "artist" => Artist
+            )),
+            "audiomack" => {
+                Self::Audiomack(parse_track_album!(AudiomackId, kind, id.to_lowercase()))
+            }
+            "audius" => Self::Audius(parse_track_album!(AudiusId, kind, id.to_lowercase())),
+            "bandcamp" => Self::Bandcamp(parse_track_album!(BandcampId, kind, id.to_lowercase())),
+            "boomplay" => Self::BoomPlay(parse_track_album!(BoomPlayId, kind, id)),
+            "deezer" => Self::Deezer(parse_track_album!(DeezerId, kind, id, "artist" => Artist)),
+            "napster" => Self::Napster(parse_track_album!(NapsterId, kind, id)),
+            "pandora" => Self::Pandora(parse_track_album!(PandoraId, kind, id)),
+            "soundcloud" => {
+                Self::SoundCloud(parse_track_album!(SoundCloudId, kind, id.to_lowercase()))
+            }
+            "spinrilla" => {
+                Self::Spinrilla(parse_track_album!(SpinrillaId, kind, id.to_lowercase()))
+            }
+            "spotify" => Self::Spotify(parse_track_album!(SpotifyId, kind, id, "artist" => Artist)),
+            "tidal" => Self::Tidal(parse_track_album!(TidalId, kind, id, "artist" => Artist)),
+            "yandex" => Self::Yandex(parse_track_album!(YandexId, kind, id)),
+            "youtube" => Self::YouTube(match kind {
+                "video" => YouTubeId::Video(id.to_string()),
+                "album" => YouTubeId::Album(id.to_string()),
+                "artist" => YouTubeId::Artist(id.to_string()),
+                _ => return Err(()),
+            }),
+            _ => return Err(()),
+        };
+
+        if !res.round_trips() {
+            return Err(());
+        }
+        Ok(res)
+    }
+}
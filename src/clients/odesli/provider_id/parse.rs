@@ -8,10 +8,62 @@ use std::fmt;
 use url::Url;
 
 use super::{
-    AmazonMusicId, AnghamiId, AppleMusicId, BoomPlayId, DeezerId, NapsterId, PandoraId, ProviderId,
-    SpotifyId, TidalId, YandexId, YouTubeId,
+    AmazonMusicId, AnghamiId, AppleMusicId, AudiomackId, AudiusId, BandcampId, BoomPlayId,
+    DeezerId, NapsterId, PandoraId, ProviderId, SoundCloudId, SpinrillaId, SpotifyId, TidalId,
+    YandexId, YouTubeId,
 };
 
+/// Top level pages of SoundCloud, which would otherwise be mistaken for user names
+const SOUNDCLOUD_RESERVED_USERS: &[&str] = &[
+    "charts",
+    "discover",
+    "feed",
+    "messages",
+    "mobile",
+    "notifications",
+    "pages",
+    "people",
+    "search",
+    "settings",
+    "stations",
+    "stream",
+    "tags",
+    "upload",
+    "you",
+];
+/// Pages of SoundCloud users, which would otherwise be mistaken for tracks
+const SOUNDCLOUD_USER_PAGES: &[&str] = &[
+    "albums",
+    "comments",
+    "followers",
+    "following",
+    "likes",
+    "popular-tracks",
+    "reposts",
+    "sets",
+    "spotlight",
+    "tracks",
+];
+/// Top level pages of Audius, which would otherwise be mistaken for user names
+const AUDIUS_RESERVED_USERS: &[&str] = &[
+    "explore",
+    "favorites",
+    "feed",
+    "history",
+    "library",
+    "messages",
+    "search",
+    "settings",
+    "signin",
+    "signup",
+    "trending",
+    "upload",
+];
+/// Pages of Audius users, which would otherwise be mistaken for tracks
+const AUDIUS_USER_PAGES: &[&str] = &["albums", "collectibles", "playlists", "reposts", "tracks"];
+/// Albums on YouTube Music are playlists with a list ID starting with this prefix
+const YOUTUBE_ALBUM_PREFIX: &str = "OLAK5uy_";
+
 #[derive(Debug)]
 pub struct InvalidProviderUrl {
     pub invalid_url: Url,
@@ -89,11 +141,17 @@ impl AmazonMusicId {
     fn from_url(url: &Url) -> Option<Self> {
         let mut path_segments = url.path_segments()?;
 
-        if path_segments.next() != Some("albums") {
+        let content_type = path_segments.next()?;
+        let id = path_segments.next()?.to_string();
+
+        if content_type == "artists" {
+            // Artist links may be followed by the artist name
+            return Some(Self::Artist(id));
+        }
+        if content_type != "albums" {
             return None;
         }
-
-        let album_id = path_segments.next()?.to_string();
+        let album_id = id;
 
         if path_segments.next().is_some() {
             return None;
@@ -122,12 +180,17 @@ impl AppleMusicId {
 
         let _country_code = path_segments.next()?;
         let content_type = path_segments.next()?;
-        let _item_name = path_segments.next()?;
-        let id = path_segments.next()?.parse().ok()?;
+        // The name of the item can be left out, e.g. in canonical links
+        let id = match (path_segments.next()?, path_segments.next()) {
+            (_item_name, Some(id)) => id.parse().ok()?,
+            (id, None) => id.parse().ok()?,
+        };
 
-        // If it's a song, the id is in the path and we can return immediately.
-        if content_type == "song" {
-            return Some(Self::Track(id));
+        // If it's a song or artist, the id is in the path and we can return immediately.
+        match content_type {
+            "song" => return Some(Self::Track(id)),
+            "artist" => return Some(Self::Artist(id)),
+            _ => {}
         }
 
         // If it's an album, we need to check for a track ID in the query parameters.
@@ -151,8 +214,93 @@ impl AppleMusicId {
     }
 }
 
+/// The non-empty path segments, so links with a trailing slash are parsed the same
+fn path_segments(url: &Url) -> Option<Vec<&str>> {
+    Some(url.path_segments()?.filter(|s| !s.is_empty()).collect())
+}
+
+fn query_param(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, value)| value.into_owned())
+}
+
+/// Whether the path segment is one of the reserved names, which are matched case-insensitively
+/// like the slugs
+fn is_reserved(reserved: &[&str], segment: &str) -> bool {
+    reserved
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(segment))
+}
+
+/// Joins the artist and the slug into an ID, slugs are case-insensitive so they are lowercased
+fn slug_id(artist: &str, slug: &str) -> String {
+    format!("{}/{}", artist.to_lowercase(), slug.to_lowercase())
+}
+
+impl AudiomackId {
+    fn from_url(url: &Url) -> Option<Self> {
+        match path_segments(url)?.as_slice() {
+            [artist, "song", slug] | ["song", artist, slug] => {
+                Some(Self::Track(slug_id(artist, slug)))
+            }
+            [artist, "album", slug] | ["album", artist, slug] => {
+                Some(Self::Album(slug_id(artist, slug)))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl AudiusId {
+    fn from_url(url: &Url) -> Option<Self> {
+        match path_segments(url)?.as_slice() {
+            [artist, "album", slug] => Some(Self::Album(slug_id(artist, slug))),
+            [artist, slug]
+                if !is_reserved(AUDIUS_RESERVED_USERS, artist)
+                    && !is_reserved(AUDIUS_USER_PAGES, slug) =>
+            {
+                Some(Self::Track(slug_id(artist, slug)))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl BandcampId {
+    /// Bandcamp links are on the subdomain of the artist, e.g. `artist.bandcamp.com/track/slug`
+    fn from_url(url: &Url, artist: &str) -> Option<Self> {
+        match path_segments(url)?.as_slice() {
+            ["track", slug] => Some(Self::Track(slug_id(artist, slug))),
+            ["album", slug] => Some(Self::Album(slug_id(artist, slug))),
+            _ => None,
+        }
+    }
+}
+
 impl_parse_track_album_providers!(BoomPlayId, "songs", "albums");
-impl_parse_track_album_providers!(DeezerId);
+
+impl DeezerId {
+    fn from_url(url: &Url) -> Option<Self> {
+        // Links shared from the website are prefixed with the language, e.g. `/en/track/<id>`
+        let segments = path_segments(url)?;
+        let segments = match segments.as_slice() {
+            [lang, rest @ ..]
+                if lang.len() == 2 && lang.chars().all(|c| c.is_ascii_lowercase()) =>
+            {
+                rest
+            }
+            rest => rest,
+        };
+
+        match segments {
+            ["track", id] => Some(Self::Track(id.parse().ok()?)),
+            ["album", id] => Some(Self::Album(id.parse().ok()?)),
+            ["artist", id] => Some(Self::Artist(id.parse().ok()?)),
+            _ => None,
+        }
+    }
+}
 
 impl NapsterId {
     fn from_url(url: &Url) -> Option<Self> {
@@ -207,16 +355,104 @@ impl PandoraId {
     }
 }
 
-impl_parse_track_album_providers!(SpotifyId);
-impl_parse_track_album_providers!(TidalId);
+impl SoundCloudId {
+    fn from_url(url: &Url) -> Option<Self> {
+        match path_segments(url)?.as_slice() {
+            [user, "sets", slug] => Some(Self::Album(slug_id(user, slug))),
+            [user, slug]
+                if !is_reserved(SOUNDCLOUD_RESERVED_USERS, user)
+                    && !is_reserved(SOUNDCLOUD_USER_PAGES, slug) =>
+            {
+                Some(Self::Track(slug_id(user, slug)))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl SpinrillaId {
+    fn from_url(url: &Url) -> Option<Self> {
+        match path_segments(url)?.as_slice() {
+            ["songs", slug] => Some(Self::Track(slug.to_lowercase())),
+            ["mixtapes", slug] => Some(Self::Album(slug.to_lowercase())),
+            _ => None,
+        }
+    }
+}
+
+impl SpotifyId {
+    fn from_url(url: &Url) -> Option<Self> {
+        // Localised links are prefixed with the locale, e.g. `/intl-de/track/<id>`, embedded
+        // players with `/embed`
+        let segments = path_segments(url)?;
+        let segments = match segments.as_slice() {
+            [prefix, rest @ ..] if prefix.starts_with("intl-") || *prefix == "embed" => rest,
+            rest => rest,
+        };
+
+        match segments {
+            ["track", id] => Some(Self::Track(id.to_string())),
+            ["album", id] => Some(Self::Album(id.to_string())),
+            ["artist", id] => Some(Self::Artist(id.to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl TidalId {
+    fn from_url(url: &Url) -> Option<Self> {
+        // Links shared from the web player are prefixed with `/browse`
+        let segments = path_segments(url)?;
+        let segments = match segments.as_slice() {
+            ["browse", rest @ ..] => rest,
+            rest => rest,
+        };
+
+        match segments {
+            ["track", id] => Some(Self::Track(id.parse().ok()?)),
+            ["album", id] => Some(Self::Album(id.parse().ok()?)),
+            ["artist", id] => Some(Self::Artist(id.parse().ok()?)),
+            _ => None,
+        }
+    }
+}
 impl_parse_track_album_providers!(YandexId);
 
 impl YouTubeId {
     fn from_url(url: &Url) -> Option<Self> {
+        // Short links only contain the video ID
+        if url.domain() == Some("youtu.be") {
+            return match path_segments(url)?.as_slice() {
+                [video_id] => Some(Self::Video(video_id.to_string())),
+                _ => None,
+            };
+        }
+
         let mut path_segments = url.path_segments()?;
 
-        if path_segments.next()? != "watch" {
-            return None;
+        match path_segments.next()? {
+            "watch" => {}
+            "playlist" => {
+                if path_segments.next().is_some() {
+                    return None;
+                }
+
+                let list_id = query_param(url, "list")?;
+
+                // Only albums are supported, other playlists don't have an entity on Odesli
+                return list_id
+                    .starts_with(YOUTUBE_ALBUM_PREFIX)
+                    .then_some(Self::Album(list_id));
+            }
+            // Artist channels only exist on YouTube Music
+            "channel" if url.domain() == Some("music.youtube.com") => {
+                let channel_id = path_segments.next()?;
+                if path_segments.next().is_some() {
+                    return None;
+                }
+                return Some(Self::Artist(channel_id.to_string()));
+            }
+            _ => return None,
         }
         if path_segments.next().is_some() {
             return None;
@@ -230,7 +466,7 @@ impl YouTubeId {
             }
         })?;
 
-        Some(Self(video_id))
+        Some(Self::Video(video_id))
     }
 }
 
@@ -250,14 +486,29 @@ impl ProviderId {
             "music.apple.com" | "geo.music.apple.com" => {
                 AppleMusicId::from_url(url).map(Self::AppleMusic)
             }
+            "audiomack.com" | "www.audiomack.com" => {
+                AudiomackId::from_url(url).map(Self::Audiomack)
+            }
+            "audius.co" => AudiusId::from_url(url).map(Self::Audius),
             "www.boomplay.com" => BoomPlayId::from_url(url).map(Self::BoomPlay),
             "www.deezer.com" => DeezerId::from_url(url).map(Self::Deezer),
             "play.napster.com" => NapsterId::from_url(url).map(Self::Napster),
             "www.pandora.com" => PandoraId::from_url(url).map(Self::Pandora),
+            "soundcloud.com" | "www.soundcloud.com" | "m.soundcloud.com" => {
+                SoundCloudId::from_url(url).map(Self::SoundCloud)
+            }
+            "spinrilla.com" | "www.spinrilla.com" => {
+                SpinrillaId::from_url(url).map(Self::Spinrilla)
+            }
             "open.spotify.com" => SpotifyId::from_url(url).map(Self::Spotify),
             "tidal.com" | "listen.tidal.com" => TidalId::from_url(url).map(Self::Tidal),
             "music.yandex.ru" => YandexId::from_url(url).map(Self::Yandex),
-            "www.youtube.com" | "music.youtube.com" => YouTubeId::from_url(url).map(Self::YouTube),
+            "www.youtube.com" | "youtube.com" | "m.youtube.com" | "music.youtube.com"
+            | "youtu.be" => YouTubeId::from_url(url).map(Self::YouTube),
+            domain if domain.ends_with(".bandcamp.com") => {
+                let artist = domain.trim_end_matches(".bandcamp.com");
+                BandcampId::from_url(url, artist).map(Self::Bandcamp)
+            }
             other_domain => {
                 return Err(InvalidProviderUrl {
                     invalid_url: url.clone(),
@@ -274,3 +525,278 @@ impl ProviderId {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Links as they are shared from the apps and websites, with the key they should parse to
+    const VALID_LINKS: &[(&str, &str)] = &[
+        // Amazon Music
+        (
+            "https://music.amazon.com/albums/B00TF1DUIA?trackAsin=B00TF1E3RO&ref=dm_sh_abc",
+            "amazon_music:track:B00TF1DUIA:B00TF1E3RO",
+        ),
+        (
+            "https://music.amazon.com/albums/B00TF1DUIA",
+            "amazon_music:album:B00TF1DUIA",
+        ),
+        (
+            "https://music.amazon.com/artists/B000QJPGV4/rick-astley",
+            "amazon_music:artist:B000QJPGV4",
+        ),
+        // Anghami
+        (
+            "https://play.anghami.com/song/23451256",
+            "anghami:track:23451256",
+        ),
+        (
+            "https://play.anghami.com/album/1015716",
+            "anghami:album:1015716",
+        ),
+        // Apple Music
+        (
+            "https://music.apple.com/us/album/never-gonna-give-you-up/1559523357?i=1559523359",
+            "apple_music:track:1559523359",
+        ),
+        (
+            "https://music.apple.com/de/album/whenever-you-need-somebody/1559523357",
+            "apple_music:album:1559523357",
+        ),
+        (
+            "https://music.apple.com/gb/song/never-gonna-give-you-up/1559523359",
+            "apple_music:track:1559523359",
+        ),
+        (
+            "https://music.apple.com/us/song/1559523359",
+            "apple_music:track:1559523359",
+        ),
+        (
+            "https://geo.music.apple.com/us/album/_/1559523357?i=1559523359&mt=1&app=music",
+            "apple_music:track:1559523359",
+        ),
+        (
+            "https://music.apple.com/us/artist/rick-astley/669771",
+            "apple_music:artist:669771",
+        ),
+        // Audiomack
+        (
+            "https://audiomack.com/some-artist/song/some-song",
+            "audiomack:track:some-artist/some-song",
+        ),
+        (
+            "https://audiomack.com/song/some-artist/some-song",
+            "audiomack:track:some-artist/some-song",
+        ),
+        (
+            "https://www.audiomack.com/Some-Artist/album/Some-Album/",
+            "audiomack:album:some-artist/some-album",
+        ),
+        // Audius
+        (
+            "https://audius.co/someartist/some-song-123",
+            "audius:track:someartist/some-song-123",
+        ),
+        (
+            "https://audius.co/someartist/album/some-album-45",
+            "audius:album:someartist/some-album-45",
+        ),
+        // Bandcamp
+        (
+            "https://someartist.bandcamp.com/track/some-song",
+            "bandcamp:track:someartist/some-song",
+        ),
+        (
+            "https://someartist.bandcamp.com/album/some-album?from=discover_page",
+            "bandcamp:album:someartist/some-album",
+        ),
+        // BoomPlay
+        (
+            "https://www.boomplay.com/songs/1234567",
+            "boomplay:track:1234567",
+        ),
+        (
+            "https://www.boomplay.com/albums/7654321",
+            "boomplay:album:7654321",
+        ),
+        // Deezer
+        (
+            "https://www.deezer.com/track/781592622",
+            "deezer:track:781592622",
+        ),
+        (
+            "https://www.deezer.com/en/track/781592622?utm_source=deezer&utm_medium=share",
+            "deezer:track:781592622",
+        ),
+        (
+            "https://www.deezer.com/us/album/216016282",
+            "deezer:album:216016282",
+        ),
+        (
+            "https://www.deezer.com/fr/artist/1152",
+            "deezer:artist:1152",
+        ),
+        // Napster
+        (
+            "https://play.napster.com/track/tra.5156528",
+            "napster:track:5156528",
+        ),
+        (
+            "https://play.napster.com/album/alb.5156492",
+            "napster:album:5156492",
+        ),
+        // Pandora
+        (
+            "https://www.pandora.com/TR:1234567",
+            "pandora:track:1234567",
+        ),
+        (
+            "https://www.pandora.com/AL:7654321",
+            "pandora:album:7654321",
+        ),
+        // SoundCloud
+        (
+            "https://soundcloud.com/rick-astley-official/never-gonna-give-you-up-4",
+            "soundcloud:track:rick-astley-official/never-gonna-give-you-up-4",
+        ),
+        (
+            "https://m.soundcloud.com/Some-Artist/Some-Song?utm_source=clipboard&in=x/sets/y",
+            "soundcloud:track:some-artist/some-song",
+        ),
+        (
+            "https://soundcloud.com/some-artist/sets/some-album/",
+            "soundcloud:album:some-artist/some-album",
+        ),
+        // Spinrilla
+        (
+            "https://spinrilla.com/songs/12345-some-song",
+            "spinrilla:track:12345-some-song",
+        ),
+        (
+            "https://www.spinrilla.com/mixtapes/some-mixtape",
+            "spinrilla:album:some-mixtape",
+        ),
+        // Spotify
+        (
+            "https://open.spotify.com/track/4cOdK2wGLETKBW3PvgPWqT?si=a1b2c3d4e5f6",
+            "spotify:track:4cOdK2wGLETKBW3PvgPWqT",
+        ),
+        (
+            "https://open.spotify.com/intl-de/track/4cOdK2wGLETKBW3PvgPWqT",
+            "spotify:track:4cOdK2wGLETKBW3PvgPWqT",
+        ),
+        (
+            "https://open.spotify.com/embed/track/4cOdK2wGLETKBW3PvgPWqT",
+            "spotify:track:4cOdK2wGLETKBW3PvgPWqT",
+        ),
+        (
+            "https://open.spotify.com/album/6XhjNHCyCDyyGJRM5mg40G/",
+            "spotify:album:6XhjNHCyCDyyGJRM5mg40G",
+        ),
+        (
+            "https://open.spotify.com/artist/0gxyHStUsqpMadRV0Di1Qt",
+            "spotify:artist:0gxyHStUsqpMadRV0Di1Qt",
+        ),
+        // Tidal
+        ("https://tidal.com/track/180783437", "tidal:track:180783437"),
+        (
+            "https://tidal.com/browse/track/180783437",
+            "tidal:track:180783437",
+        ),
+        (
+            "https://listen.tidal.com/album/180783436/",
+            "tidal:album:180783436",
+        ),
+        ("https://tidal.com/browse/artist/3544", "tidal:artist:3544"),
+        // Yandex
+        ("https://music.yandex.ru/track/12345", "yandex:track:12345"),
+        ("https://music.yandex.ru/album/54321", "yandex:album:54321"),
+        // YouTube
+        (
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=42s",
+            "youtube:video:dQw4w9WgXcQ",
+        ),
+        (
+            "https://m.youtube.com/watch?v=dQw4w9WgXcQ",
+            "youtube:video:dQw4w9WgXcQ",
+        ),
+        (
+            "https://youtu.be/dQw4w9WgXcQ?si=abcdef",
+            "youtube:video:dQw4w9WgXcQ",
+        ),
+        (
+            "https://music.youtube.com/watch?v=dQw4w9WgXcQ&feature=share",
+            "youtube:video:dQw4w9WgXcQ",
+        ),
+        (
+            "https://music.youtube.com/playlist?list=OLAK5uy_k8AhbDdJ2vcx6X3z",
+            "youtube:album:OLAK5uy_k8AhbDdJ2vcx6X3z",
+        ),
+        (
+            "https://music.youtube.com/channel/UCuAXFkgsw1L7xaCfnd5JJOw",
+            "youtube:artist:UCuAXFkgsw1L7xaCfnd5JJOw",
+        ),
+    ];
+
+    /// Links to supported platforms, that don't link to a single track, album or artist
+    const MALFORMED_LINKS: &[&str] = &[
+        "https://open.spotify.com/playlist/37i9dQZF1DXcBWIGoYBM5M",
+        "https://open.spotify.com/user/spotify",
+        "https://open.spotify.com/",
+        "https://music.apple.com/us/playlist/todays-hits/pl.f4d106fed2bd41149aaacabb233eb5eb",
+        "https://music.apple.com/us/album/not-a-number",
+        "https://www.deezer.com/en/playlist/1234",
+        "https://www.deezer.com/track/not-a-number",
+        "https://tidal.com/browse/playlist/d1f6f3a4-0b1c-4d8e-9a3f-0a1b2c3d4e5f",
+        "https://www.youtube.com/playlist?list=PLFgquLnL59alCl_2TQvOiD5Vgm1hCaGSI",
+        "https://www.youtube.com/shorts/dQw4w9WgXcQ",
+        "https://www.youtube.com/channel/UCuAXFkgsw1L7xaCfnd5JJOw",
+        "https://soundcloud.com/discover/sets",
+        "https://soundcloud.com/some-artist/likes",
+        "https://soundcloud.com/some-artist",
+        "https://audius.co/trending/week",
+        "https://audius.co/someartist/playlists",
+        "https://someartist.bandcamp.com/music",
+        "https://play.napster.com/track/5156528",
+        "https://www.pandora.com/artist/AR:1234",
+    ];
+
+    #[test]
+    fn parses_real_world_links() {
+        for (link, key) in VALID_LINKS {
+            let url = Url::parse(link).unwrap();
+            let id = ProviderId::parse_url(&url)
+                .unwrap_or_else(|err| panic!("{link} should be parsed: {err}"));
+
+            assert_eq!(id.to_string(), *key, "{link}");
+            assert_eq!(key.parse::<ProviderId>(), Ok(id), "{key}");
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_links() {
+        for link in MALFORMED_LINKS {
+            let url = Url::parse(link).unwrap();
+            let err = ProviderId::parse_url(&url).expect_err(link);
+
+            assert!(
+                matches!(err.reason, InvalidProviderIdReason::MalformedUrl),
+                "{link}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_domains() {
+        let url = Url::parse("https://example.com/track/12345").unwrap();
+        let err = ProviderId::parse_url(&url).unwrap_err();
+        assert!(matches!(
+            err.reason,
+            InvalidProviderIdReason::UnknownDomain(domain) if domain == "example.com"
+        ));
+
+        let url = Url::parse("http://127.0.0.1/track/12345").unwrap();
+        let err = ProviderId::parse_url(&url).unwrap_err();
+        assert!(matches!(err.reason, InvalidProviderIdReason::MissingDomain));
+    }
+}
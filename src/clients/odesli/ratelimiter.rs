@@ -3,81 +3,406 @@
  * All Rights Reserved
  */
 
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
 use std::sync::Arc;
-use std::sync::atomic::AtomicU64;
 use std::time::Duration;
 
-use prometheus_client::metrics::gauge::Gauge;
-use tokio::sync::Semaphore;
+use parking_lot::Mutex;
+use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
-use tokio::time::interval;
-use tracing::debug;
+use tokio::time::{Instant, sleep_until, timeout_at};
+use tracing::{debug, info, warn};
 
-type RateLimitMetric = Gauge<u64, AtomicU64>;
+use crate::metrics::MetricsStore;
+use crate::metrics::labels::{RateLimitState, RateLimitStateLabels};
 
+/// The most the refill interval is stretched after repeated 429 responses
+const MAX_SLOWDOWN: u32 = 16;
+/// The number of tokens handed out at a reduced rate before the rate is doubled again
+const RECOVERY_TOKENS: u32 = 10;
+/// Upper bound for pauses, in case the API asks for an unreasonably long one
+const MAX_BACKOFF: Duration = Duration::from_hours(1);
+
+/// How urgent a request is, waiting requests with a higher priority are served first
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RequestPriority {
+    /// Requests nobody is actively waiting on, e.g. from the REST API
+    Background,
+    /// Requests for multiple links at once, e.g. Find Links on a message
+    Batch,
+    /// A user waiting on a single link, e.g. `/share`
+    Interactive,
+}
+
+/// Controls what happens when no token is available
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AcquirePolicy {
+    /// Wait until a token becomes available, giving up if that can't happen before the deadline
+    Wait {
+        priority: RequestPriority,
+        deadline: Option<Instant>,
+    },
+    /// Give up right away if no token is available
+    Immediate,
+}
+
+impl AcquirePolicy {
+    /// Wait for a token, giving up if it can't be acquired before the deadline
+    pub const fn wait_until(priority: RequestPriority, deadline: Instant) -> Self {
+        Self::Wait {
+            priority,
+            deadline: Some(deadline),
+        }
+    }
+
+    pub const fn deadline(&self) -> Option<Instant> {
+        match self {
+            Self::Wait { deadline, .. } => *deadline,
+            Self::Immediate => None,
+        }
+    }
+}
+
+/// Why no token was acquired
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AcquireErr {
+    /// No token was available and the request was not allowed to wait
+    Unavailable,
+    /// No token could be acquired before the deadline of the request
+    DeadlineExceeded,
+}
+
+struct Waiter {
+    priority: RequestPriority,
+    /// Used to serve waiters with the same priority in order of arrival
+    seq: u64,
+    sender: oneshot::Sender<()>,
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiter {
+    /// The greatest waiter is served first: highest priority, then the one waiting the longest
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+struct BucketState {
+    tokens: usize,
+    waiters: BinaryHeap<Waiter>,
+    next_seq: u64,
+    /// No tokens are handed out until then, set when the API responded with a 429
+    paused_until: Option<Instant>,
+    /// Factor the refill interval is stretched by while recovering from a 429
+    slowdown: u32,
+    /// Tokens handed out since the last change of the slowdown
+    recovery_progress: u32,
+}
+
+impl BucketState {
+    /// Hands the token to the next waiter still waiting, or stores it if there is none
+    fn release_token(&mut self, capacity: usize) {
+        while let Some(waiter) = self.waiters.pop() {
+            if waiter.sender.send(()).is_ok() {
+                self.recover();
+                return;
+            }
+            // The waiter gave up, try the next one
+        }
+
+        if self.tokens < capacity {
+            self.tokens += 1;
+        }
+    }
+
+    /// Takes a stored token if one is available
+    fn take_token(&mut self) -> bool {
+        if self.tokens == 0 {
+            return false;
+        }
+
+        self.tokens -= 1;
+        self.recover();
+        true
+    }
+
+    /// Speeds the refill back up once enough tokens were handed out without another 429, called
+    /// for every token handed out to a request
+    fn recover(&mut self) {
+        if self.slowdown == 1 {
+            return;
+        }
+
+        self.recovery_progress += 1;
+        if self.recovery_progress >= RECOVERY_TOKENS {
+            self.slowdown /= 2;
+            self.recovery_progress = 0;
+            debug!(slowdown = self.slowdown, "Odesli rate limiter recovering");
+        }
+    }
+
+    fn is_paused(&self, now: Instant) -> bool {
+        self.paused_until.is_some_and(|until| until > now)
+    }
+
+    fn limiter_state(&self, now: Instant) -> RateLimitState {
+        if self.is_paused(now) {
+            RateLimitState::Paused
+        } else if self.slowdown > 1 {
+            RateLimitState::Recovering
+        } else {
+            RateLimitState::Normal
+        }
+    }
+
+    /// The number of waiters that would be served before a new one with the priority
+    fn waiters_ahead(&self, priority: RequestPriority) -> usize {
+        self.waiters
+            .iter()
+            .filter(|waiter| waiter.priority >= priority && !waiter.sender.is_closed())
+            .count()
+    }
+}
+
+/// Token bucket refilled at a constant rate, serving waiting requests by their priority.
+///
+/// When the API responds with a 429 anyway, the bucket is drained and refilling is paused. It
+/// then resumes at a reduced rate, which is doubled again every few tokens until it is back to
+/// the configured one.
 pub(super) struct OdesliRateLimiter {
-    semaphore: Arc<Semaphore>,
+    state: Arc<Mutex<BucketState>>,
+    refill_interval: Duration,
     jh: JoinHandle<()>,
-    metric: RateLimitMetric,
+    metrics: MetricsStore,
 }
 
 impl OdesliRateLimiter {
-    pub fn new(hourly_limit: usize, metric: RateLimitMetric) -> Self {
-        Self::new_with_initial_tokens(hourly_limit, 0, metric)
+    pub fn new(hourly_limit: usize, metrics: MetricsStore) -> Self {
+        Self::new_with_initial_tokens(hourly_limit, 0, metrics)
     }
 
     pub fn new_with_initial_tokens(
         hourly_limit: usize,
         initial_tokens: usize,
-        metric: RateLimitMetric,
+        metrics: MetricsStore,
     ) -> Self {
-        let semaphore = Arc::new(Semaphore::new(initial_tokens));
+        let state = Arc::new(Mutex::new(BucketState {
+            tokens: initial_tokens,
+            waiters: BinaryHeap::new(),
+            next_seq: 0,
+            paused_until: None,
+            slowdown: 1,
+            recovery_progress: 0,
+        }));
+        report_state(&metrics, &state.lock());
+
+        let refill_interval = Duration::from_secs_f64(3600.0 / hourly_limit as f64);
         let jh = tokio::spawn(Self::bucket_fill_task(
-            semaphore.clone(),
-            metric.clone(),
+            state.clone(),
+            metrics.clone(),
+            refill_interval,
             hourly_limit,
         ));
 
         Self {
-            semaphore,
+            state,
+            refill_interval,
             jh,
-            metric,
+            metrics,
         }
     }
 
     async fn bucket_fill_task(
-        semaphore: Arc<Semaphore>,
-        metric: RateLimitMetric,
+        state: Arc<Mutex<BucketState>>,
+        metrics: MetricsStore,
+        refill_interval: Duration,
         hourly_limit: usize,
     ) {
-        let duration = Duration::from_secs_f64(3600.0 / hourly_limit as f64);
-        let mut interval = interval(duration);
-        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
-        // Initial tick returns immediately, so we poll it first before entering the loop
-        interval.tick().await;
+        let mut next_refill = Instant::now() + refill_interval;
 
         loop {
-            interval.tick().await;
+            sleep_until(next_refill).await;
 
-            if semaphore.available_permits() < hourly_limit {
-                semaphore.add_permits(1);
+            let now = Instant::now();
+            let mut bucket = state.lock();
+            if let Some(until) = bucket.paused_until.filter(|until| *until > now) {
+                // A 429 arrived while sleeping, wait for the pause to end
+                next_refill = until;
+                continue;
             }
-            metric.set(semaphore.available_permits() as u64);
+            if bucket.paused_until.take().is_some() {
+                info!(
+                    slowdown = bucket.slowdown,
+                    "Odesli rate limit pause ended, resuming at a reduced rate"
+                );
+            }
+
+            bucket.release_token(hourly_limit);
+            report_state(&metrics, &bucket);
+
+            next_refill = now + refill_interval * bucket.slowdown;
         }
     }
 
-    /// Acquires a token from the bucket, waiting if necessary until one is available.
-    pub async fn acquire(&self) {
-        let permit = self
-            .semaphore
-            .acquire()
-            .await
-            .expect("Semaphore should never be closed");
-        // Don't release the permit back to the semaphore
-        permit.forget();
-        self.metric.dec();
+    /// Takes a token if one is available right away
+    fn take_token(&self, state: &mut BucketState) -> bool {
+        if !state.take_token() {
+            return false;
+        }
+
+        report_state(&self.metrics, state);
+        true
+    }
+
+    /// Whether a new waiter with the priority can expect a token before the deadline, assuming
+    /// every waiter ahead of it takes one of the next tokens
+    fn reachable_by(
+        &self,
+        state: &BucketState,
+        priority: RequestPriority,
+        deadline: Instant,
+    ) -> bool {
+        let now = Instant::now();
+        let ahead = state.waiters_ahead(priority) as u32;
+        let refill_start = state.paused_until.map_or(now, |until| until.max(now));
+
+        refill_start + self.refill_interval * state.slowdown * (ahead + 1) <= deadline
+    }
+
+    /// Backs off after the API responded with a 429, draining the bucket and pausing the refill
+    /// for the `Retry-After` duration if the API sent one.
+    ///
+    /// Responses to requests sent before the pause started don't slow the limiter down further.
+    pub fn on_rate_limited(&self, retry_after: Option<Duration>) {
+        let now = Instant::now();
+        let mut state = self.state.lock();
+
+        state.tokens = 0;
+        if !state.is_paused(now) {
+            state.slowdown = (state.slowdown * 2).min(MAX_SLOWDOWN);
+        }
+        state.recovery_progress = 0;
+
+        let backoff = retry_after
+            .unwrap_or(self.refill_interval * state.slowdown)
+            .min(MAX_BACKOFF);
+        let until = now + backoff;
+        state.paused_until = Some(state.paused_until.map_or(until, |prev| prev.max(until)));
+
+        warn!(
+            ?backoff,
+            slowdown = state.slowdown,
+            "Odesli rate limit exceeded, pausing requests"
+        );
+        report_state(&self.metrics, &state);
+    }
+
+    /// Acquires a token, waiting behind requests with the same or a higher priority until one is
+    /// available.
+    ///
+    /// Fails early if the waiting requests ahead already use up all tokens until the deadline.
+    pub async fn acquire(
+        &self,
+        priority: RequestPriority,
+        deadline: Option<Instant>,
+    ) -> Result<(), AcquireErr> {
+        let receiver = {
+            let mut state = self.state.lock();
+            if self.take_token(&mut state) {
+                debug!("Odesli rate limit token acquired, proceeding with request.");
+                return Ok(());
+            }
+
+            if deadline.is_some_and(|deadline| !self.reachable_by(&state, priority, deadline)) {
+                debug!(
+                    ?priority,
+                    "Odesli rate limit token can't be acquired before the deadline"
+                );
+                return Err(AcquireErr::DeadlineExceeded);
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            let seq = state.next_seq;
+            state.next_seq += 1;
+            state.waiters.push(Waiter {
+                priority,
+                seq,
+                sender,
+            });
+            receiver
+        };
+
+        debug!(?priority, "Waiting for an Odesli rate limit token");
+        let result = match deadline {
+            Some(deadline) => timeout_at(deadline, receiver)
+                .await
+                .map_err(|_| AcquireErr::DeadlineExceeded)?,
+            None => receiver.await,
+        };
+        // The sender is only dropped without sending if the limiter itself is dropped
+        result.map_err(|_| AcquireErr::Unavailable)?;
 
         debug!("Odesli rate limit token acquired, proceeding with request.");
+        Ok(())
+    }
+
+    /// Acquires a token from the bucket if one is available right away, returns whether a token
+    /// was acquired.
+    pub fn try_acquire(&self) -> bool {
+        let mut state = self.state.lock();
+        if !self.take_token(&mut state) {
+            debug!("No Odesli rate limit token available.");
+            return false;
+        }
+
+        debug!("Odesli rate limit token acquired, proceeding with request.");
+        true
+    }
+
+    /// Acquires a token according to the given policy
+    pub async fn acquire_with(&self, policy: AcquirePolicy) -> Result<(), AcquireErr> {
+        match policy {
+            AcquirePolicy::Wait { priority, deadline } => self.acquire(priority, deadline).await,
+            AcquirePolicy::Immediate => self
+                .try_acquire()
+                .then_some(())
+                .ok_or(AcquireErr::Unavailable),
+        }
+    }
+}
+
+/// Updates the token gauge and marks the current state of the limiter
+fn report_state(metrics: &MetricsStore, state: &BucketState) {
+    metrics.odesli_rate_limit_tokens().set(state.tokens as u64);
+
+    let current = state.limiter_state(Instant::now());
+    for limiter_state in [
+        RateLimitState::Normal,
+        RateLimitState::Paused,
+        RateLimitState::Recovering,
+    ] {
+        metrics
+            .odesli_rate_limit_state()
+            .get_or_create(&RateLimitStateLabels {
+                state: limiter_state,
+            })
+            .set(i64::from(limiter_state == current));
     }
 }
 
@@ -87,3 +412,261 @@ impl Drop for OdesliRateLimiter {
         self.jh.abort();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tokio::task::yield_now;
+    use tokio::time::sleep;
+
+    use super::*;
+
+    /// Hands out one token per second
+    const HOURLY_LIMIT: usize = 3600;
+    const REFILL: Duration = Duration::from_secs(1);
+
+    /// Queues a waiter that records its label once it acquired a token
+    async fn queue(
+        limiter: &Arc<OdesliRateLimiter>,
+        order: &Arc<Mutex<Vec<&'static str>>>,
+        label: &'static str,
+        priority: RequestPriority,
+    ) -> JoinHandle<()> {
+        let limiter = limiter.clone();
+        let order = order.clone();
+        let handle = tokio::spawn(async move {
+            limiter.acquire(priority, None).await.unwrap();
+            order.lock().push(label);
+        });
+        // Let the waiter register itself before the next one is queued
+        yield_now().await;
+        handle
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn serves_higher_priorities_first() {
+        let limiter = Arc::new(OdesliRateLimiter::new(HOURLY_LIMIT, MetricsStore::new()));
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        queue(&limiter, &order, "background", RequestPriority::Background).await;
+        queue(&limiter, &order, "batch", RequestPriority::Batch).await;
+        queue(
+            &limiter,
+            &order,
+            "interactive",
+            RequestPriority::Interactive,
+        )
+        .await;
+
+        sleep(REFILL * 3 + REFILL / 2).await;
+        assert_eq!(*order.lock(), ["interactive", "batch", "background"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn serves_the_same_priority_in_order_of_arrival() {
+        let limiter = Arc::new(OdesliRateLimiter::new(HOURLY_LIMIT, MetricsStore::new()));
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        for label in ["first", "second", "third"] {
+            queue(&limiter, &order, label, RequestPriority::Batch).await;
+        }
+
+        sleep(REFILL * 2 + REFILL / 2).await;
+        assert_eq!(*order.lock(), ["first", "second"]);
+        sleep(REFILL).await;
+        assert_eq!(*order.lock(), ["first", "second", "third"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn skips_waiters_that_gave_up() {
+        let limiter = Arc::new(OdesliRateLimiter::new(HOURLY_LIMIT, MetricsStore::new()));
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let gave_up = queue(&limiter, &order, "gave up", RequestPriority::Interactive).await;
+        gave_up.abort();
+        yield_now().await;
+
+        // The waiter that gave up is neither ahead of new ones nor handed the next token
+        let deadline = Instant::now() + REFILL;
+        assert!(limiter.reachable_by(&limiter.state.lock(), RequestPriority::Batch, deadline));
+        queue(&limiter, &order, "waiting", RequestPriority::Batch).await;
+
+        sleep(REFILL + REFILL / 2).await;
+        assert_eq!(*order.lock(), ["waiting"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rejects_unreachable_deadlines_early() {
+        let limiter = Arc::new(OdesliRateLimiter::new(HOURLY_LIMIT, MetricsStore::new()));
+        let order = Arc::new(Mutex::new(Vec::new()));
+        queue(&limiter, &order, "first", RequestPriority::Interactive).await;
+        queue(&limiter, &order, "second", RequestPriority::Interactive).await;
+
+        // Both waiters ahead take a token, so the third one arrives after three seconds
+        let start = Instant::now();
+        let result = limiter
+            .acquire(
+                RequestPriority::Interactive,
+                Some(start + REFILL * 3 - REFILL / 2),
+            )
+            .await;
+        assert_eq!(result, Err(AcquireErr::DeadlineExceeded));
+        assert_eq!(
+            Instant::now(),
+            start,
+            "The request should be rejected right away"
+        );
+
+        let result = limiter
+            .acquire(
+                RequestPriority::Interactive,
+                Some(start + REFILL * 3 + REFILL / 2),
+            )
+            .await;
+        assert_eq!(result, Ok(()));
+        assert_eq!(Instant::now(), start + REFILL * 3);
+        assert_eq!(*order.lock(), ["first", "second"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn lower_priorities_dont_delay_higher_ones() {
+        let limiter = Arc::new(OdesliRateLimiter::new(HOURLY_LIMIT, MetricsStore::new()));
+        let order = Arc::new(Mutex::new(Vec::new()));
+        queue(&limiter, &order, "first", RequestPriority::Background).await;
+        queue(&limiter, &order, "second", RequestPriority::Background).await;
+
+        let start = Instant::now();
+        let result = limiter
+            .acquire(
+                RequestPriority::Interactive,
+                Some(start + REFILL + REFILL / 2),
+            )
+            .await;
+        assert_eq!(result, Ok(()));
+        assert!(order.lock().is_empty());
+
+        // A background request queues behind them
+        let result = limiter
+            .acquire(
+                RequestPriority::Background,
+                Some(Instant::now() + REFILL * 2 + REFILL / 2),
+            )
+            .await;
+        assert_eq!(result, Err(AcquireErr::DeadlineExceeded));
+    }
+
+    fn current_state(metrics: &MetricsStore) -> RateLimitState {
+        let states = [
+            RateLimitState::Normal,
+            RateLimitState::Paused,
+            RateLimitState::Recovering,
+        ];
+        let active = states
+            .into_iter()
+            .filter(|state| {
+                metrics
+                    .odesli_rate_limit_state()
+                    .get_or_create(&RateLimitStateLabels { state: *state })
+                    .get()
+                    == 1
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(active.len(), 1, "Exactly one state should be active");
+        active[0]
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limits_drain_the_bucket() {
+        let metrics = MetricsStore::new();
+        let limiter = OdesliRateLimiter::new_with_initial_tokens(HOURLY_LIMIT, 5, metrics.clone());
+        assert!(limiter.try_acquire());
+        assert_eq!(metrics.odesli_rate_limit_tokens().get(), 4);
+
+        limiter.on_rate_limited(None);
+        assert!(!limiter.try_acquire());
+        assert_eq!(metrics.odesli_rate_limit_tokens().get(), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn pauses_for_the_retry_after_duration() {
+        let limiter = OdesliRateLimiter::new(HOURLY_LIMIT, MetricsStore::new());
+        let start = Instant::now();
+        limiter.on_rate_limited(Some(Duration::from_secs(10)));
+
+        limiter
+            .acquire(RequestPriority::Interactive, None)
+            .await
+            .unwrap();
+        assert_eq!(Instant::now(), start + Duration::from_secs(10));
+
+        // Unreasonably long pauses are capped
+        let start = Instant::now();
+        limiter.on_rate_limited(Some(Duration::from_hours(5)));
+        assert_eq!(limiter.state.lock().paused_until, Some(start + MAX_BACKOFF));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slowdown_doubles_up_to_the_maximum() {
+        let limiter = OdesliRateLimiter::new(HOURLY_LIMIT, MetricsStore::new());
+
+        for expected in [2, 4, 8, MAX_SLOWDOWN, MAX_SLOWDOWN] {
+            limiter.on_rate_limited(None);
+            assert_eq!(limiter.state.lock().slowdown, expected);
+
+            // Responses to requests sent before the pause don't slow it down further
+            limiter.on_rate_limited(None);
+            assert_eq!(limiter.state.lock().slowdown, expected);
+
+            // Without a Retry-After header, the pause lasts one refill at the reduced rate
+            sleep(REFILL * expected + REFILL / 2).await;
+            assert!(!limiter.state.lock().is_paused(Instant::now()));
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn recovers_gradually_with_handed_out_tokens() {
+        let metrics = MetricsStore::new();
+        let limiter = OdesliRateLimiter::new(HOURLY_LIMIT, metrics.clone());
+        assert_eq!(current_state(&metrics), RateLimitState::Normal);
+
+        limiter.on_rate_limited(None);
+        assert_eq!(current_state(&metrics), RateLimitState::Paused);
+        sleep(REFILL * 2 + REFILL / 2).await;
+        limiter.on_rate_limited(None);
+        assert_eq!(limiter.state.lock().slowdown, 4);
+
+        // Tokens filling up the bucket don't count towards the recovery
+        sleep(REFILL * 4 * 30).await;
+        assert_eq!(current_state(&metrics), RateLimitState::Recovering);
+        assert_eq!(limiter.state.lock().slowdown, 4);
+        assert!(limiter.state.lock().tokens >= 2 * RECOVERY_TOKENS as usize);
+
+        for _ in 0..RECOVERY_TOKENS {
+            assert!(limiter.try_acquire());
+        }
+        assert_eq!(limiter.state.lock().slowdown, 2);
+        assert_eq!(current_state(&metrics), RateLimitState::Recovering);
+
+        for _ in 0..RECOVERY_TOKENS {
+            assert!(limiter.try_acquire());
+        }
+        assert_eq!(limiter.state.lock().slowdown, 1);
+        assert_eq!(current_state(&metrics), RateLimitState::Normal);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn tokens_handed_to_waiters_count_towards_the_recovery() {
+        let limiter = Arc::new(OdesliRateLimiter::new(HOURLY_LIMIT, MetricsStore::new()));
+        let order = Arc::new(Mutex::new(Vec::new()));
+        limiter.on_rate_limited(None);
+
+        for _ in 0..RECOVERY_TOKENS {
+            queue(&limiter, &order, "waiter", RequestPriority::Batch).await;
+        }
+        // The refill is paused for one interval at half the rate, then one token every two
+        sleep(REFILL * 2 * (RECOVERY_TOKENS + 1)).await;
+
+        assert_eq!(order.lock().len(), RECOVERY_TOKENS as usize);
+        assert_eq!(limiter.state.lock().slowdown, 1);
+    }
+}
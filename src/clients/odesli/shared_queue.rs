@@ -57,7 +57,7 @@ where
     ) -> Result<V, Err>
     where
         F: FnOnce() -> Fut,
-        Fut: Future<Output = Result<V, Err>> + Send + Sync,
+        Fut: Future<Output = Result<V, Err>> + Send,
         FnDup: FnMut(&V) -> V,
     {
         let mut reciever = loop {
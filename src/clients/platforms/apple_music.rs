@@ -0,0 +1,244 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use crate::clients::odesli::Platform;
+use crate::clients::platforms::metadata::{EntityKind, PlatformEntity};
+use crate::clients::platforms::{PlatformApiErr, PlatformsClient, PlaylistPage, PlaylistTrack};
+
+const API_BASE: &str = "https://api.music.apple.com/v1";
+/// The storefront used for lookups without one, catalogue IDs are the same in every storefront
+const DEFAULT_STOREFRONT: &str = "us";
+
+#[derive(Deserialize)]
+pub(super) struct AppleMusicList<T> {
+    pub data: Vec<AppleMusicResource<T>>,
+    pub next: Option<String>,
+    pub meta: Option<AppleMusicMeta>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct AppleMusicMeta {
+    pub total: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct AppleMusicResource<T> {
+    pub id: String,
+    pub attributes: T,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct SongAttributes {
+    pub name: String,
+    pub artist_name: Option<String>,
+    pub url: Option<String>,
+    pub isrc: Option<String>,
+    pub artwork: Option<Artwork>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct AlbumAttributes {
+    pub name: String,
+    pub artist_name: Option<String>,
+    pub url: Option<String>,
+    pub upc: Option<String>,
+    pub artwork: Option<Artwork>,
+}
+
+/// The artwork url is a template with `{w}` and `{h}` placeholders for the size
+#[derive(Deserialize)]
+pub(super) struct Artwork {
+    pub url: String,
+}
+
+impl Artwork {
+    fn thumbnail_url(&self) -> String {
+        self.url.replace("{w}", "640").replace("{h}", "640")
+    }
+}
+
+#[derive(Deserialize)]
+struct PlaylistAttributes {
+    name: String,
+}
+
+impl From<SongAttributes> for PlaylistTrack {
+    fn from(song: SongAttributes) -> Self {
+        Self {
+            title: song.name,
+            artist: song.artist_name,
+            url: song.url,
+        }
+    }
+}
+
+impl AppleMusicResource<SongAttributes> {
+    pub(super) fn into_entity(self) -> Option<PlatformEntity> {
+        let attributes = self.attributes;
+        Some(PlatformEntity {
+            platform: Platform::AppleMusic,
+            id: self.id,
+            kind: EntityKind::Song,
+            title: attributes.name,
+            artist_name: attributes.artist_name,
+            thumbnail_url: attributes.artwork.map(|artwork| artwork.thumbnail_url()),
+            url: attributes.url?,
+            code: attributes.isrc,
+        })
+    }
+}
+
+impl AppleMusicResource<AlbumAttributes> {
+    pub(super) fn into_entity(self) -> Option<PlatformEntity> {
+        let attributes = self.attributes;
+        Some(PlatformEntity {
+            platform: Platform::AppleMusic,
+            id: self.id,
+            kind: EntityKind::Album,
+            title: attributes.name,
+            artist_name: attributes.artist_name,
+            thumbnail_url: attributes.artwork.map(|artwork| artwork.thumbnail_url()),
+            url: attributes.url?,
+            code: attributes.upc,
+        })
+    }
+}
+
+impl PlatformsClient {
+    /// Sends an authenticated GET request to the given Apple Music API path
+    pub(super) async fn apple_music_get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, PlatformApiErr> {
+        let token = self
+            .inner
+            .apple_music_token
+            .as_deref()
+            .ok_or(PlatformApiErr::NotConfigured("Apple Music"))?;
+
+        let req = self
+            .inner
+            .client
+            .get(format!("{}{}", API_BASE, path))
+            .query(query)
+            .bearer_auth(token)
+            .build()?;
+
+        self.execute_json(req).await
+    }
+
+    pub(super) async fn apple_music_playlist_page(
+        &self,
+        storefront: &str,
+        id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<PlaylistPage, PlatformApiErr> {
+        let playlist: AppleMusicList<PlaylistAttributes> = self
+            .apple_music_get(&format!("/catalog/{}/playlists/{}", storefront, id), &[])
+            .await?;
+        let name = playlist
+            .data
+            .into_iter()
+            .next()
+            .ok_or(PlatformApiErr::NotFound)?
+            .attributes
+            .name;
+
+        let offset_str = offset.to_string();
+        // The Apple Music API returns at most 100 tracks per request
+        let limit_str = limit.min(100).to_string();
+        let tracks: AppleMusicList<SongAttributes> = self
+            .apple_music_get(
+                &format!("/catalog/{}/playlists/{}/tracks", storefront, id),
+                &[
+                    ("offset", offset_str.as_str()),
+                    ("limit", limit_str.as_str()),
+                ],
+            )
+            .await?;
+
+        let tracks_len = tracks.data.len();
+        // The total is not always included, so fall back to what we know from the pagination
+        let total = tracks
+            .meta
+            .and_then(|meta| meta.total)
+            .unwrap_or(offset + tracks_len + usize::from(tracks.next.is_some()));
+
+        Ok(PlaylistPage {
+            name,
+            total,
+            tracks: tracks
+                .data
+                .into_iter()
+                .map(|resource| PlaylistTrack::from(resource.attributes))
+                .collect(),
+        })
+    }
+
+    /// Looks up the catalogue resources of the kind, either by their IDs or a filter
+    async fn apple_music_entities(
+        &self,
+        kind: EntityKind,
+        query: &[(&str, &str)],
+    ) -> Result<PlatformEntity, PlatformApiErr> {
+        let entity = match kind {
+            EntityKind::Song => self
+                .apple_music_get::<AppleMusicList<SongAttributes>>(
+                    &format!("/catalog/{}/songs", DEFAULT_STOREFRONT),
+                    query,
+                )
+                .await?
+                .data
+                .into_iter()
+                .find_map(AppleMusicResource::<SongAttributes>::into_entity),
+            EntityKind::Album => self
+                .apple_music_get::<AppleMusicList<AlbumAttributes>>(
+                    &format!("/catalog/{}/albums", DEFAULT_STOREFRONT),
+                    query,
+                )
+                .await?
+                .data
+                .into_iter()
+                .find_map(AppleMusicResource::<AlbumAttributes>::into_entity),
+        };
+
+        entity.ok_or(PlatformApiErr::NotFound)
+    }
+
+    pub(super) async fn apple_music_entity(
+        &self,
+        kind: EntityKind,
+        id: u64,
+    ) -> Result<PlatformEntity, PlatformApiErr> {
+        let id = id.to_string();
+        self.apple_music_entities(kind, &[("ids", id.as_str())])
+            .await
+    }
+
+    /// Finds the song with the ISRC or the album with the UPC
+    pub(super) async fn apple_music_find_by_code(
+        &self,
+        kind: EntityKind,
+        code: &str,
+    ) -> Result<PlatformEntity, PlatformApiErr> {
+        let filter = match kind {
+            EntityKind::Song => "filter[isrc]",
+            EntityKind::Album => "filter[upc]",
+        };
+
+        let entity = self.apple_music_entities(kind, &[(filter, code)]).await?;
+        Ok(PlatformEntity {
+            code: Some(code.to_string()),
+            ..entity
+        })
+    }
+}
@@ -0,0 +1,135 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use serde::Deserialize;
+use tracing::{debug, instrument};
+use url::Url;
+
+use crate::clients::odesli::Platform;
+use crate::clients::odesli::provider_id::{
+    AmazonMusicId, AppleMusicId, DeezerId, ProviderId, SpotifyId, TidalId, YouTubeId,
+};
+use crate::clients::platforms::deezer::{DeezerList, DeezerTrack};
+use crate::clients::platforms::spotify::SpotifyTrack;
+use crate::clients::platforms::{PlatformApiErr, PlatformsClient};
+
+#[derive(Deserialize)]
+struct TopTracksResponse {
+    tracks: Vec<SpotifyTrack>,
+}
+
+impl PlatformsClient {
+    /// Whether we are able to look up the top track of the artist with the configured credentials
+    pub fn supports_artist(&self, artist: &ProviderId) -> bool {
+        match artist {
+            ProviderId::AppleMusic(AppleMusicId::Artist(_))
+            | ProviderId::Deezer(DeezerId::Artist(_)) => true,
+            ProviderId::Spotify(SpotifyId::Artist(_)) => self.inner.spotify.is_some(),
+            ProviderId::Tidal(TidalId::Artist(_)) => self.inner.tidal.is_some(),
+            // Neither platform has a public API to look up the top tracks of an artist
+            ProviderId::AmazonMusic(AmazonMusicId::Artist(_))
+            | ProviderId::YouTube(YouTubeId::Artist(_)) => false,
+            _ => false,
+        }
+    }
+
+    /// Finds the most popular track of the artist and returns the link to it
+    #[instrument(level = "debug", skip(self))]
+    pub async fn artist_top_track(&self, artist: &ProviderId) -> Result<String, PlatformApiErr> {
+        debug!("Fetching top track of artist");
+
+        match artist {
+            ProviderId::AppleMusic(AppleMusicId::Artist(id)) => {
+                let id = id.to_string();
+                let results = self
+                    .itunes_lookup(&[("id", id.as_str()), ("entity", "song"), ("limit", "1")])
+                    .await?;
+
+                // The first result is the artist itself
+                results
+                    .into_iter()
+                    .find(|item| item.wrapper_type == "track")
+                    .and_then(|item| item.track_view_url)
+                    .ok_or(PlatformApiErr::NotFound)
+            }
+            ProviderId::Deezer(DeezerId::Artist(id)) => {
+                let top: DeezerList<DeezerTrack> = self
+                    .deezer_get(&format!("/artist/{}/top", id), &[("limit", "1")])
+                    .await?;
+
+                top.data
+                    .into_iter()
+                    .next()
+                    .map(|track| track.link)
+                    .ok_or(PlatformApiErr::NotFound)
+            }
+            ProviderId::Spotify(SpotifyId::Artist(id)) => {
+                let top: TopTracksResponse = self
+                    .spotify_get(&format!("/artists/{}/top-tracks", id), &[("market", "US")])
+                    .await?;
+
+                top.tracks
+                    .into_iter()
+                    .next()
+                    .and_then(|track| track.external_urls.spotify)
+                    .ok_or(PlatformApiErr::NotFound)
+            }
+            ProviderId::Tidal(TidalId::Artist(id)) => self.tidal_artist_top_track(*id).await,
+            _ => Err(PlatformApiErr::Unsupported),
+        }
+    }
+
+    /// Looks up the profile of the (main) artist of the track
+    #[instrument(level = "debug", skip(self))]
+    pub async fn track_artist_link(&self, track: &ProviderId) -> Result<String, PlatformApiErr> {
+        match track {
+            ProviderId::AppleMusic(AppleMusicId::Track(id)) => {
+                let id = id.to_string();
+                let results = self.itunes_lookup(&[("id", id.as_str())]).await?;
+
+                results
+                    .into_iter()
+                    .find_map(|item| item.artist_view_url)
+                    .ok_or(PlatformApiErr::NotFound)
+            }
+            ProviderId::Deezer(DeezerId::Track(id)) => {
+                let track: DeezerTrack = self.deezer_get(&format!("/track/{}", id), &[]).await?;
+
+                track.artist.link.ok_or(PlatformApiErr::NotFound)
+            }
+            ProviderId::Spotify(SpotifyId::Track(id)) => {
+                let track: SpotifyTrack = self.spotify_get(&format!("/tracks/{}", id), &[]).await?;
+
+                track
+                    .artists
+                    .into_iter()
+                    .next()
+                    .and_then(|artist| artist.external_urls)
+                    .and_then(|urls| urls.spotify)
+                    .ok_or(PlatformApiErr::NotFound)
+            }
+            ProviderId::Tidal(TidalId::Track(id)) => self.tidal_track_artist(*id).await,
+            _ => Err(PlatformApiErr::Unsupported),
+        }
+    }
+}
+
+/// Builds a link searching for the artist on platforms we can't look up artist profiles for
+pub fn artist_search_url(platform: &Platform, artist_name: &str) -> Option<Url> {
+    let (base, query_key) = match platform {
+        Platform::AmazonMusic => {
+            let mut url = Url::parse("https://music.amazon.com/search").ok()?;
+            url.path_segments_mut().ok()?.push(artist_name);
+            return Some(url);
+        }
+        Platform::Tidal => ("https://listen.tidal.com/search/artists", "q"),
+        Platform::YouTube => ("https://www.youtube.com/results", "search_query"),
+        Platform::YouTubeMusic => ("https://music.youtube.com/search", "q"),
+        Platform::Soundcloud => ("https://soundcloud.com/search/people", "q"),
+        _ => return None,
+    };
+
+    Url::parse_with_params(base, [(query_key, artist_name)]).ok()
+}
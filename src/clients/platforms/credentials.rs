@@ -0,0 +1,84 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use tokio::sync::Mutex;
+use tracing::debug;
+
+use crate::clients::platforms::{PlatformApiErr, PlatformsClient};
+
+/// Credentials for an API authenticated with the OAuth client credentials flow, the access token
+/// is cached until it expires.
+pub(super) struct ClientCredentials {
+    token_url: &'static str,
+    client_id: Box<str>,
+    client_secret: Box<str>,
+    token: Mutex<Option<AccessToken>>,
+}
+
+struct AccessToken {
+    token: String,
+    expires_at: Instant,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+impl ClientCredentials {
+    pub fn new(token_url: &'static str, client_id: &str, client_secret: &str) -> Self {
+        Self {
+            token_url,
+            client_id: Box::from(client_id),
+            client_secret: Box::from(client_secret),
+            token: Mutex::new(None),
+        }
+    }
+}
+
+impl PlatformsClient {
+    /// Returns a valid access token, requesting a new one if the cached token expired
+    pub(super) async fn access_token(
+        &self,
+        credentials: &ClientCredentials,
+    ) -> Result<String, PlatformApiErr> {
+        let mut token = credentials.token.lock().await;
+        if let Some(token) = token.as_ref()
+            && token.expires_at > Instant::now()
+        {
+            return Ok(token.token.clone());
+        }
+
+        debug!(
+            token_url = credentials.token_url,
+            "Requesting new access token"
+        );
+        let req = self
+            .inner
+            .client
+            .post(credentials.token_url)
+            .basic_auth(
+                credentials.client_id.as_ref(),
+                Some(credentials.client_secret.as_ref()),
+            )
+            .form(&[("grant_type", "client_credentials")])
+            .build()?;
+
+        let response: TokenResponse = self.execute_json(req).await?;
+        let new_token = response.access_token.clone();
+        *token = Some(AccessToken {
+            token: response.access_token,
+            // Refresh the token a minute early, so it doesn't expire mid request
+            expires_at: Instant::now()
+                + Duration::from_secs(response.expires_in).saturating_sub(Duration::from_mins(1)),
+        });
+
+        Ok(new_token)
+    }
+}
@@ -0,0 +1,220 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use crate::clients::odesli::Platform;
+use crate::clients::platforms::metadata::{EntityKind, PlatformEntity};
+use crate::clients::platforms::{PlatformApiErr, PlatformsClient, PlaylistPage, PlaylistTrack};
+
+const API_BASE: &str = "https://api.deezer.com";
+
+/// Deezer reports errors with a 200 status code, so the body has to be inspected
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DeezerResponse<T> {
+    Error { error: DeezerError },
+    Ok(T),
+}
+
+#[derive(Deserialize)]
+struct DeezerError {
+    #[serde(rename = "type")]
+    kind: String,
+    message: String,
+    code: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct DeezerArtist {
+    pub name: String,
+    pub link: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct DeezerTrack {
+    pub id: u64,
+    pub title: String,
+    pub link: String,
+    pub artist: DeezerArtist,
+    /// Only included when the track is requested directly
+    pub isrc: Option<String>,
+    pub album: Option<DeezerTrackAlbum>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct DeezerTrackAlbum {
+    pub cover_xl: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct DeezerAlbum {
+    pub id: u64,
+    pub title: String,
+    pub link: String,
+    pub artist: DeezerArtist,
+    pub cover_xl: Option<String>,
+    pub upc: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct DeezerList<T> {
+    pub data: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct DeezerPlaylist {
+    title: String,
+    nb_tracks: usize,
+}
+
+impl From<DeezerTrack> for PlaylistTrack {
+    fn from(track: DeezerTrack) -> Self {
+        Self {
+            title: track.title,
+            artist: Some(track.artist.name),
+            url: Some(track.link),
+        }
+    }
+}
+
+impl From<DeezerTrack> for PlatformEntity {
+    fn from(track: DeezerTrack) -> Self {
+        Self {
+            platform: Platform::Deezer,
+            id: track.id.to_string(),
+            kind: EntityKind::Song,
+            title: track.title,
+            artist_name: Some(track.artist.name),
+            thumbnail_url: track.album.and_then(|album| album.cover_xl),
+            url: track.link,
+            code: track.isrc,
+        }
+    }
+}
+
+impl From<DeezerAlbum> for PlatformEntity {
+    fn from(album: DeezerAlbum) -> Self {
+        Self {
+            platform: Platform::Deezer,
+            id: album.id.to_string(),
+            kind: EntityKind::Album,
+            title: album.title,
+            artist_name: Some(album.artist.name),
+            thumbnail_url: album.cover_xl,
+            url: album.link,
+            code: album.upc,
+        }
+    }
+}
+
+impl PlatformsClient {
+    /// Sends a GET request to the given Deezer API path
+    pub(super) async fn deezer_get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, PlatformApiErr> {
+        let req = self
+            .inner
+            .client
+            .get(format!("{}{}", API_BASE, path))
+            .query(query)
+            .build()?;
+
+        match self.execute_json(req).await? {
+            DeezerResponse::Ok(data) => Ok(data),
+            // 800 is the code for "no data", which deezer uses for unknown ids
+            DeezerResponse::Error { error } if error.code == Some(800) => {
+                Err(PlatformApiErr::NotFound)
+            }
+            DeezerResponse::Error { error } => Err(PlatformApiErr::UnexpectedResponseStatus {
+                status_code: reqwest::StatusCode::OK,
+                text: format!("{}: {}", error.kind, error.message),
+            }),
+        }
+    }
+
+    pub(super) async fn deezer_playlist_page(
+        &self,
+        id: u64,
+        offset: usize,
+        limit: usize,
+    ) -> Result<PlaylistPage, PlatformApiErr> {
+        let playlist: DeezerPlaylist = self.deezer_get(&format!("/playlist/{}", id), &[]).await?;
+
+        let index = offset.to_string();
+        let limit = limit.to_string();
+        let tracks: DeezerList<DeezerTrack> = self
+            .deezer_get(
+                &format!("/playlist/{}/tracks", id),
+                &[("index", index.as_str()), ("limit", limit.as_str())],
+            )
+            .await?;
+
+        Ok(PlaylistPage {
+            name: playlist.title,
+            total: playlist.nb_tracks,
+            tracks: tracks.data.into_iter().map(PlaylistTrack::from).collect(),
+        })
+    }
+
+    /// Looks up the entity by its Deezer ID, or its ISRC/UPC with an `isrc:`/`upc:` prefix
+    pub(super) async fn deezer_entity(
+        &self,
+        kind: EntityKind,
+        id: &str,
+    ) -> Result<PlatformEntity, PlatformApiErr> {
+        match kind {
+            EntityKind::Song => Ok(self
+                .deezer_get::<DeezerTrack>(&format!("/track/{}", id), &[])
+                .await?
+                .into()),
+            EntityKind::Album => Ok(self
+                .deezer_get::<DeezerAlbum>(&format!("/album/{}", id), &[])
+                .await?
+                .into()),
+        }
+    }
+
+    /// Finds the song with the ISRC or the album with the UPC
+    pub(super) async fn deezer_find_by_code(
+        &self,
+        kind: EntityKind,
+        code: &str,
+    ) -> Result<PlatformEntity, PlatformApiErr> {
+        let id = match kind {
+            EntityKind::Song => format!("isrc:{}", code),
+            EntityKind::Album => format!("upc:{}", code),
+        };
+
+        self.deezer_entity(kind, &id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_are_read_from_successful_responses() {
+        let body = include_str!("../fixtures/platforms/deezer_error.json");
+        let response: DeezerResponse<DeezerTrack> =
+            serde_json::from_str(body).expect("The fixture should deserialize");
+        assert!(matches!(
+            response,
+            DeezerResponse::Error { error } if error.code == Some(800)
+        ));
+
+        let body = include_str!("../fixtures/platforms/deezer_track.json");
+        let response: DeezerResponse<DeezerTrack> =
+            serde_json::from_str(body).expect("The fixture should deserialize");
+        assert!(matches!(
+            response,
+            DeezerResponse::Ok(track) if track.isrc.as_deref() == Some("GBARL9300135")
+        ));
+    }
+}
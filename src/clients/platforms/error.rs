@@ -0,0 +1,51 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::error::Error;
+use std::fmt;
+
+use reqwest::StatusCode;
+
+#[derive(Debug)]
+pub enum PlatformApiErr {
+    Reqwest(reqwest::Error),
+    /// The platform API requires credentials that were not configured
+    NotConfigured(&'static str),
+    NotFound,
+    /// The platform doesn't offer an API for the requested resource
+    Unsupported,
+    UnexpectedResponseStatus {
+        status_code: StatusCode,
+        text: String,
+    },
+}
+
+impl From<reqwest::Error> for PlatformApiErr {
+    fn from(err: reqwest::Error) -> Self {
+        PlatformApiErr::Reqwest(err)
+    }
+}
+
+impl fmt::Display for PlatformApiErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlatformApiErr::Reqwest(err) => write!(f, "Reqwest error: {}", err),
+            PlatformApiErr::NotConfigured(platform) => {
+                write!(f, "No credentials configured for the {} API", platform)
+            }
+            PlatformApiErr::NotFound => write!(f, "The requested resource could not be found"),
+            PlatformApiErr::Unsupported => {
+                write!(f, "The platform does not support the requested resource")
+            }
+            PlatformApiErr::UnexpectedResponseStatus { status_code, text } => write!(
+                f,
+                "Unexpected response status {}, with body: \"{}\"",
+                status_code, text
+            ),
+        }
+    }
+}
+
+impl Error for PlatformApiErr {}
@@ -0,0 +1,82 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use serde::Deserialize;
+
+use crate::clients::platforms::{PlatformApiErr, PlatformsClient};
+use crate::clients::search::SearchResult;
+
+const LOOKUP_URL: &str = "https://itunes.apple.com/lookup";
+const SEARCH_URL: &str = "https://itunes.apple.com/search";
+
+#[derive(Deserialize)]
+pub(super) struct ITunesResults<T> {
+    pub results: Vec<T>,
+}
+
+/// A single result of the iTunes Search API, all fields are optional since the API returns
+/// artists, collections and tracks in the same list
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ITunesItem {
+    pub wrapper_type: String,
+    pub artist_view_url: Option<String>,
+    pub track_view_url: Option<String>,
+    pub track_name: Option<String>,
+    pub artist_name: Option<String>,
+    pub collection_name: Option<String>,
+}
+
+impl ITunesItem {
+    fn into_search_result(self) -> Option<SearchResult> {
+        Some(SearchResult {
+            title: self.track_name?,
+            artist: self.artist_name?,
+            album: self.collection_name,
+            url: self.track_view_url?,
+        })
+    }
+}
+
+impl PlatformsClient {
+    /// Sends a request to the (public) iTunes lookup API
+    pub(super) async fn itunes_lookup(
+        &self,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<ITunesItem>, PlatformApiErr> {
+        let req = self.inner.client.get(LOOKUP_URL).query(query).build()?;
+        let response: ITunesResults<ITunesItem> = self.execute_json(req).await?;
+
+        Ok(response.results)
+    }
+
+    /// Searches the (public) iTunes catalogue for songs matching the term
+    pub async fn itunes_search(
+        &self,
+        term: String,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>, PlatformApiErr> {
+        let limit = limit.to_string();
+        let req = self
+            .inner
+            .client
+            .get(SEARCH_URL)
+            .query(&[
+                ("term", term.as_str()),
+                ("media", "music"),
+                ("entity", "song"),
+                ("limit", limit.as_str()),
+            ])
+            .build()?;
+        let response: ITunesResults<ITunesItem> = self.execute_json(req).await?;
+
+        Ok(response
+            .results
+            .into_iter()
+            .filter(|item| item.wrapper_type == "track")
+            .filter_map(ITunesItem::into_search_result)
+            .collect())
+    }
+}
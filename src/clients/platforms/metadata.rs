@@ -0,0 +1,141 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::fmt;
+
+use tracing::{debug, instrument};
+
+use crate::clients::odesli::Platform;
+use crate::clients::odesli::provider_id::{AppleMusicId, DeezerId, ProviderId, SpotifyId};
+use crate::clients::platforms::{PlatformApiErr, PlatformsClient};
+
+/// The platforms songs and albums can be looked up on by their ISRC/UPC
+pub const MATCHABLE_PLATFORMS: &[Platform] = &[
+    Platform::Spotify,
+    Platform::Deezer,
+    Platform::AppleMusic,
+    Platform::Tidal,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityKind {
+    Song,
+    Album,
+}
+
+impl EntityKind {
+    /// Parses the entity type as named by Odesli
+    pub fn from_odesli(kind: &str) -> Option<Self> {
+        match kind {
+            "song" => Some(EntityKind::Song),
+            "album" => Some(EntityKind::Album),
+            _ => None,
+        }
+    }
+
+    /// The entity type as named by Odesli
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntityKind::Song => "song",
+            EntityKind::Album => "album",
+        }
+    }
+}
+
+impl fmt::Display for EntityKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A song or album as returned by the metadata API of a platform
+#[derive(Debug, Clone)]
+pub struct PlatformEntity {
+    pub platform: Platform,
+    /// The ID of the entity on the platform
+    pub id: String,
+    pub kind: EntityKind,
+    pub title: String,
+    pub artist_name: Option<String>,
+    pub thumbnail_url: Option<String>,
+    pub url: String,
+    /// The ISRC of a song or the UPC of an album, which identify it across platforms
+    pub code: Option<String>,
+}
+
+impl PlatformsClient {
+    /// Whether the metadata of the entity can be looked up with the configured credentials
+    pub fn supports_entity(&self, id: &ProviderId) -> bool {
+        match id {
+            ProviderId::Deezer(DeezerId::Track(_) | DeezerId::Album(_)) => true,
+            ProviderId::Spotify(SpotifyId::Track(_) | SpotifyId::Album(_)) => {
+                self.inner.spotify.is_some()
+            }
+            ProviderId::AppleMusic(AppleMusicId::Track(_) | AppleMusicId::Album(_)) => {
+                self.inner.apple_music_token.is_some()
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether entities can be looked up by their ISRC/UPC on the platform with the configured
+    /// credentials
+    pub fn supports_code_lookup(&self, platform: &Platform) -> bool {
+        match platform {
+            Platform::AppleMusic => self.inner.apple_music_token.is_some(),
+            Platform::Deezer => true,
+            Platform::Spotify => self.inner.spotify.is_some(),
+            Platform::Tidal => self.inner.tidal.is_some(),
+            _ => false,
+        }
+    }
+
+    /// Looks up the metadata of the song or album, including its ISRC/UPC
+    #[instrument(level = "debug", skip(self))]
+    pub async fn fetch_entity(&self, id: &ProviderId) -> Result<PlatformEntity, PlatformApiErr> {
+        debug!("Fetching entity metadata");
+
+        match id {
+            ProviderId::AppleMusic(AppleMusicId::Track(id)) => {
+                self.apple_music_entity(EntityKind::Song, *id).await
+            }
+            ProviderId::AppleMusic(AppleMusicId::Album(id)) => {
+                self.apple_music_entity(EntityKind::Album, *id).await
+            }
+            ProviderId::Deezer(DeezerId::Track(id)) => {
+                self.deezer_entity(EntityKind::Song, &id.to_string()).await
+            }
+            ProviderId::Deezer(DeezerId::Album(id)) => {
+                self.deezer_entity(EntityKind::Album, &id.to_string()).await
+            }
+            ProviderId::Spotify(SpotifyId::Track(id)) => {
+                self.spotify_entity(EntityKind::Song, id).await
+            }
+            ProviderId::Spotify(SpotifyId::Album(id)) => {
+                self.spotify_entity(EntityKind::Album, id).await
+            }
+            _ => Err(PlatformApiErr::Unsupported),
+        }
+    }
+
+    /// Finds the song with the ISRC or the album with the UPC on the platform
+    #[instrument(level = "debug", skip(self))]
+    pub async fn find_by_code(
+        &self,
+        platform: &Platform,
+        kind: EntityKind,
+        code: &str,
+    ) -> Result<PlatformEntity, PlatformApiErr> {
+        debug!("Looking up entity by its code");
+
+        match platform {
+            Platform::AppleMusic => self.apple_music_find_by_code(kind, code).await,
+            Platform::Deezer => self.deezer_find_by_code(kind, code).await,
+            Platform::Spotify => self.spotify_find_by_code(kind, code).await,
+            Platform::Tidal => self.tidal_find_by_code(kind, code).await,
+            _ => Err(PlatformApiErr::Unsupported),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use metronomos_pulse::value::{ArcValue, PulseValue};
+use reqwest::{Request, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use tracing::{Instrument, debug_span};
+use url::Host;
+
+use crate::args::Args;
+use crate::metrics::MetricsStore;
+use crate::metrics::labels::ThirdPartyLabels;
+use crate::util::metric_utils::{HasHistogramFamilyExt, TimeFutureExt, UnpackErr};
+
+mod apple_music;
+mod artist;
+mod credentials;
+mod deezer;
+mod error;
+mod itunes;
+mod metadata;
+mod playlist;
+#[cfg(test)]
+mod recorded;
+mod spotify;
+mod tidal;
+
+pub use artist::artist_search_url;
+use credentials::ClientCredentials;
+pub use error::PlatformApiErr;
+pub use metadata::{EntityKind, MATCHABLE_PLATFORMS, PlatformEntity};
+pub use playlist::{PlaylistId, PlaylistPage, PlaylistTrack};
+#[cfg(test)]
+pub use recorded::recorded_entities;
+
+/// Client for the public metadata APIs of the individual streaming platforms.
+///
+/// Platforms requiring credentials are only available if these were configured, requests to
+/// unconfigured platforms fail with [`PlatformApiErr::NotConfigured`].
+#[derive(Clone, PulseValue)]
+pub struct PlatformsClient {
+    inner: Arc<PlatformsClientInner>,
+}
+
+struct PlatformsClientInner {
+    client: reqwest::Client,
+    spotify: Option<ClientCredentials>,
+    tidal: Option<ClientCredentials>,
+    apple_music_token: Option<Box<str>>,
+    metrics: MetricsStore,
+}
+
+impl fmt::Debug for PlatformsClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PlatformsClient")
+            .field("client", &self.inner.client)
+            .field("spotify", &self.inner.spotify.as_ref().map(|_| "****"))
+            .field("tidal", &self.inner.tidal.as_ref().map(|_| "****"))
+            .field(
+                "apple_music_token",
+                &self.inner.apple_music_token.as_ref().map(|_| "****"),
+            )
+            .finish()
+    }
+}
+
+impl PlatformsClient {
+    pub fn init(client: reqwest::Client, args: ArcValue<Args>, metrics: MetricsStore) -> Self {
+        let spotify = match (&args.spotify_client_id, &args.spotify_client_secret) {
+            (Some(id), Some(secret)) => {
+                Some(ClientCredentials::new(spotify::TOKEN_URL, id, secret))
+            }
+            _ => None,
+        };
+        let tidal = match (&args.tidal_client_id, &args.tidal_client_secret) {
+            (Some(id), Some(secret)) => Some(ClientCredentials::new(tidal::TOKEN_URL, id, secret)),
+            _ => None,
+        };
+
+        let inner = PlatformsClientInner {
+            client,
+            spotify,
+            tidal,
+            apple_music_token: args.apple_music_token.as_deref().map(Box::from),
+            metrics,
+        };
+
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Executes the request, recording its duration in the third party api metrics
+    async fn execute(&self, req: Request) -> Result<Response, PlatformApiErr> {
+        let metrics_url = format!(
+            "{}://{}",
+            req.url().scheme(),
+            req.url().host().unwrap_or(Host::Domain("unknown.host"))
+        );
+        let method = req.method().clone();
+
+        let (resp, diff) = self
+            .inner
+            .client
+            .execute(req)
+            .instrument(debug_span!("http_request"))
+            .time()
+            .await
+            .unpack_err()?;
+
+        self.inner.metrics.observe_duration(
+            ThirdPartyLabels {
+                method: method.into(),
+                url: Cow::from(metrics_url),
+                status: resp.status().into(),
+            },
+            diff,
+        );
+
+        Ok(resp)
+    }
+
+    /// Executes the request and deserializes the JSON body of a successful response
+    async fn execute_json<T: DeserializeOwned>(&self, req: Request) -> Result<T, PlatformApiErr> {
+        let resp = self.execute(req).await?;
+
+        match resp.status() {
+            StatusCode::OK => Ok(resp.json().await?),
+            StatusCode::NOT_FOUND => Err(PlatformApiErr::NotFound),
+            status => {
+                let text = resp.text().await?;
+                Err(PlatformApiErr::UnexpectedResponseStatus {
+                    status_code: status,
+                    text,
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::fmt;
+use std::str::FromStr;
+
+use tracing::{debug, instrument};
+use url::Url;
+
+use crate::clients::platforms::{PlatformApiErr, PlatformsClient};
+
+/// Identifies a playlist on one of the platforms we can expand playlists for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PlaylistId {
+    AppleMusic { storefront: String, id: String },
+    Deezer(u64),
+    Spotify(String),
+}
+
+#[derive(Debug)]
+pub struct PlaylistPage {
+    pub name: String,
+    /// The total number of tracks in the playlist
+    pub total: usize,
+    pub tracks: Vec<PlaylistTrack>,
+}
+
+#[derive(Debug)]
+pub struct PlaylistTrack {
+    pub title: String,
+    pub artist: Option<String>,
+    /// The link to the track on the platform the playlist is from, unavailable for local files
+    pub url: Option<String>,
+}
+
+impl PlaylistId {
+    pub fn parse_url(url: &Url) -> Option<Self> {
+        let mut path_segments = url.path_segments()?.filter(|s| !s.is_empty());
+
+        match url.domain()? {
+            "open.spotify.com" => {
+                if path_segments.next()? != "playlist" {
+                    return None;
+                }
+                let id = path_segments.next()?;
+                Some(Self::Spotify(id.to_string()))
+            }
+            "music.apple.com" | "geo.music.apple.com" => {
+                let storefront = path_segments.next()?;
+                if path_segments.next()? != "playlist" {
+                    return None;
+                }
+                // The name segment is optional, the id is always the last segment
+                let id = path_segments.next_back()?;
+                if !id.starts_with("pl.") {
+                    return None;
+                }
+                Some(Self::AppleMusic {
+                    storefront: storefront.to_string(),
+                    id: id.to_string(),
+                })
+            }
+            "www.deezer.com" | "deezer.com" => {
+                let mut content_type = path_segments.next()?;
+                // Deezer links may be prefixed with a language code
+                if content_type != "playlist" {
+                    content_type = path_segments.next()?;
+                }
+                if content_type != "playlist" {
+                    return None;
+                }
+                let id = path_segments.next()?.parse().ok()?;
+                Some(Self::Deezer(id))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Formats the playlist ID as a compact key, used to keep the playlist in component custom ids.
+impl fmt::Display for PlaylistId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AppleMusic { storefront, id } => write!(f, "am:{}:{}", storefront, id),
+            Self::Deezer(id) => write!(f, "dz:{}", id),
+            Self::Spotify(id) => write!(f, "sp:{}", id),
+        }
+    }
+}
+
+impl FromStr for PlaylistId {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let res = match parts.next().ok_or(())? {
+            "am" => Self::AppleMusic {
+                storefront: parts.next().ok_or(())?.to_string(),
+                id: parts.next().ok_or(())?.to_string(),
+            },
+            "dz" => Self::Deezer(parts.next().ok_or(())?.parse().map_err(|_| ())?),
+            "sp" => Self::Spotify(parts.next().ok_or(())?.to_string()),
+            _ => return Err(()),
+        };
+
+        if parts.next().is_some() {
+            return Err(());
+        }
+        Ok(res)
+    }
+}
+
+impl PlatformsClient {
+    /// Whether playlists from the given platform can be expanded with the configured credentials
+    pub fn supports_playlist(&self, id: &PlaylistId) -> bool {
+        match id {
+            PlaylistId::AppleMusic { .. } => self.inner.apple_music_token.is_some(),
+            PlaylistId::Deezer(_) => true,
+            PlaylistId::Spotify(_) => self.inner.spotify.is_some(),
+        }
+    }
+
+    /// Fetches the playlist name and `limit` tracks starting at `offset`
+    #[instrument(level = "debug", skip(self))]
+    pub async fn fetch_playlist_page(
+        &self,
+        id: &PlaylistId,
+        offset: usize,
+        limit: usize,
+    ) -> Result<PlaylistPage, PlatformApiErr> {
+        debug!("Fetching playlist page");
+
+        match id {
+            PlaylistId::AppleMusic { storefront, id } => {
+                self.apple_music_playlist_page(storefront, id, offset, limit)
+                    .await
+            }
+            PlaylistId::Deezer(id) => self.deezer_playlist_page(*id, offset, limit).await,
+            PlaylistId::Spotify(id) => self.spotify_playlist_page(id, offset, limit).await,
+        }
+    }
+}
@@ -0,0 +1,201 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+//! Responses recorded from the metadata APIs of the platforms, converted the same way as live
+//! responses
+
+use crate::clients::platforms::PlatformEntity;
+use crate::clients::platforms::apple_music::{AlbumAttributes, AppleMusicList, SongAttributes};
+use crate::clients::platforms::deezer::{DeezerAlbum, DeezerTrack};
+use crate::clients::platforms::spotify::{SearchResponse, SpotifyAlbum, SpotifyTrack};
+use crate::clients::platforms::tidal::{self, TidalList};
+
+const SPOTIFY_TRACK: &str = include_str!("../fixtures/platforms/spotify_track.json");
+const SPOTIFY_ALBUM: &str = include_str!("../fixtures/platforms/spotify_album.json");
+const SPOTIFY_SEARCH_ALBUM: &str = include_str!("../fixtures/platforms/spotify_search_album.json");
+const DEEZER_TRACK: &str = include_str!("../fixtures/platforms/deezer_track.json");
+const DEEZER_ALBUM: &str = include_str!("../fixtures/platforms/deezer_album.json");
+const APPLE_MUSIC_SONGS: &str = include_str!("../fixtures/platforms/apple_music_songs.json");
+const APPLE_MUSIC_ALBUMS: &str = include_str!("../fixtures/platforms/apple_music_albums.json");
+const TIDAL_TRACKS: &str = include_str!("../fixtures/platforms/tidal_tracks.json");
+const TIDAL_ALBUMS: &str = include_str!("../fixtures/platforms/tidal_albums.json");
+
+fn spotify_track() -> PlatformEntity {
+    serde_json::from_str::<SpotifyTrack>(SPOTIFY_TRACK)
+        .expect("The fixture should deserialize")
+        .into_entity()
+        .expect("The track should be complete")
+}
+
+fn spotify_album() -> PlatformEntity {
+    serde_json::from_str::<SpotifyAlbum>(SPOTIFY_ALBUM)
+        .expect("The fixture should deserialize")
+        .into_entity()
+        .expect("The album should be complete")
+}
+
+fn deezer_track() -> PlatformEntity {
+    serde_json::from_str::<DeezerTrack>(DEEZER_TRACK)
+        .expect("The fixture should deserialize")
+        .into()
+}
+
+fn deezer_album() -> PlatformEntity {
+    serde_json::from_str::<DeezerAlbum>(DEEZER_ALBUM)
+        .expect("The fixture should deserialize")
+        .into()
+}
+
+fn apple_music_songs() -> Vec<PlatformEntity> {
+    serde_json::from_str::<AppleMusicList<SongAttributes>>(APPLE_MUSIC_SONGS)
+        .expect("The fixture should deserialize")
+        .data
+        .into_iter()
+        .filter_map(|song| song.into_entity())
+        .collect()
+}
+
+fn apple_music_albums() -> Vec<PlatformEntity> {
+    serde_json::from_str::<AppleMusicList<AlbumAttributes>>(APPLE_MUSIC_ALBUMS)
+        .expect("The fixture should deserialize")
+        .data
+        .into_iter()
+        .map(|album| album.into_entity().expect("The album should be complete"))
+        .collect()
+}
+
+fn tidal_tracks() -> Vec<PlatformEntity> {
+    serde_json::from_str::<TidalList<tidal::TrackAttributes>>(TIDAL_TRACKS)
+        .expect("The fixture should deserialize")
+        .data
+        .into_iter()
+        .map(|track| track.into_entity())
+        .collect()
+}
+
+fn tidal_albums() -> Vec<PlatformEntity> {
+    serde_json::from_str::<TidalList<tidal::AlbumAttributes>>(TIDAL_ALBUMS)
+        .expect("The fixture should deserialize")
+        .data
+        .into_iter()
+        .map(|album| album.into_entity())
+        .collect()
+}
+
+/// The recorded song on every matchable platform and its album on Spotify and Deezer
+pub fn recorded_entities() -> Vec<PlatformEntity> {
+    let mut entities = vec![
+        spotify_track(),
+        deezer_track(),
+        spotify_album(),
+        deezer_album(),
+    ];
+    entities.extend(apple_music_songs());
+    entities.extend(tidal_tracks());
+    entities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::odesli::Platform;
+    use crate::clients::platforms::EntityKind;
+
+    const ISRC: &str = "GBARL9300135";
+    const UPC: &str = "4050538669950";
+
+    fn assert_entity(entity: &PlatformEntity, platform: Platform, kind: EntityKind, code: &str) {
+        assert_eq!(entity.platform, platform);
+        assert_eq!(entity.kind, kind);
+        assert_eq!(entity.code.as_deref(), Some(code), "{}", entity.url);
+    }
+
+    #[test]
+    fn spotify_codes_are_read_from_the_external_ids() {
+        let track = spotify_track();
+        assert_entity(&track, Platform::Spotify, EntityKind::Song, ISRC);
+        assert_eq!(track.id, "4cOdK2wGLETKBW3PvgPWqT");
+        assert_eq!(track.title, "Never Gonna Give You Up");
+        assert_eq!(track.artist_name.as_deref(), Some("Rick Astley"));
+        // The first image is the largest one
+        assert_eq!(
+            track.thumbnail_url.as_deref(),
+            Some("https://i.scdn.co/image/ab67616d0000b27315ebbedaacef61af244262a8")
+        );
+
+        let album = spotify_album();
+        assert_entity(&album, Platform::Spotify, EntityKind::Album, UPC);
+        assert_eq!(
+            album.url,
+            "https://open.spotify.com/album/6XhjNHCyCDyyGJRM5mg40G"
+        );
+    }
+
+    #[test]
+    fn spotify_search_results_have_no_external_ids() {
+        let response: SearchResponse =
+            serde_json::from_str(SPOTIFY_SEARCH_ALBUM).expect("The fixture should deserialize");
+        assert!(response.tracks.is_none());
+
+        let album = response
+            .albums
+            .and_then(|albums| albums.items.into_iter().next())
+            .and_then(SpotifyAlbum::into_entity)
+            .expect("The search should find the album");
+        assert_eq!(album.id, "6XhjNHCyCDyyGJRM5mg40G");
+        // Which is why the code of the lookup is used instead
+        assert_eq!(album.code, None);
+    }
+
+    #[test]
+    fn deezer_codes_are_read_from_the_entity() {
+        let track = deezer_track();
+        assert_entity(&track, Platform::Deezer, EntityKind::Song, ISRC);
+        assert_eq!(track.id, "781592622");
+        assert_eq!(track.url, "https://www.deezer.com/track/781592622");
+        assert!(
+            track
+                .thumbnail_url
+                .is_some_and(|url| url.contains("1000x1000"))
+        );
+
+        let album = deezer_album();
+        assert_entity(&album, Platform::Deezer, EntityKind::Album, UPC);
+        assert_eq!(album.artist_name.as_deref(), Some("Rick Astley"));
+    }
+
+    #[test]
+    fn apple_music_codes_are_read_from_the_attributes() {
+        let [song] = apple_music_songs()
+            .try_into()
+            .expect("One song was recorded");
+        assert_entity(&song, Platform::AppleMusic, EntityKind::Song, ISRC);
+        assert_eq!(song.id, "1559523359");
+        assert_eq!(
+            song.thumbnail_url.as_deref(),
+            Some(
+                "https://is1-ssl.mzstatic.com/image/thumb/Music116/v4/b6/0a/92/\
+                 b60a92ff-d3c3-2a0c-a4a5-3c4a12a48c1b/4050538669950.jpg/640x640bb.jpg"
+            )
+        );
+
+        let [album] = apple_music_albums()
+            .try_into()
+            .expect("One album was recorded");
+        assert_entity(&album, Platform::AppleMusic, EntityKind::Album, UPC);
+        assert_eq!(album.id, "1559523357");
+    }
+
+    #[test]
+    fn tidal_codes_are_read_from_the_attributes() {
+        let [track] = tidal_tracks().try_into().expect("One track was recorded");
+        assert_entity(&track, Platform::Tidal, EntityKind::Song, ISRC);
+        assert_eq!(track.url, "https://tidal.com/browse/track/180783437");
+
+        let [album] = tidal_albums().try_into().expect("One album was recorded");
+        assert_entity(&album, Platform::Tidal, EntityKind::Album, UPC);
+        assert_eq!(album.url, "https://tidal.com/browse/album/180783436");
+    }
+}
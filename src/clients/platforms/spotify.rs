@@ -0,0 +1,263 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use serde::Deserialize;
+
+use crate::clients::odesli::Platform;
+use crate::clients::platforms::metadata::{EntityKind, PlatformEntity};
+use crate::clients::platforms::{PlatformApiErr, PlatformsClient, PlaylistPage, PlaylistTrack};
+
+pub(super) const TOKEN_URL: &str = "https://accounts.spotify.com/api/token";
+const API_BASE: &str = "https://api.spotify.com/v1";
+
+#[derive(Deserialize)]
+pub(super) struct SpotifyArtist {
+    pub name: String,
+    pub external_urls: Option<ExternalUrls>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct ExternalUrls {
+    pub spotify: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct ExternalIds {
+    pub isrc: Option<String>,
+    pub upc: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct SpotifyImage {
+    pub url: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct SpotifyTrack {
+    pub id: Option<String>,
+    pub name: String,
+    pub artists: Vec<SpotifyArtist>,
+    pub external_urls: ExternalUrls,
+    pub external_ids: Option<ExternalIds>,
+    pub album: Option<SpotifyTrackAlbum>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct SpotifyTrackAlbum {
+    pub images: Vec<SpotifyImage>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct SpotifyAlbum {
+    pub id: String,
+    pub name: String,
+    pub artists: Vec<SpotifyArtist>,
+    pub images: Vec<SpotifyImage>,
+    pub external_urls: ExternalUrls,
+    /// Only included when the album is requested directly
+    pub external_ids: Option<ExternalIds>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct SearchResponse {
+    pub tracks: Option<SearchItems<SpotifyTrack>>,
+    pub albums: Option<SearchItems<SpotifyAlbum>>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct SearchItems<T> {
+    pub items: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct PlaylistResponse {
+    name: String,
+    tracks: PlaylistTracksResponse,
+}
+
+#[derive(Deserialize)]
+struct PlaylistTracksResponse {
+    total: usize,
+}
+
+#[derive(Deserialize)]
+struct PlaylistItemsResponse {
+    items: Vec<PlaylistItem>,
+}
+
+#[derive(Deserialize)]
+struct PlaylistItem {
+    /// Null for tracks that were removed from Spotify
+    track: Option<SpotifyTrack>,
+}
+
+impl From<SpotifyTrack> for PlaylistTrack {
+    fn from(track: SpotifyTrack) -> Self {
+        Self {
+            title: track.name,
+            artist: track.artists.into_iter().next().map(|artist| artist.name),
+            url: track.external_urls.spotify,
+        }
+    }
+}
+
+impl SpotifyTrack {
+    pub(super) fn into_entity(self) -> Option<PlatformEntity> {
+        // The first image is the largest one
+        let thumbnail_url = self
+            .album
+            .and_then(|album| album.images.into_iter().next())
+            .map(|image| image.url);
+
+        Some(PlatformEntity {
+            platform: Platform::Spotify,
+            id: self.id?,
+            kind: EntityKind::Song,
+            title: self.name,
+            artist_name: self.artists.into_iter().next().map(|artist| artist.name),
+            thumbnail_url,
+            url: self.external_urls.spotify?,
+            code: self.external_ids.and_then(|ids| ids.isrc),
+        })
+    }
+}
+
+impl SpotifyAlbum {
+    pub(super) fn into_entity(self) -> Option<PlatformEntity> {
+        Some(PlatformEntity {
+            platform: Platform::Spotify,
+            id: self.id,
+            kind: EntityKind::Album,
+            title: self.name,
+            artist_name: self.artists.into_iter().next().map(|artist| artist.name),
+            thumbnail_url: self.images.into_iter().next().map(|image| image.url),
+            url: self.external_urls.spotify?,
+            code: self.external_ids.and_then(|ids| ids.upc),
+        })
+    }
+}
+
+impl PlatformsClient {
+    pub(super) async fn spotify_token(&self) -> Result<String, PlatformApiErr> {
+        let credentials = self
+            .inner
+            .spotify
+            .as_ref()
+            .ok_or(PlatformApiErr::NotConfigured("Spotify"))?;
+
+        self.access_token(credentials).await
+    }
+
+    /// Sends an authenticated GET request to the given Spotify API path
+    pub(super) async fn spotify_get<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, PlatformApiErr> {
+        let token = self.spotify_token().await?;
+        let req = self
+            .inner
+            .client
+            .get(format!("{}{}", API_BASE, path))
+            .query(query)
+            .bearer_auth(token)
+            .build()?;
+
+        self.execute_json(req).await
+    }
+
+    pub(super) async fn spotify_playlist_page(
+        &self,
+        id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<PlaylistPage, PlatformApiErr> {
+        let playlist: PlaylistResponse = self
+            .spotify_get(
+                &format!("/playlists/{}", id),
+                &[("fields", "name,tracks.total")],
+            )
+            .await?;
+
+        let offset_str = offset.to_string();
+        let limit_str = limit.to_string();
+        let items: PlaylistItemsResponse = self
+            .spotify_get(
+                &format!("/playlists/{}/tracks", id),
+                &[
+                    ("offset", offset_str.as_str()),
+                    ("limit", limit_str.as_str()),
+                    ("fields", "items(track(name,artists(name),external_urls))"),
+                ],
+            )
+            .await?;
+
+        Ok(PlaylistPage {
+            name: playlist.name,
+            total: playlist.tracks.total,
+            tracks: items
+                .items
+                .into_iter()
+                .filter_map(|item| item.track)
+                .map(PlaylistTrack::from)
+                .collect(),
+        })
+    }
+
+    pub(super) async fn spotify_entity(
+        &self,
+        kind: EntityKind,
+        id: &str,
+    ) -> Result<PlatformEntity, PlatformApiErr> {
+        let entity = match kind {
+            EntityKind::Song => self
+                .spotify_get::<SpotifyTrack>(&format!("/tracks/{}", id), &[])
+                .await?
+                .into_entity(),
+            EntityKind::Album => self
+                .spotify_get::<SpotifyAlbum>(&format!("/albums/{}", id), &[])
+                .await?
+                .into_entity(),
+        };
+
+        entity.ok_or(PlatformApiErr::NotFound)
+    }
+
+    /// Finds the song with the ISRC or the album with the UPC
+    pub(super) async fn spotify_find_by_code(
+        &self,
+        kind: EntityKind,
+        code: &str,
+    ) -> Result<PlatformEntity, PlatformApiErr> {
+        let (query, kind_str) = match kind {
+            EntityKind::Song => (format!("isrc:{}", code), "track"),
+            EntityKind::Album => (format!("upc:{}", code), "album"),
+        };
+        let response: SearchResponse = self
+            .spotify_get(
+                "/search",
+                &[("q", query.as_str()), ("type", kind_str), ("limit", "1")],
+            )
+            .await?;
+
+        let entity = match kind {
+            EntityKind::Song => response
+                .tracks
+                .and_then(|tracks| tracks.items.into_iter().next())
+                .and_then(SpotifyTrack::into_entity),
+            EntityKind::Album => response
+                .albums
+                .and_then(|albums| albums.items.into_iter().next())
+                .and_then(SpotifyAlbum::into_entity),
+        };
+
+        entity
+            .map(|entity| PlatformEntity {
+                code: Some(code.to_string()),
+                ..entity
+            })
+            .ok_or(PlatformApiErr::NotFound)
+    }
+}
@@ -0,0 +1,194 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use crate::clients::odesli::Platform;
+use crate::clients::platforms::metadata::{EntityKind, PlatformEntity};
+use crate::clients::platforms::{PlatformApiErr, PlatformsClient};
+
+pub(super) const TOKEN_URL: &str = "https://auth.tidal.com/v1/oauth2/token";
+const API_BASE: &str = "https://openapi.tidal.com/v2";
+const COUNTRY_CODE: &str = "US";
+
+/// The Tidal API follows the JSON:API specification
+#[derive(Deserialize)]
+pub(super) struct TidalList<T> {
+    pub data: Vec<TidalResource<T>>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TidalResource<T> {
+    pub id: String,
+    pub attributes: T,
+}
+
+/// A relationship only lists the identifiers of the related resources
+#[derive(Deserialize)]
+struct TidalIdentifier {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct TidalRelationship {
+    data: Vec<TidalIdentifier>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TrackAttributes {
+    pub title: String,
+    pub isrc: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct AlbumAttributes {
+    pub title: String,
+    pub barcode_id: Option<String>,
+}
+
+impl TidalResource<TrackAttributes> {
+    pub(super) fn into_entity(self) -> PlatformEntity {
+        tidal_entity(
+            EntityKind::Song,
+            self.id,
+            self.attributes.title,
+            self.attributes.isrc,
+        )
+    }
+}
+
+impl TidalResource<AlbumAttributes> {
+    pub(super) fn into_entity(self) -> PlatformEntity {
+        tidal_entity(
+            EntityKind::Album,
+            self.id,
+            self.attributes.title,
+            self.attributes.barcode_id,
+        )
+    }
+}
+
+fn tidal_entity(
+    kind: EntityKind,
+    id: String,
+    title: String,
+    code: Option<String>,
+) -> PlatformEntity {
+    PlatformEntity {
+        platform: Platform::Tidal,
+        url: format!("https://tidal.com/browse/{}/{}", tidal_kind(kind), id),
+        id,
+        kind,
+        title,
+        // Artists and artwork are only available as separate relationships
+        artist_name: None,
+        thumbnail_url: None,
+        code,
+    }
+}
+
+impl PlatformsClient {
+    /// Sends an authenticated GET request to the given Tidal API path
+    async fn tidal_get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, PlatformApiErr> {
+        let credentials = self
+            .inner
+            .tidal
+            .as_ref()
+            .ok_or(PlatformApiErr::NotConfigured("Tidal"))?;
+        let token = self.access_token(credentials).await?;
+
+        let req = self
+            .inner
+            .client
+            .get(format!("{}{}", API_BASE, path))
+            .query(&[("countryCode", COUNTRY_CODE)])
+            .query(query)
+            .header(reqwest::header::ACCEPT, "application/vnd.api+json")
+            .bearer_auth(token)
+            .build()?;
+
+        self.execute_json(req).await
+    }
+
+    /// Returns the link to the most popular track of the artist
+    pub(super) async fn tidal_artist_top_track(&self, id: u64) -> Result<String, PlatformApiErr> {
+        let tracks: TidalRelationship = self
+            .tidal_get(
+                &format!("/artists/{}/relationships/tracks", id),
+                &[("collapseBy", "FINGERPRINT")],
+            )
+            .await?;
+
+        tracks
+            .data
+            .into_iter()
+            .next()
+            .map(|track| format!("https://tidal.com/browse/track/{}", track.id))
+            .ok_or(PlatformApiErr::NotFound)
+    }
+
+    /// Returns the link to the profile of the (main) artist of the track
+    pub(super) async fn tidal_track_artist(&self, id: u64) -> Result<String, PlatformApiErr> {
+        let artists: TidalRelationship = self
+            .tidal_get(&format!("/tracks/{}/relationships/artists", id), &[])
+            .await?;
+
+        artists
+            .data
+            .into_iter()
+            .next()
+            .map(|artist| format!("https://tidal.com/browse/artist/{}", artist.id))
+            .ok_or(PlatformApiErr::NotFound)
+    }
+
+    /// Finds the song with the ISRC or the album with the UPC
+    pub(super) async fn tidal_find_by_code(
+        &self,
+        kind: EntityKind,
+        code: &str,
+    ) -> Result<PlatformEntity, PlatformApiErr> {
+        let entity = match kind {
+            EntityKind::Song => {
+                let tracks: TidalList<TrackAttributes> =
+                    self.tidal_get("/tracks", &[("filter[isrc]", code)]).await?;
+                tracks
+                    .data
+                    .into_iter()
+                    .next()
+                    .map(|track| track.into_entity())
+            }
+            EntityKind::Album => {
+                let albums: TidalList<AlbumAttributes> = self
+                    .tidal_get("/albums", &[("filter[barcodeId]", code)])
+                    .await?;
+                albums
+                    .data
+                    .into_iter()
+                    .next()
+                    .map(|album| album.into_entity())
+            }
+        };
+
+        entity
+            .map(|entity| PlatformEntity {
+                code: Some(code.to_string()),
+                ..entity
+            })
+            .ok_or(PlatformApiErr::NotFound)
+    }
+}
+
+fn tidal_kind(kind: EntityKind) -> &'static str {
+    match kind {
+        EntityKind::Song => "track",
+        EntityKind::Album => "album",
+    }
+}
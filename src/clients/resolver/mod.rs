@@ -0,0 +1,293 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::future::BoxFuture;
+use metronomos_pulse::value::{ArcValue, PulseValue};
+use tokio::time::Instant;
+use tracing::{debug, instrument};
+use url::Url;
+
+use crate::args::{Args, LinkResolverKind};
+use crate::clients::odesli::{
+    AcquirePolicy, ApiErr, OdesliClient, OdesliClientResponse, RequestPriority,
+};
+use crate::clients::platforms::{PlatformApiErr, PlatformsClient};
+
+mod native;
+
+pub use native::NativeResolver;
+
+/// A service that finds the links to a song or album on all platforms.
+pub trait LinkResolver: Send + Sync {
+    /// The name of the resolver, used for logging
+    fn name(&self) -> &'static str;
+
+    /// Resolves the link, the policy decides whether to wait for rate limits
+    fn resolve<'a>(
+        &'a self,
+        url: &'a Url,
+        policy: AcquirePolicy,
+    ) -> BoxFuture<'a, Result<OdesliClientResponse, ApiErr>>;
+}
+
+impl LinkResolver for OdesliClient {
+    fn name(&self) -> &'static str {
+        "Odesli"
+    }
+
+    fn resolve<'a>(
+        &'a self,
+        url: &'a Url,
+        policy: AcquirePolicy,
+    ) -> BoxFuture<'a, Result<OdesliClientResponse, ApiErr>> {
+        Box::pin(self.fetch_with_policy(url, policy))
+    }
+}
+
+/// Client for resolving links, trying each configured [`LinkResolver`] in order while they are
+/// unavailable.
+#[derive(Clone, PulseValue)]
+pub struct ResolverClient {
+    chain: Arc<[Arc<dyn LinkResolver>]>,
+}
+
+impl fmt::Debug for ResolverClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolverClient")
+            .field(
+                "chain",
+                &self.chain.iter().map(|r| r.name()).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl ResolverClient {
+    pub fn init(args: ArcValue<Args>, odesli: OdesliClient, platforms: PlatformsClient) -> Self {
+        let mut kinds = Vec::new();
+        for kind in &args.link_resolvers {
+            if !kinds.contains(kind) {
+                kinds.push(*kind);
+            }
+        }
+
+        let chain = kinds
+            .into_iter()
+            .map(|kind| -> Arc<dyn LinkResolver> {
+                match kind {
+                    LinkResolverKind::Odesli => Arc::new(odesli.clone()),
+                    LinkResolverKind::Native => {
+                        Arc::new(NativeResolver::new(platforms.clone(), odesli.clone()))
+                    }
+                }
+            })
+            .collect();
+
+        Self::with_chain(chain)
+    }
+
+    pub fn with_chain(chain: Vec<Arc<dyn LinkResolver>>) -> Self {
+        Self {
+            chain: chain.into(),
+        }
+    }
+
+    /// Resolves the link with the lowest priority, for requests nobody is actively waiting on.
+    /// Fails with [`ApiErr::RateLimitDeadlineExceeded`] if the rate limit doesn't allow the
+    /// request within the timeout.
+    pub async fn resolve(
+        &self,
+        url: &Url,
+        timeout: Duration,
+    ) -> Result<OdesliClientResponse, ApiErr> {
+        let policy =
+            AcquirePolicy::wait_until(RequestPriority::Background, Instant::now() + timeout);
+        self.resolve_with_policy(url, policy).await
+    }
+
+    /// Like [`Self::resolve`], but doesn't wait for rate limits, failing with
+    /// [`ApiErr::RateLimitBudgetExhausted`] if no resolver could answer right away.
+    pub async fn try_resolve(&self, url: &Url) -> Result<OdesliClientResponse, ApiErr> {
+        self.resolve_with_policy(url, AcquirePolicy::Immediate)
+            .await
+    }
+
+    /// Tries the resolvers in order, the next one is only tried if the previous one is
+    /// [unavailable](ApiErr::is_unavailable). Any other error, e.g. an exhausted rate limit budget,
+    /// is returned right away, otherwise the error of the first resolver is returned.
+    #[instrument(level = "debug", skip(self, url))]
+    pub async fn resolve_with_policy(
+        &self,
+        url: &Url,
+        policy: AcquirePolicy,
+    ) -> Result<OdesliClientResponse, ApiErr> {
+        let mut first_err = None;
+        for resolver in self.chain.iter() {
+            match resolver.resolve(url, policy).await {
+                Ok(response) => return Ok(response),
+                Err(e) if !e.is_unavailable() => return Err(e),
+                Err(e) => {
+                    debug!(
+                        resolver = resolver.name(),
+                        "Failed to resolve link, trying the next resolver: {}", e
+                    );
+                    first_err.get_or_insert(e);
+                }
+            }
+        }
+
+        Err(first_err.unwrap_or(ApiErr::Platform(PlatformApiErr::Unsupported)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::clients::odesli::{ApiClientErr, OdesliResponse};
+
+    /// Answers every link with the same result, counting how often it was asked
+    struct StubResolver {
+        name: &'static str,
+        result: fn() -> Result<OdesliClientResponse, ApiErr>,
+        calls: AtomicUsize,
+    }
+
+    impl StubResolver {
+        fn new(
+            name: &'static str,
+            result: fn() -> Result<OdesliClientResponse, ApiErr>,
+        ) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                result,
+                calls: AtomicUsize::new(0),
+            })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::Relaxed)
+        }
+    }
+
+    impl LinkResolver for StubResolver {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _url: &'a Url,
+            _policy: AcquirePolicy,
+        ) -> BoxFuture<'a, Result<OdesliClientResponse, ApiErr>> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            Box::pin(std::future::ready((self.result)()))
+        }
+    }
+
+    fn resolved() -> Result<OdesliClientResponse, ApiErr> {
+        Ok(OdesliClientResponse::uncached(OdesliResponse {
+            entity_unique_id: String::new(),
+            user_country: "US".to_string(),
+            page_url: "https://song.link/s/1".to_string(),
+            links_by_platform: HashMap::new(),
+            entities_by_unique_id: HashMap::new(),
+        }))
+    }
+
+    fn server_error() -> Result<OdesliClientResponse, ApiErr> {
+        Err(ApiErr::UnexpectedResponseStatus {
+            status_code: StatusCode::BAD_GATEWAY,
+            text: String::new(),
+        })
+    }
+
+    fn rate_limited() -> Result<OdesliClientResponse, ApiErr> {
+        Err(ApiErr::RateLimitExceeded)
+    }
+
+    fn budget_exhausted() -> Result<OdesliClientResponse, ApiErr> {
+        Err(ApiErr::RateLimitBudgetExhausted)
+    }
+
+    fn deadline_exceeded() -> Result<OdesliClientResponse, ApiErr> {
+        Err(ApiErr::RateLimitDeadlineExceeded)
+    }
+
+    fn unknown_entity() -> Result<OdesliClientResponse, ApiErr> {
+        Err(ApiErr::ClientError(ApiClientErr::UnknownEntity))
+    }
+
+    fn unsupported() -> Result<OdesliClientResponse, ApiErr> {
+        Err(ApiErr::Platform(PlatformApiErr::Unsupported))
+    }
+
+    async fn resolve(
+        first: &Arc<StubResolver>,
+        second: &Arc<StubResolver>,
+    ) -> Result<OdesliClientResponse, ApiErr> {
+        let client = ResolverClient::with_chain(vec![first.clone(), second.clone()]);
+        let url = Url::parse("https://open.spotify.com/track/4cOdK2wGLETKBW3PvgPWqT").unwrap();
+
+        client.try_resolve(&url).await
+    }
+
+    #[tokio::test]
+    async fn falls_back_while_unavailable() {
+        for result in [server_error, rate_limited, unsupported] {
+            let odesli = StubResolver::new("Odesli", result);
+            let native = StubResolver::new("Native", resolved);
+
+            let response = resolve(&odesli, &native).await;
+
+            assert_eq!(
+                response.map(|r| r.page_url.clone()).ok().as_deref(),
+                Some("https://song.link/s/1")
+            );
+            assert_eq!(native.calls(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn returns_other_errors_without_falling_back() {
+        for result in [budget_exhausted, deadline_exceeded, unknown_entity] {
+            let odesli = StubResolver::new("Odesli", result);
+            let native = StubResolver::new("Native", resolved);
+
+            let err = resolve(&odesli, &native).await.err().unwrap();
+
+            assert_eq!(err.to_string(), result().err().unwrap().to_string());
+            assert_eq!(native.calls(), 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn returns_the_first_error_if_all_are_unavailable() {
+        let odesli = StubResolver::new("Odesli", server_error);
+        let native = StubResolver::new("Native", unsupported);
+
+        let err = resolve(&odesli, &native).await.err().unwrap();
+
+        assert!(matches!(err, ApiErr::UnexpectedResponseStatus { .. }));
+        assert_eq!((odesli.calls(), native.calls()), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn stops_at_the_first_answer() {
+        let odesli = StubResolver::new("Odesli", resolved);
+        let native = StubResolver::new("Native", resolved);
+
+        assert!(resolve(&odesli, &native).await.is_ok());
+        assert_eq!(native.calls(), 0);
+    }
+}
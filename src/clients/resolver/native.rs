@@ -0,0 +1,103 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::collections::HashMap;
+
+use futures_util::future::BoxFuture;
+use tracing::{debug, instrument};
+use url::Url;
+
+use crate::clients::matching::{Matcher, insert_entity};
+use crate::clients::odesli::provider_id::ProviderId;
+use crate::clients::odesli::{
+    AcquirePolicy, ApiClientErr, ApiErr, OdesliClient, OdesliClientResponse, OdesliResponse,
+};
+use crate::clients::platforms::{PlatformApiErr, PlatformEntity, PlatformsClient};
+use crate::clients::resolver::LinkResolver;
+
+/// Resolves links with the metadata APIs of the platforms, matching songs by their ISRC and
+/// albums by their UPC.
+///
+/// Links can only be resolved from Spotify, Deezer and Apple Music, so this is mostly useful as a
+/// fallback for when Odesli is unavailable.
+pub struct NativeResolver {
+    platforms: PlatformsClient,
+    matcher: Matcher,
+    /// Used to cache the responses, so the buttons and landing pages can look them up
+    odesli: OdesliClient,
+}
+
+impl NativeResolver {
+    pub fn new(platforms: PlatformsClient, odesli: OdesliClient) -> Self {
+        Self {
+            matcher: Matcher::new(platforms.clone()),
+            platforms,
+            odesli,
+        }
+    }
+
+    #[instrument(level = "debug", skip_all, fields(link = %url))]
+    async fn resolve_inner(&self, url: &Url) -> Result<OdesliClientResponse, ApiErr> {
+        let provider_id = ProviderId::parse_url(url).map_err(|e| {
+            debug!("Failed to parse provider ID from URL: {}", e);
+            ApiErr::Platform(PlatformApiErr::Unsupported)
+        })?;
+        if !self.platforms.supports_entity(&provider_id) {
+            return Err(ApiErr::Platform(PlatformApiErr::Unsupported));
+        }
+
+        let source = self
+            .platforms
+            .fetch_entity(&provider_id)
+            .await
+            .map_err(platform_err)?;
+        let kind = source.kind;
+        let code = source.code.clone();
+
+        let mut response = build_response(source);
+        match code {
+            Some(code) => self.matcher.add_matches(&mut response, kind, &code).await,
+            None => debug!("Entity has no ISRC/UPC, only linking the original platform"),
+        }
+
+        Ok(self.odesli.store_native_response(response))
+    }
+}
+
+impl LinkResolver for NativeResolver {
+    fn name(&self) -> &'static str {
+        "Native"
+    }
+
+    fn resolve<'a>(
+        &'a self,
+        url: &'a Url,
+        _policy: AcquirePolicy,
+    ) -> BoxFuture<'a, Result<OdesliClientResponse, ApiErr>> {
+        Box::pin(self.resolve_inner(url))
+    }
+}
+
+fn platform_err(err: PlatformApiErr) -> ApiErr {
+    match err {
+        PlatformApiErr::NotFound => ApiErr::ClientError(ApiClientErr::UnknownEntity),
+        err => ApiErr::Platform(err),
+    }
+}
+
+/// Builds a response in the same shape Odesli returns, with the source entity as the input
+fn build_response(source: PlatformEntity) -> OdesliResponse {
+    let mut response = OdesliResponse {
+        entity_unique_id: String::new(),
+        user_country: "US".to_string(),
+        // There is no song.link page for the entity, so link to the original instead
+        page_url: source.url.clone(),
+        links_by_platform: HashMap::new(),
+        entities_by_unique_id: HashMap::new(),
+    };
+    response.entity_unique_id = insert_entity(&mut response, source);
+
+    response
+}
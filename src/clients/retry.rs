@@ -0,0 +1,299 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hasher, RandomState};
+use std::time::Duration;
+
+use tokio::time::{Instant, sleep};
+use tracing::debug;
+
+use crate::metrics::MetricsStore;
+use crate::metrics::labels::{AttemptOutcome, ThirdPartyApi, ThirdPartyAttemptLabels};
+
+/// Errors that can tell whether they are worth retrying
+pub trait Retryable {
+    /// Whether the failure is likely transient, so sending the request again could succeed
+    fn is_retryable(&self) -> bool;
+}
+
+impl Retryable for reqwest::Error {
+    fn is_retryable(&self) -> bool {
+        self.is_timeout()
+            || self.is_connect()
+            || self.status().is_some_and(|status| status.is_server_error())
+    }
+}
+
+/// Retries transient failures with jittered exponential backoff
+#[derive(Copy, Clone, Debug)]
+pub struct RetryPolicy {
+    /// The number of attempts including the first one
+    pub max_attempts: u32,
+    /// The backoff before the first retry, doubled for every further retry
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The default backoff with the number of attempts, at least one attempt is always made
+    pub fn with_max_attempts(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// A random delay between zero and the exponential backoff for the attempt
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+            .min(self.max_delay);
+
+        exponential.mul_f64(jitter())
+    }
+
+    /// Runs the attempt until it succeeds, fails with an error that isn't retryable or runs out
+    /// of attempts.
+    ///
+    /// No retry is started if its backoff would end after the deadline, the error of the last
+    /// attempt is returned instead.
+    pub async fn run<T, E, F, Fut>(
+        &self,
+        api: ThirdPartyApi,
+        deadline: Option<Instant>,
+        metrics: &MetricsStore,
+        mut attempt_fn: F,
+    ) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Retryable + Display,
+    {
+        let record = |outcome| {
+            metrics
+                .third_party_api_attempts()
+                .get_or_create(&ThirdPartyAttemptLabels { api, outcome })
+                .inc();
+        };
+
+        let mut attempt = 1;
+        loop {
+            let err = match attempt_fn().await {
+                Ok(value) => {
+                    record(AttemptOutcome::Success);
+                    return Ok(value);
+                }
+                Err(err) => err,
+            };
+
+            if !err.is_retryable() || attempt >= self.max_attempts {
+                record(AttemptOutcome::Failure);
+                return Err(err);
+            }
+
+            let delay = self.backoff(attempt);
+            if deadline.is_some_and(|deadline| Instant::now() + delay >= deadline) {
+                debug!(
+                    attempt,
+                    "Not retrying, the backoff would exceed the deadline"
+                );
+                record(AttemptOutcome::Failure);
+                return Err(err);
+            }
+
+            debug!(
+                attempt,
+                ?delay,
+                ?api,
+                "Request failed with a transient error, retrying: {}",
+                err
+            );
+            record(AttemptOutcome::Retried);
+            sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// A random factor between 0 and 1, a randomly seeded hasher is good enough for jitter
+fn jitter() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::Router;
+    use axum::extract::State;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// Answers every request with the next of the statuses, repeating the last one
+    struct MockServer {
+        url: String,
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl MockServer {
+        async fn start(statuses: &[u16]) -> Self {
+            let statuses = statuses.to_vec();
+            let requests = Arc::new(AtomicUsize::new(0));
+            let router = Router::new()
+                .route(
+                    "/",
+                    get(move |State(requests): State<Arc<AtomicUsize>>| async move {
+                        let request = requests.fetch_add(1, Ordering::Relaxed);
+                        let status = statuses[request.min(statuses.len() - 1)];
+                        StatusCode::from_u16(status).unwrap()
+                    }),
+                )
+                .with_state(requests.clone());
+
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}/", listener.local_addr().unwrap());
+            tokio::spawn(async move { axum::serve(listener, router).await });
+
+            Self { url, requests }
+        }
+
+        fn requests(&self) -> usize {
+            self.requests.load(Ordering::Relaxed)
+        }
+    }
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        }
+    }
+
+    async fn request(
+        policy: RetryPolicy,
+        deadline: Option<Instant>,
+        url: &str,
+    ) -> Result<StatusCode, reqwest::Error> {
+        let client = reqwest::Client::new();
+        policy
+            .run(
+                ThirdPartyApi::Thumbnail,
+                deadline,
+                &MetricsStore::new(),
+                || async {
+                    let resp = client.get(url).send().await?.error_for_status()?;
+                    Ok(resp.status())
+                },
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_until_success() {
+        let server = MockServer::start(&[503, 502, 200]).await;
+
+        let result = request(policy(), None, &server.url).await;
+
+        assert_eq!(result.ok(), Some(StatusCode::OK));
+        assert_eq!(server.requests(), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_the_last_attempt() {
+        let server = MockServer::start(&[500]).await;
+
+        let result = request(policy(), None, &server.url).await;
+
+        assert_eq!(
+            result.err().and_then(|err| err.status()),
+            Some(StatusCode::INTERNAL_SERVER_ERROR)
+        );
+        assert_eq!(server.requests(), 3);
+    }
+
+    #[tokio::test]
+    async fn doesnt_retry_client_errors() {
+        for status in [400, 404, 429] {
+            let server = MockServer::start(&[status, 200]).await;
+
+            let result = request(policy(), None, &server.url).await;
+
+            assert!(result.is_err());
+            assert_eq!(server.requests(), 1, "status {}", status);
+        }
+    }
+
+    #[tokio::test]
+    async fn doesnt_retry_past_the_deadline() {
+        let server = MockServer::start(&[503, 200]).await;
+        let policy = RetryPolicy {
+            base_delay: Duration::from_secs(60),
+            max_delay: Duration::from_secs(60),
+            ..policy()
+        };
+
+        // The jitter can pick a very short backoff, so the deadline has to be before any of them
+        let result = request(policy, Some(Instant::now()), &server.url).await;
+
+        assert!(result.is_err());
+        assert_eq!(server.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn retries_connection_errors() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+
+        let attempts = AtomicUsize::new(0);
+        let client = reqwest::Client::new();
+        let result = policy()
+            .run(ThirdPartyApi::Thumbnail, None, &MetricsStore::new(), || {
+                attempts.fetch_add(1, Ordering::Relaxed);
+                client.get(&url).send()
+            })
+            .await;
+
+        assert!(result.is_err_and(|err| err.is_connect()));
+        assert_eq!(attempts.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn at_least_one_attempt_is_made() {
+        assert_eq!(RetryPolicy::with_max_attempts(0).max_attempts, 1);
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(300),
+        };
+
+        for attempt in 1..10 {
+            assert!(policy.backoff(attempt) <= Duration::from_millis(300));
+        }
+        assert!(policy.backoff(1) <= Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,195 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::fmt;
+use std::sync::Arc;
+
+use futures_util::future::BoxFuture;
+use metronomos_pulse::value::PulseValue;
+use tracing::{debug, instrument};
+
+use crate::clients::platforms::{PlatformApiErr, PlatformsClient};
+
+/// What the user is looking for, the title is always given while the rest narrows down the results
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    pub title: String,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+}
+
+impl SearchQuery {
+    /// Joins all parts of the query into a single search term
+    pub fn term(&self) -> String {
+        [
+            Some(self.title.as_str()),
+            self.artist.as_deref(),
+            self.album.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub title: String,
+    pub artist: String,
+    pub album: Option<String>,
+    /// Link to the track on the platform of the backend, this can be passed to Odesli
+    pub url: String,
+}
+
+impl SearchResult {
+    /// A short human-readable description of the result
+    pub fn label(&self) -> String {
+        match &self.album {
+            Some(album) => format!("{} \u{2013} {} ({})", self.title, self.artist, album),
+            None => format!("{} \u{2013} {}", self.title, self.artist),
+        }
+    }
+}
+
+/// A service tracks can be looked up by their metadata with.
+pub trait SearchBackend: Send + Sync {
+    /// The name of the backend, used for logging
+    fn name(&self) -> &'static str;
+
+    /// Returns up to `limit` tracks matching the query, best matches first
+    fn search<'a>(
+        &'a self,
+        query: &'a SearchQuery,
+        limit: usize,
+    ) -> BoxFuture<'a, Result<Vec<SearchResult>, PlatformApiErr>>;
+}
+
+/// Searches the iTunes catalogue, which doesn't require any credentials.
+pub struct ITunesSearch {
+    platforms: PlatformsClient,
+}
+
+impl ITunesSearch {
+    pub fn new(platforms: PlatformsClient) -> Self {
+        Self { platforms }
+    }
+}
+
+impl SearchBackend for ITunesSearch {
+    fn name(&self) -> &'static str {
+        "iTunes"
+    }
+
+    fn search<'a>(
+        &'a self,
+        query: &'a SearchQuery,
+        limit: usize,
+    ) -> BoxFuture<'a, Result<Vec<SearchResult>, PlatformApiErr>> {
+        Box::pin(self.platforms.itunes_search(query.term(), limit))
+    }
+}
+
+/// Client for searching tracks by their metadata, backed by a [`SearchBackend`].
+#[derive(Clone, PulseValue)]
+pub struct SearchClient {
+    backend: Arc<dyn SearchBackend>,
+}
+
+impl fmt::Debug for SearchClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchClient")
+            .field("backend", &self.backend.name())
+            .finish()
+    }
+}
+
+impl SearchClient {
+    pub fn init(platforms: PlatformsClient) -> Self {
+        Self::with_backend(Arc::new(ITunesSearch::new(platforms)))
+    }
+
+    pub fn with_backend(backend: Arc<dyn SearchBackend>) -> Self {
+        Self { backend }
+    }
+
+    #[instrument(level = "debug", skip(self), fields(backend = self.backend.name()))]
+    pub async fn search(
+        &self,
+        query: &SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>, PlatformApiErr> {
+        let results = self.backend.search(query, limit).await?;
+        debug!("Search returned {} results", results.len());
+
+        Ok(results)
+    }
+}
+
+/// Serves fixed results instead of searching, for testing the code built on top of the search
+#[cfg(test)]
+pub struct StubSearch {
+    results: Option<Vec<SearchResult>>,
+    /// The term and limit of every search, in order
+    pub queries: std::sync::Mutex<Vec<(String, usize)>>,
+}
+
+#[cfg(test)]
+impl StubSearch {
+    /// Returns the given `(title, artist, url)` results for every query
+    pub fn with_results(results: &[(&str, &str, &str)]) -> Self {
+        let results = results
+            .iter()
+            .map(|(title, artist, url)| SearchResult {
+                title: title.to_string(),
+                artist: artist.to_string(),
+                album: None,
+                url: url.to_string(),
+            })
+            .collect();
+
+        Self {
+            results: Some(results),
+            queries: Default::default(),
+        }
+    }
+
+    /// Fails every query, as if the backend was unavailable
+    pub fn failing() -> Self {
+        Self {
+            results: None,
+            queries: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl SearchBackend for StubSearch {
+    fn name(&self) -> &'static str {
+        "Stub"
+    }
+
+    fn search<'a>(
+        &'a self,
+        query: &'a SearchQuery,
+        limit: usize,
+    ) -> BoxFuture<'a, Result<Vec<SearchResult>, PlatformApiErr>> {
+        self.queries
+            .lock()
+            .expect("The lock should not be poisoned")
+            .push((query.term(), limit));
+
+        let results = match &self.results {
+            Some(results) => Ok(results.iter().take(limit).cloned().collect()),
+            None => Err(PlatformApiErr::UnexpectedResponseStatus {
+                status_code: reqwest::StatusCode::SERVICE_UNAVAILABLE,
+                text: String::new(),
+            }),
+        };
+        Box::pin(std::future::ready(results))
+    }
+}
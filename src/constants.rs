@@ -6,6 +6,9 @@
 pub const NAME: &str = "ShareMusic";
 pub const NAME_SHORT: &str = "Sharing";
 
+/// Milliseconds between the unix epoch and the first second of 2015, which snowflakes count from
+pub const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
 pub const CLUSTER_ID: u16 = 0;
 pub const CLUSTER_COUNT: u16 = 1;
 
@@ -21,7 +24,9 @@ pub mod cluster_consts {
     use twilight_model::gateway::payload::outgoing::update_presence::UpdatePresencePayload;
     use twilight_model::gateway::presence::{ActivityType, MinimalActivity, Status};
 
-    pub const GATEWAY_INTENTS: Intents = Intents::GUILDS;
+    pub const GATEWAY_INTENTS: Intents = Intents::GUILDS.union(Intents::GUILD_MESSAGES);
+    /// Privileged intent, only requested if it was enabled for the bot
+    pub const MESSAGE_CONTENT_INTENT: Intents = Intents::MESSAGE_CONTENT;
 
     pub fn presence() -> UpdatePresencePayload {
         UpdatePresencePayload {
@@ -42,4 +47,6 @@ pub mod cluster_consts {
 
 pub mod colour_consts {
     pub const MAX_IMAGE_SIZE: u32 = 4096;
+    /// Thumbnails with a larger body are not decoded
+    pub const MAX_IMAGE_BYTES: usize = 8 * 1024 * 1024;
 }
@@ -0,0 +1,135 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::collections::HashSet;
+
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, Set, sea_query};
+use tracing::trace;
+use twilight_model::id::Id;
+use twilight_model::id::marker::{ChannelMarker, GuildMarker};
+
+use crate::db::Database;
+use crate::db::entity::{auto_share_channel, auto_share_guild};
+use crate::db::util::snowflake_to_db;
+
+/// Where in a guild messages are scanned for links to share automatically.
+#[derive(Debug, Clone, Default)]
+pub struct AutoShareConfig {
+    pub guild_wide: bool,
+    pub channels: HashSet<Id<ChannelMarker>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum AutoShareScope {
+    Guild,
+    Channel(Id<ChannelMarker>),
+}
+
+impl AutoShareConfig {
+    pub fn is_enabled(&self, channel_id: Id<ChannelMarker>) -> bool {
+        self.guild_wide || self.channels.contains(&channel_id)
+    }
+
+    pub fn set(&mut self, scope: AutoShareScope, enabled: bool) {
+        match scope {
+            AutoShareScope::Guild => self.guild_wide = enabled,
+            AutoShareScope::Channel(channel_id) if enabled => {
+                self.channels.insert(channel_id);
+            }
+            AutoShareScope::Channel(channel_id) => {
+                self.channels.remove(&channel_id);
+            }
+        }
+    }
+}
+
+impl Database {
+    /// Loads the auto-share configuration of the guild, disabled if none was saved yet.
+    pub async fn load_auto_share_config(
+        &self,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<AutoShareConfig, DbErr> {
+        let Some(conn) = &self.connection else {
+            trace!("Db Url not provided, skipping loading auto-share config");
+            return Ok(AutoShareConfig::default());
+        };
+
+        let guild_id = snowflake_to_db(guild_id);
+        let guild_wide = auto_share_guild::Entity::find_by_id(guild_id)
+            .one(conn)
+            .await?;
+        let channels = auto_share_channel::Entity::find()
+            .filter(auto_share_channel::Column::GuildId.eq(guild_id))
+            .all(conn)
+            .await?;
+
+        Ok(AutoShareConfig {
+            guild_wide: guild_wide.is_some(),
+            channels: channels
+                .into_iter()
+                .map(|channel| Id::new(channel.channel_id as u64))
+                .collect(),
+        })
+    }
+
+    /// Enables or disables auto-share for the given scope of the guild.
+    pub async fn save_auto_share(
+        &self,
+        guild_id: Id<GuildMarker>,
+        scope: AutoShareScope,
+        enabled: bool,
+    ) -> Result<(), DbErr> {
+        let Some(conn) = &self.connection else {
+            trace!("Db Url not provided, auto-share config is only kept in memory");
+            return Ok(());
+        };
+
+        let guild_id = snowflake_to_db(guild_id);
+        match (scope, enabled) {
+            (AutoShareScope::Guild, true) => {
+                auto_share_guild::Entity::insert(auto_share_guild::ActiveModel {
+                    guild_id: Set(guild_id),
+                })
+                .on_conflict(
+                    sea_query::OnConflict::column(auto_share_guild::Column::GuildId)
+                        .do_nothing()
+                        .to_owned(),
+                )
+                .do_nothing()
+                .exec(conn)
+                .await?;
+            }
+            (AutoShareScope::Guild, false) => {
+                auto_share_guild::Entity::delete_by_id(guild_id)
+                    .exec(conn)
+                    .await?;
+            }
+            (AutoShareScope::Channel(channel_id), true) => {
+                auto_share_channel::Entity::insert(auto_share_channel::ActiveModel {
+                    guild_id: Set(guild_id),
+                    channel_id: Set(snowflake_to_db(channel_id)),
+                })
+                .on_conflict(
+                    sea_query::OnConflict::columns([
+                        auto_share_channel::Column::GuildId,
+                        auto_share_channel::Column::ChannelId,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .do_nothing()
+                .exec(conn)
+                .await?;
+            }
+            (AutoShareScope::Channel(channel_id), false) => {
+                auto_share_channel::Entity::delete_by_id((guild_id, snowflake_to_db(channel_id)))
+                    .exec(conn)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -20,6 +20,7 @@ pub struct Model {
     pub kind: Option<String>,
     pub artist: Option<String>,
     pub title: Option<String>,
+    pub provider_id: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -1,6 +1,12 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.17
 
+pub mod auto_share_channel;
+pub mod auto_share_guild;
 pub mod command_usage;
 pub mod discord_guild;
 pub mod discord_user;
+pub mod guild_settings;
+pub mod odesli_response;
+pub mod odesli_response_alias;
 pub mod sea_orm_active_enums;
+pub mod user_preferences;
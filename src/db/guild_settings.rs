@@ -0,0 +1,133 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use sea_orm::{DbErr, EntityTrait, Set, sea_query};
+use tracing::trace;
+use twilight_model::id::Id;
+use twilight_model::id::marker::GuildMarker;
+
+use crate::clients::odesli::Platform;
+use crate::db::Database;
+use crate::db::entity::guild_settings;
+use crate::db::util::snowflake_to_db;
+
+/// The per-guild configuration of the shared cards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuildSettings {
+    /// Platforms that are never linked in this guild
+    pub disabled_platforms: Vec<Platform>,
+    /// Platforms listed before all others, in this order, the rest is sorted alphabetically
+    pub platform_order: Vec<Platform>,
+    /// Whether to show the embedded player select menu
+    pub show_player: bool,
+    /// Whether replies to commands are only visible to the user who ran them
+    pub ephemeral_replies: bool,
+}
+
+impl Default for GuildSettings {
+    fn default() -> Self {
+        Self {
+            disabled_platforms: Vec::new(),
+            platform_order: Vec::new(),
+            show_player: true,
+            ephemeral_replies: false,
+        }
+    }
+}
+
+impl GuildSettings {
+    pub fn is_platform_enabled(&self, platform: &Platform) -> bool {
+        platform.is_enabled() && !self.disabled_platforms.contains(platform)
+    }
+
+    /// The key to sort platforms by, ordered platforms first followed by all others by name
+    pub fn platform_sort_key(&self, platform: &Platform) -> (usize, String) {
+        let position = self
+            .platform_order
+            .iter()
+            .position(|p| p == platform)
+            .unwrap_or(usize::MAX);
+
+        (position, platform.to_string().to_lowercase())
+    }
+
+    fn from_model(model: guild_settings::Model) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            disabled_platforms: serde_json::from_value(model.disabled_platforms)?,
+            platform_order: serde_json::from_value(model.platform_order)?,
+            show_player: model.show_player,
+            ephemeral_replies: model.ephemeral_replies,
+        })
+    }
+
+    fn into_active_model(
+        self,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<guild_settings::ActiveModel, serde_json::Error> {
+        Ok(guild_settings::ActiveModel {
+            guild_id: Set(snowflake_to_db(guild_id)),
+            disabled_platforms: Set(serde_json::to_value(self.disabled_platforms)?),
+            platform_order: Set(serde_json::to_value(self.platform_order)?),
+            show_player: Set(self.show_player),
+            ephemeral_replies: Set(self.ephemeral_replies),
+        })
+    }
+}
+
+impl Database {
+    /// Loads the settings of the guild, the defaults are returned if none were saved yet.
+    ///
+    /// Fails if the settings can't be loaded, so the defaults never replace the saved settings.
+    pub async fn load_guild_settings(
+        &self,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<GuildSettings, DbErr> {
+        let Some(conn) = &self.connection else {
+            trace!("Db Url not provided, skipping loading guild settings");
+            return Ok(GuildSettings::default());
+        };
+
+        let Some(model) = guild_settings::Entity::find_by_id(snowflake_to_db(guild_id))
+            .one(conn)
+            .await?
+        else {
+            return Ok(GuildSettings::default());
+        };
+
+        GuildSettings::from_model(model)
+            .map_err(|e| DbErr::Custom(format!("Failed to deserialize guild settings: {}", e)))
+    }
+
+    pub async fn save_guild_settings(
+        &self,
+        guild_id: Id<GuildMarker>,
+        settings: GuildSettings,
+    ) -> Result<(), DbErr> {
+        let Some(conn) = &self.connection else {
+            trace!("Db Url not provided, guild settings are only kept in memory");
+            return Ok(());
+        };
+
+        let model = settings
+            .into_active_model(guild_id)
+            .map_err(|e| DbErr::Custom(format!("Failed to serialize guild settings: {}", e)))?;
+
+        guild_settings::Entity::insert(model)
+            .on_conflict(
+                sea_query::OnConflict::column(guild_settings::Column::GuildId)
+                    .update_columns([
+                        guild_settings::Column::DisabledPlatforms,
+                        guild_settings::Column::PlatformOrder,
+                        guild_settings::Column::ShowPlayer,
+                        guild_settings::Column::EphemeralReplies,
+                    ])
+                    .to_owned(),
+            )
+            .exec(conn)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -12,13 +12,23 @@ use tracing::{debug, trace, warn};
 use crate::args::Args;
 use crate::util::error::expect_err;
 
+mod auto_share;
 mod entity;
 mod guild_meta;
+mod guild_settings;
+mod odesli_cache;
+mod recent_shares;
+mod stats;
 mod usage_data;
 mod user_meta;
+mod user_preferences;
 mod util;
 
+pub use auto_share::{AutoShareConfig, AutoShareScope};
 pub use guild_meta::GuildMetadata;
+pub use guild_settings::GuildSettings;
+pub use odesli_cache::PersistedOdesliResponse;
+pub use stats::{StatsEntry, StatsKind, StatsScope, StatsSubject};
 pub use usage_data::UsageData;
 pub use user_meta::UserMetadata;
 
@@ -0,0 +1,198 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::time::Duration;
+
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, Set, TransactionTrait,
+    sea_query,
+};
+use tracing::{debug, trace, warn};
+
+use crate::clients::odesli::OdesliResponse;
+use crate::db::Database;
+use crate::db::entity::{odesli_response, odesli_response_alias};
+
+/// An Odesli response together with the keys of all provider IDs it can be looked up by.
+#[derive(Debug)]
+pub struct PersistedOdesliResponse {
+    pub response: OdesliResponse,
+    pub provider_ids: Vec<String>,
+}
+
+fn expiry_cutoff(max_age: Duration) -> chrono::DateTime<chrono::FixedOffset> {
+    let max_age = chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::MAX);
+    (chrono::Utc::now() - max_age).fixed_offset()
+}
+
+impl Database {
+    /// Loads a persisted Odesli response by its provider ID key, ignoring entries older than
+    /// `max_age`.
+    pub async fn load_odesli_response(
+        &self,
+        provider_id: &str,
+        max_age: Duration,
+    ) -> Option<OdesliResponse> {
+        let Some(conn) = &self.connection else {
+            trace!("Db Url not provided, skipping persistent odesli cache lookup");
+            return None;
+        };
+
+        let result = odesli_response::Entity::find()
+            .inner_join(odesli_response_alias::Entity)
+            .filter(odesli_response_alias::Column::ProviderId.eq(provider_id))
+            .filter(odesli_response::Column::FetchedAt.gte(expiry_cutoff(max_age)))
+            .one(conn)
+            .await;
+
+        let model = match result {
+            Ok(Some(model)) => model,
+            Ok(None) => return None,
+            Err(e) => {
+                warn!("Failed to load odesli response from the database: {}", e);
+                return None;
+            }
+        };
+
+        match serde_json::from_value(model.response) {
+            Ok(response) => Some(response),
+            Err(e) => {
+                warn!(
+                    entity_unique_id = model.entity_unique_id,
+                    "Failed to deserialize persisted odesli response: {}", e
+                );
+                None
+            }
+        }
+    }
+
+    pub fn spawn_save_odesli_response(&self, data: PersistedOdesliResponse) {
+        if self.connection.is_none() {
+            trace!("Db Url not provided, skipping saving odesli response");
+            return;
+        }
+
+        let db_clone = self.clone();
+        tokio::spawn(async move {
+            db_clone.save_odesli_response(data).await;
+        });
+    }
+
+    pub async fn save_odesli_response(&self, data: PersistedOdesliResponse) {
+        let Some(conn) = &self.connection else {
+            trace!("Db Url not provided, skipping saving odesli response");
+            return;
+        };
+
+        match save_odesli_response_inner(conn, data).await {
+            Ok(()) => debug!("Successfully saved odesli response to the database"),
+            Err(e) => warn!("Failed to save odesli response to the database: {}", e),
+        }
+    }
+
+    /// Deletes the persisted Odesli response with the provider ID key, including its aliases.
+    /// Returns whether a response was deleted.
+    pub async fn delete_odesli_response(&self, provider_id: &str) -> bool {
+        let Some(conn) = &self.connection else {
+            trace!("Db Url not provided, skipping deleting odesli response");
+            return false;
+        };
+
+        let result = async {
+            let Some(alias) = odesli_response_alias::Entity::find_by_id(provider_id)
+                .one(conn)
+                .await?
+            else {
+                return Ok(false);
+            };
+
+            let res = odesli_response::Entity::delete_by_id(alias.entity_unique_id)
+                .exec(conn)
+                .await?;
+            Ok::<_, DbErr>(res.rows_affected > 0)
+        }
+        .await;
+
+        match result {
+            Ok(deleted) => deleted,
+            Err(e) => {
+                warn!("Failed to delete odesli response from the database: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Deletes all persisted Odesli responses older than `max_age`, including their aliases.
+    pub async fn clear_expired_odesli_responses(&self, max_age: Duration) {
+        let Some(conn) = &self.connection else {
+            trace!("Db Url not provided, skipping clearing expired odesli responses");
+            return;
+        };
+
+        let result = odesli_response::Entity::delete_many()
+            .filter(odesli_response::Column::FetchedAt.lt(expiry_cutoff(max_age)))
+            .exec(conn)
+            .await;
+
+        match result {
+            Ok(res) => debug!(
+                "Removed {} expired odesli responses from the database",
+                res.rows_affected
+            ),
+            Err(e) => warn!(
+                "Failed to remove expired odesli responses from the database: {}",
+                e
+            ),
+        }
+    }
+}
+
+async fn save_odesli_response_inner(
+    conn: &DatabaseConnection,
+    data: PersistedOdesliResponse,
+) -> Result<(), DbErr> {
+    let entity_unique_id = data.response.entity_unique_id.clone();
+    let response = serde_json::to_value(&data.response)
+        .map_err(|e| DbErr::Custom(format!("Failed to serialize odesli response: {}", e)))?;
+
+    let txn = conn.begin().await?;
+
+    odesli_response::Entity::insert(odesli_response::ActiveModel {
+        entity_unique_id: Set(entity_unique_id.clone()),
+        response: Set(response),
+        fetched_at: Set(chrono::Utc::now().fixed_offset()),
+    })
+    .on_conflict(
+        sea_query::OnConflict::column(odesli_response::Column::EntityUniqueId)
+            .update_columns([
+                odesli_response::Column::Response,
+                odesli_response::Column::FetchedAt,
+            ])
+            .to_owned(),
+    )
+    .exec(&txn)
+    .await?;
+
+    if !data.provider_ids.is_empty() {
+        let aliases =
+            data.provider_ids
+                .into_iter()
+                .map(|provider_id| odesli_response_alias::ActiveModel {
+                    provider_id: Set(provider_id),
+                    entity_unique_id: Set(entity_unique_id.clone()),
+                });
+
+        odesli_response_alias::Entity::insert_many(aliases)
+            .on_conflict(
+                sea_query::OnConflict::column(odesli_response_alias::Column::ProviderId)
+                    .update_column(odesli_response_alias::Column::EntityUniqueId)
+                    .to_owned(),
+            )
+            .exec(&txn)
+            .await?;
+    }
+
+    txn.commit().await
+}
@@ -0,0 +1,160 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::collections::HashSet;
+
+use sea_orm::sea_query::extension::postgres::PgExpr;
+use sea_orm::sea_query::{Expr, LikeExpr};
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use tracing::{trace, warn};
+use twilight_model::id::Id;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+
+use crate::clients::odesli::provider_id::ProviderId;
+use crate::db::Database;
+use crate::db::entity::command_usage;
+use crate::db::util::snowflake_to_db;
+
+/// A link that was shared before, along with the metadata Odesli returned for it.
+#[derive(Debug, Clone)]
+pub struct RecentShare {
+    pub original_url: String,
+    pub song_link_url: String,
+    pub kind: Option<String>,
+    pub title: String,
+    pub artist: Option<String>,
+}
+
+impl RecentShare {
+    /// A short human-readable description of the share
+    pub fn label(&self) -> String {
+        match &self.artist {
+            Some(artist) => format!("{} \u{2013} {}", self.title, artist),
+            None => self.title.clone(),
+        }
+    }
+}
+
+impl Database {
+    /// Loads the most recent distinct links shared by the user or in the guild, optionally only
+    /// those whose title, artist or link contains the filter.
+    pub async fn load_recent_shares(
+        &self,
+        user_id: Id<UserMarker>,
+        guild_id: Option<Id<GuildMarker>>,
+        filter: &str,
+        limit: usize,
+    ) -> Vec<RecentShare> {
+        let Some(conn) = &self.connection else {
+            trace!("Db Url not provided, skipping loading recent shares");
+            return Vec::new();
+        };
+
+        let mut scope =
+            Condition::any().add(command_usage::Column::UserId.eq(snowflake_to_db(user_id)));
+        if let Some(guild_id) = guild_id {
+            scope = scope.add(command_usage::Column::GuildId.eq(snowflake_to_db(guild_id)));
+        }
+
+        let mut condition = Condition::all()
+            .add(scope)
+            .add(command_usage::Column::Title.is_not_null());
+
+        let filter = filter.trim();
+        if !filter.is_empty() {
+            let pattern = format!("%{}%", escape_like(filter));
+            condition = condition.add(
+                Condition::any()
+                    .add(
+                        Expr::col(command_usage::Column::Title)
+                            .ilike(LikeExpr::new(&pattern).escape('\\')),
+                    )
+                    .add(
+                        Expr::col(command_usage::Column::Artist)
+                            .ilike(LikeExpr::new(&pattern).escape('\\')),
+                    )
+                    .add(
+                        Expr::col(command_usage::Column::OriginalUrl)
+                            .ilike(LikeExpr::new(&pattern).escape('\\')),
+                    ),
+            );
+        }
+
+        // Over-fetch since the same link is usually shared more than once
+        let rows = match command_usage::Entity::find()
+            .filter(condition)
+            .order_by_desc(command_usage::Column::InteractionId)
+            .limit((limit * 4) as u64)
+            .all(conn)
+            .await
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                warn!("Failed to load recent shares from the database: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut seen = HashSet::new();
+        rows.into_iter()
+            .filter(|row| seen.insert(row.song_link_url.clone()))
+            .filter_map(|row| {
+                Some(RecentShare {
+                    kind: row.kind,
+                    title: row.title?,
+                    artist: row.artist,
+                    original_url: row.original_url,
+                    song_link_url: row.song_link_url,
+                })
+            })
+            .take(limit)
+            .collect()
+    }
+
+    /// Finds the most recent share of the entity, used when its response is no longer cached
+    ///
+    /// Only shares recorded with their provider ID are found, which excludes those from before
+    /// the landing pages were introduced.
+    pub async fn find_share(&self, provider_id: &ProviderId) -> Option<RecentShare> {
+        let Some(conn) = &self.connection else {
+            trace!("Db Url not provided, skipping looking up share");
+            return None;
+        };
+
+        let row = match command_usage::Entity::find()
+            .filter(command_usage::Column::ProviderId.eq(provider_id.to_string()))
+            .filter(command_usage::Column::Title.is_not_null())
+            .order_by_desc(command_usage::Column::InteractionId)
+            .one(conn)
+            .await
+        {
+            Ok(row) => row?,
+            Err(e) => {
+                warn!("Failed to look up share in the database: {}", e);
+                return None;
+            }
+        };
+
+        Some(RecentShare {
+            kind: row.kind,
+            title: row.title?,
+            artist: row.artist,
+            original_url: row.original_url,
+            song_link_url: row.song_link_url,
+        })
+    }
+}
+
+fn escape_like(filter: &str) -> String {
+    let mut escaped = String::with_capacity(filter.len());
+    for c in filter.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
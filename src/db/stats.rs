@@ -0,0 +1,177 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::time::Duration;
+
+use sea_orm::sea_query::{Expr, SimpleExpr};
+use sea_orm::{
+    ColumnTrait, Condition, DbErr, EntityTrait, FromQueryResult, Order, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use tracing::trace;
+use twilight_model::id::Id;
+use twilight_model::id::marker::{GuildMarker, UserMarker};
+
+use crate::db::Database;
+use crate::db::entity::command_usage;
+use crate::db::util::{snowflake_since, snowflake_to_db};
+
+/// What is ranked by the number of shares
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsKind {
+    Tracks,
+    Artists,
+    Sharers,
+}
+
+/// Whose shares are counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsScope {
+    Guild(Id<GuildMarker>),
+    User(Id<UserMarker>),
+}
+
+#[derive(Debug, Clone)]
+pub enum StatsSubject {
+    Track {
+        url: String,
+        title: Option<String>,
+        artist: Option<String>,
+    },
+    Artist(String),
+    Sharer(Id<UserMarker>),
+}
+
+#[derive(Debug, Clone)]
+pub struct StatsEntry {
+    pub subject: StatsSubject,
+    pub shares: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StatsPage {
+    pub entries: Vec<StatsEntry>,
+    pub has_next: bool,
+}
+
+#[derive(FromQueryResult)]
+struct TrackRow {
+    song_link_url: String,
+    title: Option<String>,
+    artist: Option<String>,
+    shares: i64,
+}
+
+#[derive(FromQueryResult)]
+struct ArtistRow {
+    artist: String,
+    shares: i64,
+}
+
+#[derive(FromQueryResult)]
+struct SharerRow {
+    user_id: i64,
+    shares: i64,
+}
+
+impl Database {
+    /// Loads a page of the most shared tracks, artists or most active sharers.
+    ///
+    /// Shares are filtered by the creation time encoded in the interaction ID, which lets the
+    /// query use the `(guild_id, interaction_id)` and `(user_id, interaction_id)` indexes.
+    pub async fn load_stats(
+        &self,
+        kind: StatsKind,
+        scope: StatsScope,
+        max_age: Option<Duration>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<StatsPage, DbErr> {
+        let Some(conn) = &self.connection else {
+            trace!("Db Url not provided, no stats available");
+            return Ok(StatsPage::default());
+        };
+
+        let mut condition = match scope {
+            StatsScope::Guild(guild_id) => {
+                Condition::all().add(command_usage::Column::GuildId.eq(snowflake_to_db(guild_id)))
+            }
+            StatsScope::User(user_id) => {
+                Condition::all().add(command_usage::Column::UserId.eq(snowflake_to_db(user_id)))
+            }
+        };
+        if let Some(max_age) = max_age {
+            condition =
+                condition.add(command_usage::Column::InteractionId.gte(snowflake_since(max_age)));
+        }
+
+        let shares = || -> SimpleExpr { Expr::col(command_usage::Column::InteractionId).count() };
+        // Fetch one more entry than requested to know whether there is a next page
+        let query = command_usage::Entity::find()
+            .select_only()
+            .expr_as(shares(), "shares")
+            .order_by(shares(), Order::Desc)
+            .offset(offset as u64)
+            .limit(limit as u64 + 1);
+
+        let mut entries: Vec<StatsEntry> = match kind {
+            StatsKind::Tracks => query
+                .column(command_usage::Column::SongLinkUrl)
+                .expr_as(Expr::col(command_usage::Column::Title).max(), "title")
+                .expr_as(Expr::col(command_usage::Column::Artist).max(), "artist")
+                .filter(condition)
+                .group_by(command_usage::Column::SongLinkUrl)
+                .order_by_asc(command_usage::Column::SongLinkUrl)
+                .into_model::<TrackRow>()
+                .all(conn)
+                .await?
+                .into_iter()
+                .map(|row| StatsEntry {
+                    subject: StatsSubject::Track {
+                        url: row.song_link_url,
+                        title: row.title,
+                        artist: row.artist,
+                    },
+                    shares: row.shares,
+                })
+                .collect(),
+            StatsKind::Artists => query
+                .column(command_usage::Column::Artist)
+                .filter(condition.add(command_usage::Column::Artist.is_not_null()))
+                .group_by(command_usage::Column::Artist)
+                .order_by_asc(command_usage::Column::Artist)
+                .into_model::<ArtistRow>()
+                .all(conn)
+                .await?
+                .into_iter()
+                .map(|row| StatsEntry {
+                    subject: StatsSubject::Artist(row.artist),
+                    shares: row.shares,
+                })
+                .collect(),
+            StatsKind::Sharers => query
+                .column(command_usage::Column::UserId)
+                .filter(condition.add(command_usage::Column::UserId.is_not_null()))
+                .group_by(command_usage::Column::UserId)
+                .order_by_asc(command_usage::Column::UserId)
+                .into_model::<SharerRow>()
+                .all(conn)
+                .await?
+                .into_iter()
+                .filter_map(|row| {
+                    Some(StatsEntry {
+                        subject: StatsSubject::Sharer(Id::new_checked(row.user_id as u64)?),
+                        shares: row.shares,
+                    })
+                })
+                .collect(),
+        };
+
+        let has_next = entries.len() > limit;
+        entries.truncate(limit);
+
+        Ok(StatsPage { entries, has_next })
+    }
+}
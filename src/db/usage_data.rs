@@ -8,7 +8,7 @@ use twilight_model::application::interaction::Interaction;
 use twilight_model::id::Id;
 use twilight_model::id::marker::{ChannelMarker, GuildMarker, InteractionMarker, UserMarker};
 
-use crate::clients::odesli::EntityData;
+use crate::clients::odesli::{EntityData, OdesliClientResponse};
 use crate::db::DbSavable;
 use crate::db::entity::command_usage;
 use crate::db::entity::sea_orm_active_enums::CommandSource as DbCommandSource;
@@ -33,6 +33,8 @@ pub struct UsageData {
     pub kind: Option<String>,
     pub artist: Option<String>,
     pub title: Option<String>,
+    /// The key of the landing page of the entity
+    pub provider_id: Option<String>,
 }
 
 impl CommandSource {
@@ -49,16 +51,14 @@ impl UsageData {
     pub fn from_share_command(
         inter: &Interaction,
         original_url: impl Into<String>,
-        song_link_url: impl Into<String>,
+        response: &OdesliClientResponse,
         entity_data: &EntityData,
-        is_data_cached: bool,
     ) -> Self {
         Self::from_data_and_source(
             inter,
             original_url,
-            song_link_url,
+            response,
             entity_data,
-            is_data_cached,
             CommandSource::ShareCommand,
         )
     }
@@ -67,16 +67,14 @@ impl UsageData {
     pub fn from_find_links_command(
         inter: &Interaction,
         original_url: impl Into<String>,
-        song_link_url: impl Into<String>,
+        response: &OdesliClientResponse,
         entity_data: &EntityData,
-        is_data_cached: bool,
     ) -> Self {
         Self::from_data_and_source(
             inter,
             original_url,
-            song_link_url,
+            response,
             entity_data,
-            is_data_cached,
             CommandSource::FindLinksCommand,
         )
     }
@@ -85,9 +83,8 @@ impl UsageData {
     fn from_data_and_source(
         inter: &Interaction,
         original_url: impl Into<String>,
-        song_link_url: impl Into<String>,
+        response: &OdesliClientResponse,
         entity_data: &EntityData,
-        is_data_cached: bool,
         source: CommandSource,
     ) -> Self {
         Self {
@@ -97,11 +94,12 @@ impl UsageData {
             guild_id: inter.guild_id,
             channel_id: inter.channel.as_ref().map(|c| c.id),
             user_id: inter.author_id(),
-            song_link_url: song_link_url.into(),
-            data_cached: is_data_cached,
+            song_link_url: response.page_url.clone(),
+            data_cached: response.is_cached,
             kind: entity_data.kind.clone(),
             artist: entity_data.artist_name.clone(),
             title: entity_data.title.clone(),
+            provider_id: response.provider_id().map(|id| id.to_string()),
         }
     }
 }
@@ -123,6 +121,7 @@ impl DbSavable for UsageData {
             kind: Set(self.kind),
             artist: Set(self.artist),
             title: Set(self.title),
+            provider_id: Set(self.provider_id),
         }
     }
 }
@@ -0,0 +1,72 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use sea_orm::{DbErr, EntityTrait, Set, sea_query};
+use tracing::trace;
+use twilight_model::id::Id;
+use twilight_model::id::marker::UserMarker;
+
+use crate::clients::odesli::Platform;
+use crate::db::Database;
+use crate::db::entity::user_preferences;
+use crate::db::util::snowflake_to_db;
+
+impl Database {
+    /// Loads the preferred platform of the user, `None` if they didn't set one.
+    pub async fn load_preferred_platform(
+        &self,
+        user_id: Id<UserMarker>,
+    ) -> Result<Option<Platform>, DbErr> {
+        let Some(conn) = &self.connection else {
+            trace!("Db Url not provided, skipping loading user preferences");
+            return Ok(None);
+        };
+
+        let Some(model) = user_preferences::Entity::find_by_id(snowflake_to_db(user_id))
+            .one(conn)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        // Platforms are stored by their serialized name, so they can be deserialized as a string
+        serde_json::from_value(serde_json::Value::String(model.preferred_platform))
+            .map(Some)
+            .map_err(|e| DbErr::Custom(format!("Failed to deserialize preferred platform: {}", e)))
+    }
+
+    pub async fn save_preferred_platform(
+        &self,
+        user_id: Id<UserMarker>,
+        platform: &Platform,
+    ) -> Result<(), DbErr> {
+        let Some(conn) = &self.connection else {
+            trace!("Db Url not provided, user preferences are only kept in memory");
+            return Ok(());
+        };
+
+        let serde_json::Value::String(platform) = serde_json::to_value(platform)
+            .map_err(|e| DbErr::Custom(format!("Failed to serialize platform: {}", e)))?
+        else {
+            return Err(DbErr::Custom(
+                "Platform did not serialize to a string".into(),
+            ));
+        };
+
+        user_preferences::Entity::insert(user_preferences::ActiveModel {
+            user_id: Set(snowflake_to_db(user_id)),
+            preferred_platform: Set(platform),
+        })
+        .on_conflict(
+            sea_query::OnConflict::column(user_preferences::Column::UserId)
+                .update_column(user_preferences::Column::PreferredPlatform)
+                .to_owned(),
+        )
+        .exec(conn)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -3,8 +3,23 @@
  * All Rights Reserved
  */
 
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use twilight_model::id::Id;
 
+use crate::constants::DISCORD_EPOCH;
+
 pub(super) fn snowflake_to_db<T>(id: Id<T>) -> i64 {
     id.get() as i64
 }
+
+/// The smallest snowflake that could have been created within the given duration from now
+pub(super) fn snowflake_since(max_age: Duration) -> i64 {
+    let since = SystemTime::now()
+        .checked_sub(max_age)
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|time| time.as_millis() as u64)
+        .unwrap_or_default();
+
+    (since.saturating_sub(DISCORD_EPOCH) << 22) as i64
+}
@@ -30,12 +30,13 @@ impl EventHandler {
 
         self.persist_guild_metadata(&event);
         self.persist_user_metadata(&event);
+        self.interactions_handler.track_guild_locale(&event);
 
         let handler = self.interactions_handler.clone();
         tokio::spawn(async move {
-            #[allow(clippy::single_match)]
             match event {
                 Event::InteractionCreate(event) => handler.handle(event.0).await,
+                Event::MessageCreate(event) => handler.handle_message(event.0).await,
                 _ => {}
             }
         });
@@ -0,0 +1,50 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
+use twilight_model::guild::Permissions;
+
+localisations! {
+    desc auto_share_desc => "commands.autoshare.description",
+    desc enabled_desc => "commands.autoshare.enabled",
+    desc scope_desc => "commands.autoshare.scope",
+    name channel_name => "commands.autoshare.scope_channel",
+    name server_name => "commands.autoshare.scope_server",
+}
+
+fn auto_share_permissions() -> Permissions {
+    Permissions::MANAGE_GUILD
+}
+
+#[derive(Debug, Clone, Copy, CommandOption, CreateOption)]
+pub enum AutoShareScopeOption {
+    #[option(
+        name = "This channel",
+        name_localizations = "channel_name",
+        value = "channel"
+    )]
+    Channel,
+    #[option(
+        name = "Whole server",
+        name_localizations = "server_name",
+        value = "server"
+    )]
+    Server,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "autoshare",
+    desc_localizations = "auto_share_desc",
+    default_permissions = "auto_share_permissions",
+    integration_types = "guild_install",
+    contexts = "guild"
+)]
+pub struct AutoShareCommand {
+    #[command(desc_localizations = "enabled_desc")]
+    pub enabled: bool,
+    #[command(desc_localizations = "scope_desc")]
+    pub scope: Option<AutoShareScopeOption>,
+}
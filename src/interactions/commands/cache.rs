@@ -0,0 +1,61 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::guild::Permissions;
+
+/// The cache is shared by all guilds, so only administrators should see the command
+fn cache_permissions() -> Permissions {
+    Permissions::ADMINISTRATOR
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "cache",
+    default_permissions = "cache_permissions",
+    integration_types = "guild_install",
+    contexts = "guild"
+)]
+/// Inspect and invalidate the Odesli cache
+pub enum CacheCommand {
+    #[command(name = "stats")]
+    Stats(CacheStatsCommand),
+    #[command(name = "lookup")]
+    Lookup(CacheLookupCommand),
+    #[command(name = "evict")]
+    Evict(CacheEvictCommand),
+    #[command(name = "clear_expired")]
+    ClearExpired(CacheClearExpiredCommand),
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "stats")]
+/// Show statistics about the in-memory cache
+pub struct CacheStatsCommand;
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "lookup")]
+/// Look up a cached response
+pub struct CacheLookupCommand {
+    /// Link or provider ID (e.g. spotify:track:<id>)
+    pub key: String,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "evict")]
+/// Remove a response from the in-memory and persistent caches
+pub struct CacheEvictCommand {
+    /// Link or provider ID (e.g. spotify:track:<id>)
+    pub key: String,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "clear_expired")]
+/// Remove all responses not accessed recently from the in-memory cache
+pub struct CacheClearExpiredCommand {
+    /// Maximum age in minutes
+    #[command(min_value = 0)]
+    pub max_age: i64,
+}
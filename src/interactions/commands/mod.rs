@@ -10,15 +10,49 @@ use twilight_interactions::command::CreateCommand;
 use twilight_model::id::Id;
 
 use crate::interactions::InteractionsHandler;
+use crate::interactions::commands::auto_share::AutoShareCommand;
+use crate::interactions::commands::cache::CacheCommand;
 use crate::interactions::commands::find_links::FindLinksCommand;
+use crate::interactions::commands::preferences::PreferencesCommand;
+use crate::interactions::commands::search::SearchCommand;
+use crate::interactions::commands::settings::SettingsCommand;
 use crate::interactions::commands::share::ShareCommand;
+use crate::interactions::commands::stats::StatsCommand;
 use crate::interactions::commands::test_colour_consts::TestColorConstsCommand;
 use crate::util::EmptyResult;
 use crate::util::error::expect_err;
 use crate::util::message_command::MessageCommand;
 
+/// Defines the functions returning the localisations of a command, their keys are collected in
+/// `KEYS`, as every shipped bundle has to define them
+macro_rules! localisations {
+    ($($kind:ident $name:ident => $key:literal),* $(,)?) => {
+        /// The localisation keys used by the command
+        pub const KEYS: &[&str] = &[$($key),*];
+
+        $( localisations!(@fn $kind $name $key); )*
+    };
+    (@fn desc $name:ident $key:literal) => {
+        fn $name() -> twilight_interactions::command::DescLocalizations {
+            crate::localisation::desc_localizations($key)
+        }
+    };
+    (@fn name $name:ident $key:literal) => {
+        fn $name() -> twilight_interactions::command::NameLocalizations {
+            crate::localisation::name_localizations($key)
+        }
+    };
+}
+
+pub mod auto_share;
+pub mod cache;
 pub mod find_links;
+pub mod platform;
+pub mod preferences;
+pub mod search;
+pub mod settings;
 pub mod share;
+pub mod stats;
 pub mod test_colour_consts;
 
 impl InteractionsHandler {
@@ -43,8 +77,14 @@ impl InteractionsHandler {
                     Id::new(*debug_server),
                     &[
                         ShareCommand::create_command().into(),
+                        SearchCommand::create_command().into(),
                         FindLinksCommand::command(),
+                        AutoShareCommand::create_command().into(),
+                        SettingsCommand::create_command().into(),
+                        PreferencesCommand::create_command().into(),
+                        StatsCommand::create_command().into(),
                         TestColorConstsCommand::create_command().into(),
+                        CacheCommand::create_command().into(),
                     ],
                 )
                 .await
@@ -60,7 +100,12 @@ impl InteractionsHandler {
             .interaction_client()
             .set_global_commands(&[
                 ShareCommand::create_command().into(),
+                SearchCommand::create_command().into(),
                 FindLinksCommand::command(),
+                AutoShareCommand::create_command().into(),
+                SettingsCommand::create_command().into(),
+                PreferencesCommand::create_command().into(),
+                StatsCommand::create_command().into(),
             ])
             .await
             .map_err(expect_err!("Failed to Synchronize Commands"))?;
@@ -70,7 +115,10 @@ impl InteractionsHandler {
                 .interaction_client()
                 .set_guild_commands(
                     Id::new(*debug_server),
-                    &[TestColorConstsCommand::create_command().into()],
+                    &[
+                        TestColorConstsCommand::create_command().into(),
+                        CacheCommand::create_command().into(),
+                    ],
                 )
                 .await
                 .map_err(expect_err!("Failed to Synchronize Commands"))?;
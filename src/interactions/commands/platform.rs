@@ -0,0 +1,77 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use twilight_interactions::command::{CommandOption, CreateOption};
+
+use crate::clients::odesli::Platform;
+
+/// The platforms users can choose from, Discord limits the choices to 25
+#[derive(Debug, Clone, Copy, CommandOption, CreateOption)]
+pub enum PlatformOption {
+    #[option(name = "Spotify", value = "spotify")]
+    Spotify,
+    #[option(name = "iTunes", value = "itunes")]
+    ITunes,
+    #[option(name = "Apple Music", value = "appleMusic")]
+    AppleMusic,
+    #[option(name = "YouTube", value = "youtube")]
+    YouTube,
+    #[option(name = "YouTube Music", value = "youtubeMusic")]
+    YouTubeMusic,
+    #[option(name = "Google", value = "google")]
+    Google,
+    #[option(name = "Google Store", value = "googleStore")]
+    GoogleStore,
+    #[option(name = "Pandora", value = "pandora")]
+    Pandora,
+    #[option(name = "Deezer", value = "deezer")]
+    Deezer,
+    #[option(name = "Tidal", value = "tidal")]
+    Tidal,
+    #[option(name = "Amazon Music", value = "amazonMusic")]
+    AmazonMusic,
+    #[option(name = "Soundcloud", value = "soundcloud")]
+    Soundcloud,
+    #[option(name = "Napster", value = "napster")]
+    Napster,
+    #[option(name = "Spinrilla", value = "spinrilla")]
+    Spinrilla,
+    #[option(name = "Audius", value = "audius")]
+    Audius,
+    #[option(name = "Audiomack", value = "audiomack")]
+    Audiomack,
+    #[option(name = "Anghami", value = "anghami")]
+    Anghami,
+    #[option(name = "Yandex", value = "yandex")]
+    Yandex,
+    #[option(name = "Boomplay", value = "boomplay")]
+    BoomPlay,
+}
+
+impl From<PlatformOption> for Platform {
+    fn from(option: PlatformOption) -> Self {
+        match option {
+            PlatformOption::Spotify => Platform::Spotify,
+            PlatformOption::ITunes => Platform::iTunes,
+            PlatformOption::AppleMusic => Platform::AppleMusic,
+            PlatformOption::YouTube => Platform::YouTube,
+            PlatformOption::YouTubeMusic => Platform::YouTubeMusic,
+            PlatformOption::Google => Platform::Google,
+            PlatformOption::GoogleStore => Platform::GoogleStore,
+            PlatformOption::Pandora => Platform::Pandora,
+            PlatformOption::Deezer => Platform::Deezer,
+            PlatformOption::Tidal => Platform::Tidal,
+            PlatformOption::AmazonMusic => Platform::AmazonMusic,
+            PlatformOption::Soundcloud => Platform::Soundcloud,
+            PlatformOption::Napster => Platform::Napster,
+            PlatformOption::Spinrilla => Platform::Spinrilla,
+            PlatformOption::Audius => Platform::Audius,
+            PlatformOption::Audiomack => Platform::Audiomack,
+            PlatformOption::Anghami => Platform::Anghami,
+            PlatformOption::Yandex => Platform::Yandex,
+            PlatformOption::BoomPlay => Platform::BoomPlay,
+        }
+    }
+}
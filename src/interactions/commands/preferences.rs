@@ -0,0 +1,25 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::interactions::commands::platform::PlatformOption;
+
+localisations! {
+    desc preferences_desc => "commands.preferences.description",
+    desc platform_desc => "commands.preferences.platform",
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "preferences",
+    desc_localizations = "preferences_desc",
+    integration_types = "guild_install user_install",
+    contexts = "guild bot_dm private_channel"
+)]
+pub struct PreferencesCommand {
+    #[command(desc_localizations = "platform_desc")]
+    pub platform: Option<PlatformOption>,
+}
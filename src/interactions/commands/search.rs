@@ -0,0 +1,38 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use twilight_interactions::command::{AutocompleteValue, CommandModel, CreateCommand};
+
+localisations! {
+    desc search_desc => "commands.search.description",
+    desc title_desc => "commands.search.title",
+    desc artist_desc => "commands.search.artist",
+    desc album_desc => "commands.search.album",
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "search",
+    desc_localizations = "search_desc",
+    integration_types = "guild_install user_install",
+    contexts = "guild bot_dm private_channel"
+)]
+pub struct SearchCommand {
+    /// Either the title typed by the user or the link of a suggestion they picked
+    #[command(desc_localizations = "title_desc", autocomplete = true)]
+    pub title: String,
+    #[command(desc_localizations = "artist_desc")]
+    pub artist: Option<String>,
+    #[command(desc_localizations = "album_desc")]
+    pub album: Option<String>,
+}
+
+#[derive(CommandModel)]
+#[command(autocomplete = true)]
+pub struct SearchCommandPartial {
+    pub title: AutocompleteValue<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+}
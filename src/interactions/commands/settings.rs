@@ -0,0 +1,89 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::guild::Permissions;
+
+use crate::interactions::commands::platform::PlatformOption;
+
+localisations! {
+    desc settings_desc => "commands.settings.description",
+    desc show_desc => "commands.settings.show",
+    desc platform_desc => "commands.settings.platform",
+    desc platform_option_desc => "commands.settings.platform_platform",
+    desc platform_enabled_desc => "commands.settings.platform_enabled",
+    desc order_desc => "commands.settings.order",
+    desc order_platforms_desc => "commands.settings.order_platforms",
+    desc player_desc => "commands.settings.player",
+    desc player_enabled_desc => "commands.settings.player_enabled",
+    desc visibility_desc => "commands.settings.visibility",
+    desc visibility_private_desc => "commands.settings.visibility_private",
+    desc reset_desc => "commands.settings.reset",
+}
+
+fn settings_permissions() -> Permissions {
+    Permissions::MANAGE_GUILD
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "settings",
+    desc_localizations = "settings_desc",
+    default_permissions = "settings_permissions",
+    integration_types = "guild_install",
+    contexts = "guild"
+)]
+pub enum SettingsCommand {
+    #[command(name = "show")]
+    Show(SettingsShowCommand),
+    #[command(name = "platform")]
+    Platform(SettingsPlatformCommand),
+    #[command(name = "order")]
+    Order(SettingsOrderCommand),
+    #[command(name = "player")]
+    Player(SettingsPlayerCommand),
+    #[command(name = "visibility")]
+    Visibility(SettingsVisibilityCommand),
+    #[command(name = "reset")]
+    Reset(SettingsResetCommand),
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "show", desc_localizations = "show_desc")]
+pub struct SettingsShowCommand;
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "platform", desc_localizations = "platform_desc")]
+pub struct SettingsPlatformCommand {
+    #[command(desc_localizations = "platform_option_desc")]
+    pub platform: PlatformOption,
+    #[command(desc_localizations = "platform_enabled_desc")]
+    pub enabled: bool,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "order", desc_localizations = "order_desc")]
+pub struct SettingsOrderCommand {
+    #[command(desc_localizations = "order_platforms_desc")]
+    pub platforms: Option<String>,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "player", desc_localizations = "player_desc")]
+pub struct SettingsPlayerCommand {
+    #[command(desc_localizations = "player_enabled_desc")]
+    pub enabled: bool,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "visibility", desc_localizations = "visibility_desc")]
+pub struct SettingsVisibilityCommand {
+    #[command(desc_localizations = "visibility_private_desc")]
+    pub private: bool,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "reset", desc_localizations = "reset_desc")]
+pub struct SettingsResetCommand;
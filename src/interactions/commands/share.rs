@@ -3,28 +3,11 @@
  * All Rights Reserved
  */
 
-use twilight_interactions::command::{CommandModel, CreateCommand, DescLocalizations};
+use twilight_interactions::command::{AutocompleteValue, CommandModel, CreateCommand};
 
-use crate::util::discord_locales::DiscordLocale;
-
-fn share_desc() -> DescLocalizations {
-    DescLocalizations::new(
-        "Share Music to all Platforms",
-        [(
-            DiscordLocale::German.to_str(),
-            "Teile Musik von für alle Plattformen",
-        )],
-    )
-}
-
-fn url_desc_localizations() -> DescLocalizations {
-    DescLocalizations::new(
-        "The Link for the Song/Album",
-        [(
-            DiscordLocale::German.to_str(),
-            "Der Link von dem Song/Album",
-        )],
-    )
+localisations! {
+    desc share_desc => "commands.share.description",
+    desc url_desc_localizations => "commands.share.url",
 }
 
 #[derive(CommandModel, CreateCommand)]
@@ -35,6 +18,12 @@ fn url_desc_localizations() -> DescLocalizations {
     contexts = "guild bot_dm private_channel"
 )]
 pub struct ShareCommand {
-    #[command(desc_localizations = "url_desc_localizations")]
+    #[command(desc_localizations = "url_desc_localizations", autocomplete = true)]
     pub url: String,
 }
+
+#[derive(CommandModel)]
+#[command(autocomplete = true)]
+pub struct ShareCommandPartial {
+    pub url: AutocompleteValue<String>,
+}
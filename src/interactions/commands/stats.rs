@@ -0,0 +1,97 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::time::Duration;
+
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
+
+localisations! {
+    desc stats_desc => "commands.stats.description",
+    desc tracks_desc => "commands.stats.tracks",
+    desc artists_desc => "commands.stats.artists",
+    desc sharers_desc => "commands.stats.sharers",
+    desc me_desc => "commands.stats.me",
+    desc period_desc => "commands.stats.period",
+    name week_name => "commands.stats.period_week",
+    name month_name => "commands.stats.period_month",
+    name year_name => "commands.stats.period_year",
+    name all_time_name => "commands.stats.period_all_time",
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CommandOption, CreateOption)]
+pub enum StatsPeriodOption {
+    #[option(name = "Past week", name_localizations = "week_name", value = "week")]
+    Week,
+    #[option(
+        name = "Past month",
+        name_localizations = "month_name",
+        value = "month"
+    )]
+    Month,
+    #[option(name = "Past year", name_localizations = "year_name", value = "year")]
+    Year,
+    #[option(name = "All time", name_localizations = "all_time_name", value = "all")]
+    AllTime,
+}
+
+impl StatsPeriodOption {
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    /// How far back shares are counted, `None` counts all of them
+    pub fn max_age(self) -> Option<Duration> {
+        match self {
+            StatsPeriodOption::Week => Some(Self::DAY * 7),
+            StatsPeriodOption::Month => Some(Self::DAY * 30),
+            StatsPeriodOption::Year => Some(Self::DAY * 365),
+            StatsPeriodOption::AllTime => None,
+        }
+    }
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "stats",
+    desc_localizations = "stats_desc",
+    integration_types = "guild_install user_install",
+    contexts = "guild bot_dm private_channel"
+)]
+pub enum StatsCommand {
+    #[command(name = "tracks")]
+    Tracks(StatsTracksCommand),
+    #[command(name = "artists")]
+    Artists(StatsArtistsCommand),
+    #[command(name = "sharers")]
+    Sharers(StatsSharersCommand),
+    #[command(name = "me")]
+    Me(StatsMeCommand),
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "tracks", desc_localizations = "tracks_desc")]
+pub struct StatsTracksCommand {
+    #[command(desc_localizations = "period_desc")]
+    pub period: Option<StatsPeriodOption>,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "artists", desc_localizations = "artists_desc")]
+pub struct StatsArtistsCommand {
+    #[command(desc_localizations = "period_desc")]
+    pub period: Option<StatsPeriodOption>,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "sharers", desc_localizations = "sharers_desc")]
+pub struct StatsSharersCommand {
+    #[command(desc_localizations = "period_desc")]
+    pub period: Option<StatsPeriodOption>,
+}
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "me", desc_localizations = "me_desc")]
+pub struct StatsMeCommand {
+    #[command(desc_localizations = "period_desc")]
+    pub period: Option<StatsPeriodOption>,
+}
@@ -0,0 +1,268 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::fmt;
+use std::fmt::Write;
+use std::future::IntoFuture;
+
+use futures_util::future::join_all;
+use tracing::{Instrument, debug, debug_span, instrument, warn};
+use twilight_model::application::interaction::Interaction;
+use twilight_model::channel::message::{Component, MessageFlags};
+use twilight_util::builder::message::{
+    ContainerBuilder, SectionBuilder, TextDisplayBuilder, ThumbnailBuilder,
+};
+use url::Url;
+
+use crate::clients::colour::RGBPixel;
+use crate::clients::odesli::provider_id::ProviderId;
+use crate::clients::odesli::{
+    AcquirePolicy, ApiErr, EntityData, OdesliClientResponse, Platform, RequestPriority,
+};
+use crate::clients::platforms::{PlatformApiErr, artist_search_url};
+use crate::db::GuildSettings;
+use crate::interactions::InteractionsHandler;
+use crate::interactions::handlers::common::unfurled_media_item_from_url;
+use crate::interactions::handlers::messages;
+use crate::interactions::utils::interaction_deadline;
+use crate::util::EmptyResult;
+use crate::util::discord_locales::DiscordLocale;
+use crate::util::error::expect_warn;
+
+/// The platforms linked on the artist card
+const ARTIST_PLATFORMS: &[Platform] = &[
+    Platform::Spotify,
+    Platform::AppleMusic,
+    Platform::YouTubeMusic,
+    Platform::Deezer,
+    Platform::Tidal,
+    Platform::AmazonMusic,
+];
+
+#[derive(Debug)]
+pub enum ArtistErr {
+    /// Looking up the top track of the artist failed
+    Platform(PlatformApiErr),
+    /// Resolving the top track with Odesli failed
+    Api(ApiErr),
+}
+
+pub struct ArtistCard {
+    /// The link the artist was shared with
+    url: Url,
+    top_track: OdesliClientResponse,
+    entity: EntityData,
+    colour: Option<RGBPixel>,
+    links: Vec<(&'static Platform, String)>,
+}
+
+impl fmt::Display for ArtistErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArtistErr::Platform(err) => write!(f, "Failed to fetch top track: {}", err),
+            ArtistErr::Api(err) => write!(f, "Failed to resolve top track: {}", err),
+        }
+    }
+}
+
+impl ArtistErr {
+    pub fn message(self, locale: DiscordLocale) -> &'static str {
+        match self {
+            ArtistErr::Platform(PlatformApiErr::NotFound) => messages::artist_not_supported(locale),
+            ArtistErr::Api(ApiErr::ClientError(err)) => {
+                messages::api_client_error_message(err, locale)
+            }
+            ArtistErr::Api(ApiErr::RateLimitDeadlineExceeded) => messages::busy(locale),
+            _ => messages::error(locale),
+        }
+    }
+}
+
+impl InteractionsHandler {
+    /// Returns the provider ID if the link is an artist we are able to build a card for
+    pub(super) fn supported_artist(&self, url: &Url) -> Option<ProviderId> {
+        ProviderId::parse_url(url)
+            .ok()
+            .filter(|id| id.is_artist() && self.platforms().supports_artist(id))
+    }
+
+    /// Resolves the artist on every platform, using the artists top track as a reference
+    #[instrument(level = "debug", skip(self, policy), fields(link = %url))]
+    pub(super) async fn artist_routine(
+        &self,
+        url: &Url,
+        artist: &ProviderId,
+        policy: AcquirePolicy,
+    ) -> Result<ArtistCard, ArtistErr> {
+        let top_track = self
+            .platforms()
+            .artist_top_track(artist)
+            .await
+            .map_err(ArtistErr::Platform)?;
+        let top_track =
+            Url::parse(&top_track).map_err(|_| ArtistErr::Platform(PlatformApiErr::NotFound))?;
+
+        debug!(top_track = %top_track, "Found top track of the artist");
+        let (top_track, entity, colour) = self
+            .data_routine(&top_track, policy)
+            .await
+            .map_err(ArtistErr::Api)?;
+
+        let artist_platform = artist.platform();
+        let links = join_all(ARTIST_PLATFORMS.iter().map(async |platform| {
+            if *platform == artist_platform {
+                return Some((platform, url.to_string()));
+            }
+
+            self.artist_link(&top_track, &entity, platform)
+                .await
+                .map(|link| (platform, link))
+        }))
+        .await;
+
+        Ok(ArtistCard {
+            url: url.clone(),
+            top_track,
+            entity,
+            colour,
+            links: links.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Looks up the artist profile via the top track on the platform, falling back to a search
+    async fn artist_link(
+        &self,
+        top_track: &OdesliClientResponse,
+        entity: &EntityData,
+        platform: &Platform,
+    ) -> Option<String> {
+        let track_id = top_track
+            .links_by_platform
+            .get(platform)
+            .and_then(|links| Url::parse(&links.url).ok())
+            .and_then(|url| ProviderId::parse_url(&url).ok());
+
+        if let Some(track_id) = track_id {
+            match self.platforms().track_artist_link(&track_id).await {
+                Ok(link) => return Some(link),
+                Err(PlatformApiErr::Unsupported) => (),
+                Err(e) => debug!(%platform, "Failed to look up artist profile: {}", e),
+            }
+        }
+
+        let artist_name = entity.artist_name.as_deref()?;
+        artist_search_url(platform, artist_name).map(String::from)
+    }
+}
+
+fn build_components(
+    card: ArtistCard,
+    locale: DiscordLocale,
+    settings: &GuildSettings,
+) -> [Component; 1] {
+    let mut container = ContainerBuilder::new();
+    if let Some(colour) = card.colour {
+        container = container.accent_color(Some(colour.to_hex()));
+    }
+
+    let EntityData {
+        title,
+        artist_name,
+        thumbnail_url,
+        ..
+    } = card.entity;
+
+    let mut details = String::new();
+    if let Some(artist_name) = artist_name {
+        writeln!(details, "## [{}]({})", artist_name, card.url)
+            .expect("Writing to string should not fail");
+    }
+
+    let links = card
+        .links
+        .iter()
+        .filter(|(platform, _)| settings.is_platform_enabled(platform));
+    for (i, (platform, link)) in links.enumerate() {
+        if i > 0 {
+            details.push_str("  \u{2022}  "); // 2 tabs with a bullet in the middle
+        }
+
+        write!(details, "[{}]({})", platform, link).expect("Writing to string should not fail");
+    }
+
+    if let Some(title) = title {
+        write!(
+            details,
+            "\n-# {}: [{}]({})",
+            messages::top_track(locale),
+            title,
+            card.top_track.page_url
+        )
+        .expect("Writing to string should not fail");
+    }
+
+    if let Some(thumbnail_url) = thumbnail_url {
+        let thumbnail = ThumbnailBuilder::new(unfurled_media_item_from_url(thumbnail_url)).build();
+
+        container = container.component(
+            SectionBuilder::new(thumbnail)
+                .component(TextDisplayBuilder::new(details).build())
+                .build(),
+        );
+    } else {
+        container = container.component(TextDisplayBuilder::new(details).build());
+    }
+
+    container =
+        container.component(TextDisplayBuilder::new(messages::powered_by_odesli(locale)).build());
+
+    [container.build().into()]
+}
+
+#[instrument(level = "debug", skip(this, inter))]
+pub(super) async fn handle_artist(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    url: Url,
+    artist: ProviderId,
+) -> EmptyResult<()> {
+    debug!("User shared an artist, deferring Response");
+    let settings = this.guild_settings(inter.guild_id).await;
+    let defer_future = this.defer(&inter, settings.ephemeral_replies);
+
+    let policy =
+        AcquirePolicy::wait_until(RequestPriority::Interactive, interaction_deadline(&inter));
+    let card = match this.artist_routine(&url, &artist, policy).await {
+        Ok(card) => card,
+        Err(e) => {
+            warn!("Failed to build artist card, informing user: {}", e);
+            defer_future
+                .await
+                .map_err(expect_warn!("Failed to join the defer future"))?;
+            this.update_defer_with_error(&inter, e.message((&inter.locale).into()))
+                .await;
+            return Err(());
+        }
+    };
+
+    let components = build_components(card, (&inter.locale).into(), &settings);
+
+    defer_future
+        .await
+        .map_err(expect_warn!("Failed to join the defer future"))?;
+
+    this.discord()
+        .interaction_client()
+        .update_response(inter.token.as_str())
+        .flags(MessageFlags::IS_COMPONENTS_V2)
+        .components(Some(&components))
+        .into_future()
+        .instrument(debug_span!("sending_response"))
+        .await
+        .map_err(expect_warn!("Failed to send the response to the user"))?;
+
+    debug!("Successfully sent Response");
+    Ok(())
+}
@@ -0,0 +1,297 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::future::IntoFuture;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use futures_util::future::join_all;
+use sea_orm::DbErr;
+use tracing::{Instrument, debug, debug_span, instrument, warn};
+use twilight_model::application::interaction::Interaction;
+use twilight_model::application::interaction::application_command::CommandData;
+use twilight_model::channel::Message;
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::gateway::event::Event;
+use twilight_model::gateway::payload::incoming::GuildCreate;
+use twilight_model::id::Id;
+use twilight_model::id::marker::{ChannelMarker, GuildMarker};
+use url::Url;
+
+use crate::clients::expander::is_short_link;
+use crate::clients::odesli::AcquirePolicy;
+use crate::db::{AutoShareConfig, AutoShareScope};
+use crate::interactions::InteractionsHandler;
+use crate::interactions::commands::auto_share::{AutoShareCommand, AutoShareScopeOption};
+use crate::interactions::handlers::common::{
+    additional_link_validation, build_components, find_urls,
+};
+use crate::interactions::handlers::messages;
+use crate::util::EmptyResult;
+use crate::util::discord_locales::DiscordLocale;
+use crate::util::error::expect_warn;
+
+/// The maximum number of links shared automatically for a single message
+const MAX_LINKS: usize = 3;
+
+/// The auto-share configuration of each guild and the cooldowns of each channel.
+#[derive(Debug)]
+pub struct AutoShareState {
+    /// Lazily loaded from the database on the first message of each guild
+    configs: DashMap<Id<GuildMarker>, AutoShareConfig>,
+    /// Only channels with auto-share enabled ever get an entry, so this is never cleaned up
+    cooldowns: DashMap<Id<ChannelMarker>, Instant>,
+    cooldown: Duration,
+    /// The preferred locale of each guild, kept up to date from the gateway events, as messages
+    /// don't carry the locale of their author
+    guild_locales: DashMap<Id<GuildMarker>, DiscordLocale>,
+}
+
+impl AutoShareState {
+    pub fn new(cooldown: Duration) -> Self {
+        Self {
+            configs: DashMap::new(),
+            cooldowns: DashMap::new(),
+            cooldown,
+            guild_locales: DashMap::new(),
+        }
+    }
+
+    /// Starts the cooldown of the channel, returns false if it is still on cooldown
+    fn try_start_cooldown(&self, channel_id: Id<ChannelMarker>) -> bool {
+        let now = Instant::now();
+        let mut cooldown_until = self.cooldowns.entry(channel_id).or_insert(now);
+        if *cooldown_until > now {
+            return false;
+        }
+
+        *cooldown_until = now + self.cooldown;
+        true
+    }
+
+    fn guild_locale(&self, guild_id: Id<GuildMarker>) -> DiscordLocale {
+        self.guild_locales
+            .get(&guild_id)
+            .map_or(DiscordLocale::EnglishUS, |locale| *locale)
+    }
+}
+
+impl InteractionsHandler {
+    pub(super) async fn handle_auto_share(&self, inter: Interaction, data: CommandData) {
+        // use an inner function to make splitting the code easier
+        let _ = handle_command(self, inter, data).await;
+    }
+
+    /// Remembers the preferred locale of the guild, auto-share replies are sent in it
+    pub fn track_guild_locale(&self, event: &Event) {
+        let locales = &self.auto_share().guild_locales;
+        match event {
+            Event::GuildCreate(create) => {
+                if let GuildCreate::Available(guild) = create.as_ref() {
+                    locales.insert(guild.id, guild.preferred_locale.as_str().into());
+                }
+            }
+            Event::GuildUpdate(update) => {
+                locales.insert(update.id, update.preferred_locale.as_str().into());
+            }
+            Event::GuildDelete(delete) => {
+                locales.remove(&delete.id);
+            }
+            _ => {}
+        }
+    }
+
+    /// Shares the links in the message if auto-share is enabled for its channel
+    pub async fn handle_message(&self, msg: Message) {
+        let _ = handle_message_inner(self, msg).await;
+    }
+
+    /// Loads the config into the cache if it isn't cached yet, a config that failed to load isn't
+    /// cached, so loading it is retried on the next message
+    async fn load_auto_share_config(&self, guild_id: Id<GuildMarker>) -> Result<(), DbErr> {
+        if self.auto_share().configs.contains_key(&guild_id) {
+            return Ok(());
+        }
+
+        let config = self.db().load_auto_share_config(guild_id).await?;
+        self.auto_share().configs.entry(guild_id).or_insert(config);
+        Ok(())
+    }
+
+    async fn auto_share_enabled(
+        &self,
+        guild_id: Id<GuildMarker>,
+        channel_id: Id<ChannelMarker>,
+    ) -> bool {
+        if let Err(e) = self.load_auto_share_config(guild_id).await {
+            warn!("Failed to load auto-share config, not sharing: {}", e);
+            return false;
+        }
+        self.auto_share()
+            .configs
+            .get(&guild_id)
+            .is_some_and(|config| config.is_enabled(channel_id))
+    }
+}
+
+#[instrument(name = "auto_share_command_handler", level = "debug", skip_all)]
+async fn handle_command(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    data: CommandData,
+) -> EmptyResult<()> {
+    debug!("Received Auto Share Command Interaction");
+
+    let command: AutoShareCommand = this.parse_command(data)?;
+    let (Some(guild_id), Some(channel)) = (inter.guild_id, inter.channel.as_ref()) else {
+        warn!("Received Auto Share Command outside of a guild");
+        return Err(());
+    };
+
+    let scope = match command.scope {
+        Some(AutoShareScopeOption::Server) => AutoShareScope::Guild,
+        Some(AutoShareScopeOption::Channel) | None => AutoShareScope::Channel(channel.id),
+    };
+    let locale: DiscordLocale = (&inter.locale).into();
+
+    if let Err(e) = this
+        .db()
+        .save_auto_share(guild_id, scope, command.enabled)
+        .await
+    {
+        warn!("Failed to save auto-share config, informing user: {}", e);
+        this.respond_with(&inter, messages::error(locale)).await;
+        return Err(());
+    }
+
+    // The cached config is updated in place, if it can't be loaded it is loaded again including
+    // this change on the next message
+    match this.load_auto_share_config(guild_id).await {
+        Ok(()) => {
+            if let Some(mut config) = this.auto_share().configs.get_mut(&guild_id) {
+                config.set(scope, command.enabled);
+            }
+        }
+        Err(e) => warn!("Failed to load auto-share config after saving it: {}", e),
+    }
+    debug!(
+        ?scope,
+        enabled = command.enabled,
+        "Updated auto-share config"
+    );
+
+    let message = match (scope, command.enabled) {
+        (AutoShareScope::Guild, true) => messages::auto_share_enabled_guild(locale),
+        (AutoShareScope::Guild, false) => messages::auto_share_disabled_guild(locale),
+        (AutoShareScope::Channel(_), true) => messages::auto_share_enabled_channel(locale),
+        (AutoShareScope::Channel(_), false) => messages::auto_share_disabled_channel(locale),
+    };
+
+    if command.enabled && !this.args().message_content_intent {
+        let message = format!("{}\n{}", message, messages::auto_share_mention_only(locale));
+        this.respond_with(&inter, &message).await;
+    } else {
+        this.respond_with(&inter, message).await;
+    }
+
+    Ok(())
+}
+
+#[instrument(
+    name = "auto_share_handler",
+    level = "debug",
+    skip_all,
+    fields(
+        msg_id = msg.id.get(),
+        channel_id = msg.channel_id.get(),
+        guild_id = msg.guild_id.map(|id| id.get())
+    )
+)]
+async fn handle_message_inner(this: &InteractionsHandler, msg: Message) -> EmptyResult<()> {
+    if msg.author.bot || msg.webhook_id.is_some() {
+        return Ok(());
+    }
+    let Some(guild_id) = msg.guild_id else {
+        return Ok(());
+    };
+
+    if !this.auto_share_enabled(guild_id, msg.channel_id).await {
+        return Ok(());
+    }
+
+    // Only check locally whether the message could contain a link to share, expanding short
+    // links requests them, which shouldn't happen for every message in a busy channel
+    let may_share = find_urls(&msg.content)
+        .any(|url| is_short_link(&url) || additional_link_validation(&url).is_ok());
+    if !may_share {
+        return Ok(());
+    }
+
+    if !this.auto_share().try_start_cooldown(msg.channel_id) {
+        debug!("Channel is on cooldown, skipping message");
+        return Ok(());
+    }
+
+    let links: Vec<Url> = this
+        .find_expanded_urls(&msg.content)
+        .await
+        .into_iter()
+        .filter(|url| additional_link_validation(url).is_ok())
+        .take(MAX_LINKS)
+        .collect();
+    if links.is_empty() {
+        return Ok(());
+    }
+
+    debug!(
+        "Found {} links in message, starting Routine for each",
+        links.len()
+    );
+    // Don't wait for the rate limiter, auto-share shouldn't use up the budget of the commands
+    let data = join_all(
+        links
+            .iter()
+            .map(|link| this.data_routine(link, AcquirePolicy::Immediate)),
+    )
+    .await;
+    let settings = this.guild_settings(Some(guild_id)).await;
+    let locale = this.auto_share().guild_locale(guild_id);
+
+    let mut components = Vec::with_capacity(links.len());
+    for (idx, result) in data.into_iter().enumerate() {
+        match result {
+            Ok((data, entity, colour)) => {
+                components.extend(build_components(
+                    &data,
+                    &this.page_url(&data),
+                    entity,
+                    colour,
+                    Some(idx as u16),
+                    locale,
+                    &settings,
+                ));
+            }
+            Err(e) => debug!("Skipping link: {}", e),
+        }
+    }
+
+    if components.is_empty() {
+        return Ok(());
+    }
+
+    this.discord()
+        .create_message(msg.channel_id)
+        .reply(msg.id)
+        .fail_if_not_exists(false)
+        .flags(MessageFlags::IS_COMPONENTS_V2)
+        .components(&components)
+        .into_future()
+        .instrument(debug_span!("sending_response"))
+        .await
+        .map_err(expect_warn!("Failed to send the auto-share reply"))?;
+
+    debug!("Successfully sent auto-share reply");
+    Ok(())
+}
@@ -0,0 +1,107 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use tracing::{debug, info, instrument};
+use twilight_model::application::interaction::Interaction;
+use twilight_model::application::interaction::application_command::CommandData;
+
+use crate::clients::odesli::provider_id::ProviderId;
+use crate::interactions::InteractionsHandler;
+use crate::interactions::commands::cache::CacheCommand;
+use crate::util::EmptyResult;
+
+impl InteractionsHandler {
+    pub(super) async fn handle_cache(&self, inter: Interaction, data: CommandData) {
+        // use an inner function to make splitting the code easier
+        let _ = handle_inner(self, inter, data).await;
+    }
+}
+
+#[instrument(name = "cache_command_handler", level = "debug", skip_all)]
+async fn handle_inner(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    data: CommandData,
+) -> EmptyResult<()> {
+    debug!("Received Cache Command Interaction");
+
+    // The command is only registered in the debug servers, but check anyway
+    let is_debug_server = inter
+        .guild_id
+        .is_some_and(|id| this.args().debug_server.contains(&id.get()));
+    if !is_debug_server {
+        debug!("Cache command used outside of a debug server, ignoring");
+        this.respond_with(&inter, "This command is only available in debug servers!")
+            .await;
+        return Ok(());
+    }
+
+    let command = this.parse_command::<CacheCommand>(data)?;
+    let message = match command {
+        CacheCommand::Stats(_) => {
+            let stats = this.odesli().cache_stats();
+            let mut msg = format!(
+                "**Keys:** {}\n**Responses:** {}\n**Size:** {} KiB / {} KiB",
+                stats.keys,
+                stats.responses,
+                stats.size / 1024,
+                stats.capacity / 1024
+            );
+            if let Some(secs) = stats.oldest_access_secs {
+                write!(msg, "\n**Oldest access:** {}s ago", secs)
+                    .expect("Writing to string should not fail");
+            }
+            msg
+        }
+        CacheCommand::Lookup(command) => match ProviderId::from_key_or_url(&command.key) {
+            Some(provider_id) => match this.odesli().inspect_cache_entry(&provider_id) {
+                Some(response) => {
+                    let data = response.get_data();
+                    format!(
+                        "`{}` is cached as `{}`\n**Title:** {}\n**Artist:** {}\n**Platforms:** {}\n**Page:** <{}>",
+                        provider_id,
+                        response.entity_unique_id,
+                        data.title.as_deref().unwrap_or("-"),
+                        data.artist_name.as_deref().unwrap_or("-"),
+                        response.links_by_platform.len(),
+                        response.page_url
+                    )
+                }
+                None => format!("`{}` is not cached", provider_id),
+            },
+            None => "The key is neither a provider ID nor a supported link!".to_string(),
+        },
+        CacheCommand::Evict(command) => match ProviderId::from_key_or_url(&command.key) {
+            Some(provider_id) => {
+                info!(%provider_id, "Evicting cache entry on command");
+                let eviction = this.odesli().evict(&provider_id).await;
+                format!(
+                    "Removed `{}` from {} keys of the in-memory cache, persistent cache: {}",
+                    provider_id,
+                    eviction.keys,
+                    if eviction.persisted {
+                        "removed"
+                    } else {
+                        "not found"
+                    }
+                )
+            }
+            None => "The key is neither a provider ID nor a supported link!".to_string(),
+        },
+        CacheCommand::ClearExpired(command) => {
+            let max_age = Duration::from_secs(command.max_age.max(0) as u64 * 60);
+            info!(?max_age, "Clearing expired cache entries on command");
+            let removed = this.odesli().clear_expired_cache_entries(max_age);
+            format!("Removed {} keys from the in-memory cache", removed)
+        }
+    };
+
+    this.respond_with(&inter, &message).await;
+
+    Ok(())
+}
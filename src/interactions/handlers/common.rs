@@ -3,8 +3,10 @@
  * All Rights Reserved
  */
 
+use futures_util::future::join_all;
 use lazy_regex::{Lazy, lazy_regex};
 use regex::Regex;
+use tokio::time::Instant;
 use tracing::{debug, instrument};
 use twilight_model::channel::message::Component;
 use twilight_model::channel::message::component::UnfurledMediaItem;
@@ -14,9 +16,16 @@ use twilight_util::builder::message::{
 use url::Url;
 
 use crate::clients::colour::RGBPixel;
-use crate::clients::odesli::{ApiErr, EntityData, OdesliClientResponse, OdesliResponse};
+use crate::clients::expander::is_short_link;
+use crate::clients::odesli::provider_id::{ProviderId, YouTubeId, canonicalize_url};
+use crate::clients::odesli::{AcquirePolicy, ApiErr, EntityData, OdesliClientResponse};
+use crate::db::GuildSettings;
 use crate::interactions::InteractionsHandler;
+use crate::interactions::handlers::messages;
+use crate::interactions::handlers::preferences::build_my_platform_button;
 use crate::interactions::handlers::show_player::build_select_menu;
+use crate::landing_page::landing_page_url;
+use crate::util::discord_locales::DiscordLocale;
 
 // language=RegExp
 pub static VALID_DOMAINS_REGEX: Lazy<Regex> = lazy_regex!(
@@ -27,9 +36,14 @@ pub static VALID_DOMAINS_REGEX: Lazy<Regex> = lazy_regex!(
         play\.anghami\.com| # Anghami
         deezer\.(?:page\.link|com)| # Deezer
         music\.apple\.com| # Apple Music & iTunes
+        audiomack\.com| # Audiomack
+        audius\.co| # Audius
+        bandcamp\.com| # Bandcamp
+        boomplay\.com| # BoomPlay
         napster\.com| # Napster
         pandora\.com| # Pandora Music
         soundcloud\.com| # Soundcloud
+        spinrilla\.com| # Spinrilla
         spotify\.com| # Spotify
         tidal\.com| # Tidal
         music\.yandex\.(?:ru|com)| # Yandex
@@ -52,7 +66,12 @@ pub enum InvalidLink {
 }
 
 pub fn additional_link_validation(link: &Url) -> Result<(), InvalidLink> {
-    if link.path().contains("/playlist") {
+    // Albums on YouTube Music are linked as playlists
+    let is_youtube_album = matches!(
+        ProviderId::parse_url(link),
+        Ok(ProviderId::YouTube(YouTubeId::Album(_)))
+    );
+    if link.path().contains("/playlist") && !is_youtube_album {
         return Err(InvalidLink::Playlist);
     }
 
@@ -60,6 +79,11 @@ pub fn additional_link_validation(link: &Url) -> Result<(), InvalidLink> {
         return Err(InvalidLink::Artist);
     }
 
+    // YouTube Music artists are linked as channels
+    if link.domain() == Some("music.youtube.com") && link.path().starts_with("/channel") {
+        return Err(InvalidLink::Artist);
+    }
+
     if link.as_str().contains("youtube.com/shorts") {
         return Err(InvalidLink::YoutubeShort);
     }
@@ -67,14 +91,74 @@ pub fn additional_link_validation(link: &Url) -> Result<(), InvalidLink> {
     Ok(())
 }
 
+/// The number of short links in a single message that are expanded
+const MAX_EXPANDED_LINKS: usize = 10;
+
+pub fn is_supported_domain(url: &Url) -> bool {
+    url.domain()
+        .is_some_and(|domain| VALID_DOMAINS_REGEX.is_match(domain))
+}
+
+/// Web links, stopping at the characters that wrap links in markdown, e.g. `<...>` or `[text](...)`
+// language=RegExp
+static URL_REGEX: Lazy<Regex> = lazy_regex!(r#"https?://[^\s<>\[\]()"'|]+"#);
+
+/// Finds all web links in the text, also the ones wrapped in markdown
+pub fn scan_urls(text: &str) -> impl Iterator<Item = Url> + '_ {
+    URL_REGEX
+        .find_iter(text)
+        .map(|m| {
+            m.as_str()
+                .trim_end_matches(['.', ',', ';', ':', '!', '?', '*', '_', '~'])
+        })
+        .filter_map(|s| Url::parse(s).ok())
+}
+
+/// Finds all links with a supported domain or of a link shortener in the message content
+pub fn find_urls(content: &str) -> impl Iterator<Item = Url> + '_ {
+    scan_urls(content).filter(|url| is_supported_domain(url) || is_short_link(url))
+}
+
 impl InteractionsHandler {
+    /// Expands the link if it is a short link, returns its canonical form if it has a supported
+    /// domain afterwards
+    pub(super) async fn expand_link(&self, url: &Url) -> Option<Url> {
+        let url = self.expander().expand(url).await;
+        is_supported_domain(&url).then(|| canonicalize_url(&url))
+    }
+
+    /// Finds all links with a supported domain in the message content, after expanding the short
+    /// links
+    pub(super) async fn find_expanded_urls(&self, content: &str) -> Vec<Url> {
+        let urls = find_urls(content)
+            .take(MAX_EXPANDED_LINKS)
+            .collect::<Vec<_>>();
+
+        join_all(urls.iter().map(|url| self.expand_link(url)))
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Resolves the link and fetches the dominant colour of its thumbnail, the policy decides
+    /// how to wait for the rate limits of the resolvers and the deadline of both requests
     #[instrument(level = "debug", skip_all, fields(link = %url))]
     pub(super) async fn data_routine(
         &self,
         url: &Url,
+        policy: AcquirePolicy,
     ) -> Result<(OdesliClientResponse, EntityData, Option<RGBPixel>), ApiErr> {
         debug!("Fetching information from API");
-        let data = self.odesli().fetch(url).await?;
+        let data = self.resolver().resolve_with_policy(url, policy).await?;
+        Ok(self.entity_routine(data, policy.deadline()).await)
+    }
+
+    async fn entity_routine(
+        &self,
+        data: OdesliClientResponse,
+        deadline: Option<Instant>,
+    ) -> (OdesliClientResponse, EntityData, Option<RGBPixel>) {
         let entity_data = data.get_data();
         debug!(
             "Got data from api: {} by {}",
@@ -86,18 +170,27 @@ impl InteractionsHandler {
             Some(url) => {
                 debug!("Album/Song has a Thumbnail, getting dominant colour");
                 self.image()
-                    .get_dominant_colour_from_url(url, Default::default())
+                    .get_dominant_colour_from_url(url, Default::default(), deadline)
                     .await
                     .ok()
             }
             None => None,
         };
 
-        Ok((data, entity_data, color))
+        (data, entity_data, color)
+    }
+
+    /// The page the title of the card links to, our own landing page if a public URL is
+    /// configured and song.link otherwise
+    pub(super) fn page_url(&self, data: &OdesliClientResponse) -> String {
+        match (&self.args().public_url, data.provider_id()) {
+            (Some(base), Some(provider_id)) => landing_page_url(base, &provider_id),
+            _ => data.page_url.clone(),
+        }
     }
 }
 
-fn unfurled_media_item_from_url(url: String) -> UnfurledMediaItem {
+pub fn unfurled_media_item_from_url(url: String) -> UnfurledMediaItem {
     UnfurledMediaItem {
         url,
         width: None,
@@ -108,10 +201,13 @@ fn unfurled_media_item_from_url(url: String) -> UnfurledMediaItem {
 }
 
 pub fn build_components(
-    data: &OdesliResponse,
+    data: &OdesliClientResponse,
+    page_url: &str,
     entity: EntityData,
     colour: Option<RGBPixel>,
     idx: Option<u16>,
+    locale: DiscordLocale,
+    settings: &GuildSettings,
 ) -> [Component; 1] {
     use std::fmt::Write;
 
@@ -131,19 +227,23 @@ pub fn build_components(
 
     let mut details = String::new();
     if let Some(title) = title {
-        writeln!(details, "## [{}]({})", title, data.page_url)
+        writeln!(details, "## [{}]({})", title, page_url)
             .expect("Writing to string should not fail");
     }
 
-    let mut links = data.links();
-    links.sort_by_key(|a| a.0.to_lowercase());
+    let mut links = data
+        .links_by_platform
+        .iter()
+        .filter(|(platform, _)| settings.is_platform_enabled(platform))
+        .collect::<Vec<_>>();
+    links.sort_by_cached_key(|(platform, _)| settings.platform_sort_key(platform));
 
     for (i, (platform, link)) in links.iter().enumerate() {
         if i > 0 {
             details.push_str("  \u{2022}  "); // 2 tabs with a bullet in the middle
         }
 
-        write!(details, "[{}]({})", platform, link).expect("Writing to string should not fail");
+        write!(details, "[{}]({})", platform, link.url).expect("Writing to string should not fail");
     }
 
     if let Some(thumbnail_url) = thumbnail_url {
@@ -164,11 +264,45 @@ pub fn build_components(
         container = container.component(TextDisplayBuilder::new(details).build());
     };
 
-    if let Some(show_platform_players) = build_select_menu(data, idx) {
+    if settings.show_player
+        && let Some(show_platform_players) = build_select_menu(data, idx, locale, settings)
+    {
         container = container.component(show_platform_players);
     }
 
-    container = container.component(TextDisplayBuilder::new("-# Powered by odesli.co").build());
+    if let Some(my_platform) = build_my_platform_button(data, idx, locale) {
+        container = container.component(my_platform);
+    }
+
+    container =
+        container.component(TextDisplayBuilder::new(messages::powered_by_odesli(locale)).build());
 
     [container.build().into()]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn supported_domains() {
+        for link in [
+            "https://audius.co/someartist/some-song",
+            "https://audiomack.com/some-artist/song/some-song",
+            "https://someartist.bandcamp.com/track/some-song",
+            "https://www.boomplay.com/songs/1234567",
+            "https://spinrilla.com/songs/12345-some-song",
+            "https://open.spotify.com/track/4cOdK2wGLETKBW3PvgPWqT",
+            "https://music.youtube.com/watch?v=dQw4w9WgXcQ",
+        ] {
+            assert!(is_supported_domain(&Url::parse(link).unwrap()), "{link}");
+        }
+
+        for link in [
+            "https://example.com/track/1",
+            "https://audius.com/someartist",
+        ] {
+            assert!(!is_supported_domain(&Url::parse(link).unwrap()), "{link}");
+        }
+    }
+}
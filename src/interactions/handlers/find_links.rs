@@ -11,25 +11,152 @@ use futures_util::future::try_join_all;
 use tracing::{Instrument, debug, debug_span, instrument, warn};
 use twilight_model::application::interaction::Interaction;
 use twilight_model::application::interaction::application_command::CommandData;
-use twilight_model::channel::message::MessageFlags;
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::channel::Message;
+use twilight_model::channel::message::component::ButtonStyle;
+use twilight_model::channel::message::{Component, MessageFlags};
+use twilight_model::guild::Permissions;
+use twilight_model::id::Id;
+use twilight_model::id::marker::{ChannelMarker, MessageMarker};
+use twilight_util::builder::message::{ActionRowBuilder, ButtonBuilder, TextDisplayBuilder};
 use url::Url;
 
-use crate::clients::odesli::ApiErr;
-use crate::db::UsageData;
+use crate::clients::colour::RGBPixel;
+use crate::clients::odesli::{
+    AcquirePolicy, ApiErr, EntityData, OdesliClientResponse, RequestPriority,
+};
+use crate::db::{GuildSettings, UsageData};
 use crate::interactions::InteractionsHandler;
-use crate::interactions::handlers::common::{
-    VALID_DOMAINS_REGEX, additional_link_validation, build_components,
+use crate::interactions::handlers::common::build_components;
+use crate::interactions::handlers::link_extraction::{
+    ExtractedLinks, MAX_PAGED_LINKS, MAX_SHARED_LINKS,
 };
 use crate::interactions::handlers::messages;
+use crate::interactions::utils::interaction_deadline;
 use crate::util::EmptyResult;
+use crate::util::discord_locales::DiscordLocale;
 use crate::util::error::expect_warn;
 use crate::util::message_command::get_message;
 
+pub const PAGE_BUTTON_ID: &str = "find_links_page";
+
+/// The number of links shown per page
+const PAGE_SIZE: usize = MAX_SHARED_LINKS;
+
+/// The permissions needed to load the message again when paging through its links
+const RELOAD_PERMISSIONS: Permissions =
+    Permissions::VIEW_CHANNEL.union(Permissions::READ_MESSAGE_HISTORY);
+
+type ResolvedLink = (Url, OdesliClientResponse, EntityData, Option<RGBPixel>);
+
+/// The message the links were found in, the page buttons load it again to find the links of the
+/// other pages, so no state has to be kept in memory
+#[derive(Debug, Clone, Copy)]
+struct SourceMessage {
+    channel_id: Id<ChannelMarker>,
+    message_id: Id<MessageMarker>,
+}
+
+fn page_custom_id(page: usize, source: SourceMessage) -> String {
+    format!(
+        "{}:{}:{}:{}",
+        PAGE_BUTTON_ID, page, source.channel_id, source.message_id
+    )
+}
+
+fn parse_page_custom_id(custom_id: &str) -> Option<(usize, SourceMessage)> {
+    let rest = custom_id.strip_prefix(PAGE_BUTTON_ID)?.strip_prefix(':')?;
+    let (page, rest) = rest.split_once(':')?;
+    let (channel_id, message_id) = rest.split_once(':')?;
+
+    Some((
+        page.parse().ok()?,
+        SourceMessage {
+            channel_id: channel_id.parse().ok()?,
+            message_id: message_id.parse().ok()?,
+        },
+    ))
+}
+
 impl InteractionsHandler {
     pub(super) async fn handle_find_links(&self, inter: Interaction, data: CommandData) {
         // use an inner function to make splitting the code easier
         let _ = handle_inner(self, inter, data).await;
     }
+
+    pub(super) async fn handle_find_links_page(
+        &self,
+        inter: Interaction,
+        data: MessageComponentInteractionData,
+    ) {
+        // use an inner function to make splitting the code easier
+        let _ = handle_page_inner(self, inter, data).await;
+    }
+
+    /// Whether the message can be loaded again when paging, which needs the message content
+    /// intent and access to the message history of the channel
+    fn can_reload_message(&self, inter: &Interaction) -> bool {
+        self.args().message_content_intent
+            && inter
+                .app_permissions
+                .is_some_and(|permissions| permissions.contains(RELOAD_PERMISSIONS))
+    }
+
+    async fn load_message(&self, source: SourceMessage) -> Option<Message> {
+        let response = self
+            .discord()
+            .message(source.channel_id, source.message_id)
+            .into_future()
+            .instrument(debug_span!("fetching_message"))
+            .await
+            .inspect_err(|e| debug!("Failed to fetch the message: {}", e))
+            .ok()?;
+
+        response
+            .model()
+            .await
+            .inspect_err(|e| debug!("Failed to deserialize the message: {}", e))
+            .ok()
+    }
+
+    /// Resolves the links with the rate limit priority for batches, links Odesli doesn't know
+    /// are skipped
+    async fn resolve_links(
+        &self,
+        inter: &Interaction,
+        links: &[Url],
+    ) -> Result<Vec<ResolvedLink>, ApiErr> {
+        let policy = AcquirePolicy::wait_until(RequestPriority::Batch, interaction_deadline(inter));
+        let futures = links
+            .iter()
+            .map(async |link| match self.data_routine(link, policy).await {
+                Ok((data, entity, colour)) => Ok(Some((link.clone(), data, entity, colour))),
+                Err(ApiErr::ClientError(e)) => {
+                    debug!(
+                        "Odesli API returned a client error for link {}, skipping it: {}",
+                        link, e
+                    );
+                    Ok(None)
+                }
+                Err(e) => Err(e),
+            });
+
+        Ok(try_join_all(futures).await?.into_iter().flatten().collect())
+    }
+}
+
+/// The message telling the user why the links couldn't be resolved
+fn resolve_error_message(err: &ApiErr, locale: DiscordLocale) -> &'static str {
+    match err {
+        ApiErr::RateLimitDeadlineExceeded => {
+            warn!("Odesli rate limit tokens could not be acquired in time, informing user");
+            messages::busy(locale)
+        }
+        e => {
+            warn!("Odesli API request failed, informing user: {}", e);
+            messages::error(locale)
+        }
+    }
 }
 
 #[instrument(name = "find_links_command_handler", level = "debug", skip_all)]
@@ -42,68 +169,84 @@ async fn handle_inner(
 
     let msg = get_message(&data)?;
 
-    let links: Vec<Url> = msg
-        .content
-        .as_str()
-        .split_whitespace()
-        .filter(|s| s.starts_with("http://") || s.starts_with("https://"))
-        .filter_map(|s| Url::parse(s).ok())
-        .filter(|url| VALID_DOMAINS_REGEX.is_match(url.domain().unwrap_or_default()))
-        .filter(|url| additional_link_validation(url).is_ok())
-        .take(5)
-        .collect();
-
-    if links.is_empty() {
+    let locale: DiscordLocale = (&inter.locale).into();
+    let source = this.can_reload_message(&inter).then_some(SourceMessage {
+        channel_id: msg.channel_id,
+        message_id: msg.id,
+    });
+    let max_links = match source {
+        Some(_) => MAX_PAGED_LINKS,
+        None => MAX_SHARED_LINKS,
+    };
+
+    let extracted = this.extract_links(msg, max_links).await;
+    let skipped_report = extracted.skipped_report(locale);
+    let ExtractedLinks {
+        links, playlists, ..
+    } = extracted;
+
+    if links.is_empty() && playlists.is_empty() {
         debug!("Could not find any links, informing user");
 
-        this.respond_with(&inter, messages::no_links_found((&inter.locale).into()))
-            .await;
+        let content = match skipped_report {
+            Some(report) => format!("{}\n{}", messages::no_links_found(locale), report),
+            None => messages::no_links_found(locale).to_string(),
+        };
+        this.respond_with(&inter, &content).await;
         return Err(());
     }
 
     debug!(
         links = %LoggerLinks(&links),
+        playlists = playlists.len(),
+        skipped = skipped_report.is_some(),
         "Found links in message, deferring Response"
     );
-    let defer_future = this.defer(&inter);
-
-    debug!("Starting Routine for each link");
-    let futures = links
-        .into_iter()
-        .map(async |link| match this.data_routine(&link).await {
-            Ok((data, entity, colour)) => Ok(Some((link.clone(), data, entity, colour))),
-            Err(ApiErr::ClientError(e)) => {
-                debug!(
-                    "Odesli API returned a client error for link {}, skipping it: {}",
-                    link, e
-                );
-                Ok(None)
-            }
-            Err(e) => Err(e),
-        });
-
-    let data = match try_join_all(futures).await {
+    let settings = this.guild_settings(inter.guild_id).await;
+    let defer_future = this.defer(&inter, settings.ephemeral_replies);
+
+    debug!("Starting Routine for each link on the first page");
+    let page_count = links.len().div_ceil(PAGE_SIZE);
+    let first_page = &links[..links.len().min(PAGE_SIZE)];
+    let data = match this.resolve_links(&inter, first_page).await {
         Ok(data) => data,
         Err(e) => {
-            warn!("Odesli API request failed, informing user: {}", e);
-            this.update_defer_with_error(&inter, messages::error((&inter.locale).into()))
+            this.update_defer_with_error(&inter, resolve_error_message(&e, locale))
                 .await;
             return Err(());
         }
     };
 
-    let mut usage_data = Vec::with_capacity(data.len());
-    let mut components = Vec::with_capacity(data.len());
+    let mut playlist_pages = Vec::with_capacity(playlists.len());
+    for id in &playlists {
+        match this.build_playlist_page(id, 0, locale, &settings).await {
+            Ok(components) => playlist_pages.push(components),
+            Err(e) => debug!(playlist = %id, "Skipping playlist: {}", e),
+        }
+    }
+
+    let usage_data = data
+        .iter()
+        .map(|(link, data, entity, _)| {
+            UsageData::from_find_links_command(&inter, link.clone(), data, entity)
+        })
+        .collect::<Vec<_>>();
+    let mut components = build_page(this, data, 0, locale, &settings);
 
-    for (idx, (link, data, entity, color)) in data.into_iter().flatten().enumerate() {
-        usage_data.push(UsageData::from_find_links_command(
-            &inter,
-            link,
-            &data.page_url,
-            &entity,
-            data.is_cached,
-        ));
-        components.extend(build_components(&data, entity, color, Some(idx as u16)));
+    // Each playlist is sent as a separate message, so paging through it only updates the playlist
+    let mut playlist_pages = playlist_pages.into_iter();
+    if components.is_empty()
+        && let Some(first_playlist) = playlist_pages.next()
+    {
+        components = first_playlist;
+    }
+    if let Some(source) = source
+        && page_count > 1
+    {
+        components.extend(build_navigation(0, page_count, source, locale));
+    }
+    if let Some(report) = skipped_report {
+        components.push(TextDisplayBuilder::new(report).build().into());
     }
 
     defer_future
@@ -120,12 +263,167 @@ async fn handle_inner(
         .await
         .map_err(expect_warn!("Failed to send the response to the user"))?;
 
+    let mut followup_flags = MessageFlags::IS_COMPONENTS_V2;
+    if settings.ephemeral_replies {
+        followup_flags |= MessageFlags::EPHEMERAL;
+    }
+    for playlist_components in playlist_pages {
+        this.discord()
+            .interaction_client()
+            .create_followup(inter.token.as_str())
+            .flags(followup_flags)
+            .components(&playlist_components)
+            .into_future()
+            .instrument(debug_span!("sending_playlist_followup"))
+            .await
+            .map_err(expect_warn!("Failed to send the playlist to the user"))?;
+    }
+
     debug!("Successfully sent Response, spawning task to save command usage data to the database");
     this.db().spawn_save_multi_to_db(usage_data);
 
     Ok(())
 }
 
+#[instrument(name = "find_links_page_handler", level = "debug", skip_all)]
+async fn handle_page_inner(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    data: MessageComponentInteractionData,
+) -> EmptyResult<()> {
+    debug!("Received Find Links Page Button Interaction");
+    let locale: DiscordLocale = (&inter.locale).into();
+
+    let Some((page, source)) = parse_page_custom_id(&data.custom_id) else {
+        warn!(
+            custom_id = data.custom_id,
+            "Received find links page button with invalid custom_id"
+        );
+        this.respond_with(&inter, messages::error(locale)).await;
+        return Err(());
+    };
+
+    let defer_future = this.defer_update(&inter);
+    let settings = this.guild_settings(inter.guild_id).await;
+
+    let extracted = match this.load_message(source).await {
+        Some(msg) => this.extract_links(&msg, MAX_PAGED_LINKS).await,
+        None => ExtractedLinks::default(),
+    };
+    if extracted.links.is_empty() {
+        debug!("The links of the message can't be found anymore, informing user");
+        defer_future
+            .await
+            .map_err(expect_warn!("Failed to join the defer future"))?;
+        this.followup_with_error(&inter, messages::find_links_expired(locale))
+            .await;
+        return Err(());
+    }
+
+    // The message could have been edited since the buttons were created, stay in bounds
+    let page_count = extracted.links.len().div_ceil(PAGE_SIZE);
+    let page = page.min(page_count - 1);
+    let page_links = extracted
+        .links
+        .chunks(PAGE_SIZE)
+        .nth(page)
+        .expect("Page should be in bounds");
+
+    debug!(page, links = %LoggerLinks(page_links), "Resolving the links of the page");
+    let data = match this.resolve_links(&inter, page_links).await {
+        Ok(data) => data,
+        Err(e) => {
+            defer_future
+                .await
+                .map_err(expect_warn!("Failed to join the defer future"))?;
+            this.followup_with_error(&inter, resolve_error_message(&e, locale))
+                .await;
+            return Err(());
+        }
+    };
+
+    // Paging doesn't count as sharing the links again, so no usage data is saved
+    let mut components = build_page(this, data, page, locale, &settings);
+    if page_count > 1 {
+        components.extend(build_navigation(page, page_count, source, locale));
+    }
+    if let Some(report) = extracted.skipped_report(locale) {
+        components.push(TextDisplayBuilder::new(report).build().into());
+    }
+
+    defer_future
+        .await
+        .map_err(expect_warn!("Failed to join the defer future"))?;
+
+    this.discord()
+        .interaction_client()
+        .update_response(inter.token.as_str())
+        .flags(MessageFlags::IS_COMPONENTS_V2)
+        .components(Some(&components))
+        .into_future()
+        .instrument(debug_span!("sending_response"))
+        .await
+        .map_err(expect_warn!("Failed to send the response to the user"))?;
+
+    Ok(())
+}
+
+/// Builds a card for each resolved link of the page
+fn build_page(
+    this: &InteractionsHandler,
+    data: Vec<ResolvedLink>,
+    page: usize,
+    locale: DiscordLocale,
+    settings: &GuildSettings,
+) -> Vec<Component> {
+    let offset = page * PAGE_SIZE;
+
+    data.into_iter()
+        .enumerate()
+        .flat_map(|(i, (_, data, entity, colour))| {
+            // The index is unique across pages, so the custom ids of the cards never collide
+            build_components(
+                &data,
+                &this.page_url(&data),
+                entity,
+                colour,
+                Some((offset + i) as u16),
+                locale,
+                settings,
+            )
+        })
+        .collect()
+}
+
+fn build_navigation(
+    page: usize,
+    page_count: usize,
+    source: SourceMessage,
+    locale: DiscordLocale,
+) -> [Component; 2] {
+    let previous = ButtonBuilder::new(ButtonStyle::Secondary)
+        .custom_id(page_custom_id(page.saturating_sub(1), source))
+        .label(messages::previous_page(locale))
+        .disabled(page == 0)
+        .build();
+    let next = ButtonBuilder::new(ButtonStyle::Secondary)
+        .custom_id(page_custom_id(page + 1, source))
+        .label(messages::next_page(locale))
+        .disabled(page + 1 >= page_count)
+        .build();
+
+    [
+        TextDisplayBuilder::new(format!("-# {}/{}", page + 1, page_count))
+            .build()
+            .into(),
+        ActionRowBuilder::new()
+            .component(previous)
+            .component(next)
+            .build()
+            .into(),
+    ]
+}
+
 struct LoggerLinks<'a>(&'a [Url]);
 
 impl<'a> fmt::Display for LoggerLinks<'a> {
@@ -0,0 +1,199 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use futures_util::future::join_all;
+use itertools::Itertools;
+use twilight_model::channel::message::Embed;
+use twilight_model::channel::{Attachment, Message};
+use url::Url;
+
+use crate::clients::odesli::provider_id::{ProviderId, canonicalize_url};
+use crate::clients::platforms::PlaylistId;
+use crate::interactions::InteractionsHandler;
+use crate::interactions::handlers::common::{
+    InvalidLink, additional_link_validation, is_supported_domain, scan_urls,
+};
+use crate::interactions::handlers::messages;
+use crate::util::discord_locales::DiscordLocale;
+
+/// The number of links shared in a single response, also the number of playlists sent for a
+/// single message
+pub const MAX_SHARED_LINKS: usize = 5;
+/// The number of links that can be paged through if the message can be loaded again for the
+/// following pages
+pub const MAX_PAGED_LINKS: usize = 25;
+/// The number of links in a single message that are looked at, to bound the short link expansion
+const MAX_CANDIDATES: usize = 40;
+/// The number of skipped links listed in the response
+const MAX_REPORTED_SKIPS: usize = 10;
+
+/// Why a link found in a message isn't shared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    UnsupportedDomain,
+    Playlist,
+    Artist,
+    YoutubeShort,
+    /// There were already as many links before it as are shared for a message
+    OverLimit,
+}
+
+impl SkipReason {
+    fn message(self, locale: DiscordLocale) -> &'static str {
+        match self {
+            SkipReason::UnsupportedDomain => messages::skipped_unsupported_domain(locale),
+            SkipReason::Playlist => messages::skipped_playlist(locale),
+            SkipReason::Artist => messages::skipped_artist(locale),
+            SkipReason::YoutubeShort => messages::skipped_youtube_short(locale),
+            SkipReason::OverLimit => messages::skipped_over_limit(locale),
+        }
+    }
+}
+
+/// The links found in a message, sorted into the ones to share and the ones that are skipped
+#[derive(Debug, Default)]
+pub struct ExtractedLinks {
+    pub links: Vec<Url>,
+    pub playlists: Vec<PlaylistId>,
+    pub skipped: Vec<(Url, SkipReason)>,
+}
+
+impl ExtractedLinks {
+    /// Whether the found link or playlist would exceed the limits
+    fn is_full(&self, found: &Found, max_links: usize) -> bool {
+        let total = self.links.len() + self.playlists.len();
+        match found {
+            Found::Playlist(_) => total >= max_links || self.playlists.len() >= MAX_SHARED_LINKS,
+            _ => total >= max_links,
+        }
+    }
+
+    /// Lists the skipped links with the reason they were skipped, if any were skipped
+    pub fn skipped_report(&self, locale: DiscordLocale) -> Option<String> {
+        if self.skipped.is_empty() {
+            return None;
+        }
+
+        let mut report = format!("-# {}", messages::links_skipped(locale));
+        for (url, reason) in self.skipped.iter().take(MAX_REPORTED_SKIPS) {
+            write!(report, "\n-# <{}> \u{2013} {}", url, reason.message(locale))
+                .expect("Writing to string should not fail");
+        }
+        if self.skipped.len() > MAX_REPORTED_SKIPS {
+            write!(report, "\n-# \u{2026}").expect("Writing to string should not fail");
+        }
+
+        Some(report)
+    }
+}
+
+/// All texts of the embed links could be in
+fn embed_texts(embed: &Embed) -> impl Iterator<Item = &str> {
+    embed
+        .url
+        .as_deref()
+        .into_iter()
+        .chain(embed.title.as_deref())
+        .chain(embed.description.as_deref())
+        .chain(embed.fields.iter().map(|field| field.value.as_str()))
+}
+
+/// The descriptions of the attachments, which can contain links as well
+fn attachment_texts(attachments: &[Attachment]) -> impl Iterator<Item = &str> {
+    attachments
+        .iter()
+        .filter_map(|attachment| attachment.description.as_deref())
+}
+
+/// All texts of the message links could be in: the content, the embeds, the descriptions of the
+/// attachments, the forwarded messages and the message replied to
+fn message_texts(msg: &Message) -> Vec<&str> {
+    let mut texts = vec![msg.content.as_str()];
+    texts.extend(msg.embeds.iter().flat_map(embed_texts));
+    texts.extend(attachment_texts(&msg.attachments));
+
+    for snapshot in &msg.message_snapshots {
+        texts.push(snapshot.message.content.as_str());
+        texts.extend(snapshot.message.embeds.iter().flat_map(embed_texts));
+        texts.extend(attachment_texts(&snapshot.message.attachments));
+    }
+
+    if let Some(replied) = &msg.referenced_message {
+        texts.push(replied.content.as_str());
+        texts.extend(replied.embeds.iter().flat_map(embed_texts));
+    }
+
+    texts
+}
+
+/// The key links are de-duplicated by, so different links to the same entity are only shared once
+fn dedup_key(url: &Url) -> String {
+    match ProviderId::parse_url(url) {
+        Ok(provider_id) => provider_id.to_string(),
+        Err(_) => url.to_string(),
+    }
+}
+
+impl InteractionsHandler {
+    /// Finds the links in every part of the message, expanding short links and sorting out the
+    /// ones that can't be shared.
+    ///
+    /// At most `max_links` links and playlists are returned, the order is stable so the same
+    /// message always yields the same links.
+    pub(super) async fn extract_links(&self, msg: &Message, max_links: usize) -> ExtractedLinks {
+        let candidates = message_texts(msg)
+            .into_iter()
+            .flat_map(scan_urls)
+            .unique()
+            .take(MAX_CANDIDATES)
+            .collect::<Vec<_>>();
+
+        let expanded = join_all(candidates.iter().map(|url| self.expander().expand(url))).await;
+
+        let mut seen = HashSet::new();
+        let mut extracted = ExtractedLinks::default();
+        for url in expanded {
+            // The same link can be in the content and its embed, with different tracking params
+            let url = canonicalize_url(&url);
+            if !seen.insert(dedup_key(&url)) {
+                continue;
+            }
+
+            let found = if !is_supported_domain(&url) {
+                Found::Skipped(SkipReason::UnsupportedDomain)
+            } else {
+                match additional_link_validation(&url) {
+                    Ok(()) => Found::Link,
+                    Err(InvalidLink::Playlist) => self
+                        .supported_playlist(&url)
+                        .map_or(Found::Skipped(SkipReason::Playlist), Found::Playlist),
+                    Err(InvalidLink::Artist) => Found::Skipped(SkipReason::Artist),
+                    Err(InvalidLink::YoutubeShort) => Found::Skipped(SkipReason::YoutubeShort),
+                }
+            };
+
+            match found {
+                Found::Skipped(reason) => extracted.skipped.push((url, reason)),
+                _ if extracted.is_full(&found, max_links) => {
+                    extracted.skipped.push((url, SkipReason::OverLimit))
+                }
+                Found::Link => extracted.links.push(url),
+                Found::Playlist(id) => extracted.playlists.push(id),
+            }
+        }
+
+        extracted
+    }
+}
+
+/// What a link found in the message turned out to be
+enum Found {
+    Link,
+    Playlist(PlaylistId),
+    Skipped(SkipReason),
+}
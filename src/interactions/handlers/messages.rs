@@ -3,110 +3,89 @@
  * All Rights Reserved
  */
 use crate::clients::odesli::ApiClientErr;
+use crate::localisation::localise;
 use crate::util::discord_locales::DiscordLocale;
 
-#[inline]
-pub const fn invalid_url(locale: DiscordLocale) -> &'static str {
-    match locale {
-        DiscordLocale::German => {
-            "Bitte sende mir einen validen Link, \
-            ich kann nur mit Links von den folgenden Plattformen arbeiten:\n\
-            Spotify, iTunes, Apple Music, YouTube, YouTube Music, Pandora, Deezer, Tidal, \
-            Amazon Music, SoundCloud and Yandex"
-        }
-        _ => {
-            "Please send a valid link, I can only work with links from the following platforms:\n\
-            Spotify, iTunes, Apple Music, YouTube, YouTube Music, Pandora, Deezer, Tidal, \
-            Amazon Music, SoundCloud and Yandex"
-        }
-    }
-}
-
-#[inline]
-pub const fn playlist_not_supported(locale: DiscordLocale) -> &'static str {
-    match locale {
-        DiscordLocale::German => {
-            "Leider unterstütze ich keine Playlists, bitte teile einen einzelnen Song oder ein Album\n\
-            -# Wenn du denkst, dass dies ein Fehler ist, öffne einen Report [hier](<https://github.com/tooboredtocode/Share-Music/issues>)"
-        }
-        _ => {
-            "Unfortunately playlists are not supported, please share a single song or album instead\n\
-            -# If you think this is a mistake, open an issue [here](<https://github.com/tooboredtocode/Share-Music/issues>)"
-        }
-    }
-}
+/// Defines a function per message, looking up the key `messages.<name>` in the catalogue, and
+/// collects all keys into [`KEYS`] so they can be checked when the catalogue is loaded.
+macro_rules! messages {
+    ($($name:ident),* $(,)?) => {
+        /// The keys of all messages, these have to be defined in every shipped bundle
+        pub const KEYS: &[&str] = &[$(concat!("messages.", stringify!($name))),*];
 
-#[inline]
-pub const fn artist_not_supported(locale: DiscordLocale) -> &'static str {
-    match locale {
-        DiscordLocale::German => {
-            "Leider unterstütze ich keine Künstler Links, bitte teile einen einzelnen Song oder ein Album\n\
-            -# Wenn du denkst, dass dies ein Fehler ist, öffne einen Report [hier](<https://github.com/tooboredtocode/Share-Music/issues>)"
-        }
-        _ => {
-            "Unfortunately artist links are not supported, please share a single song or album instead\n\
-            -# If you think this is a mistake, open an issue [here](<https://github.com/tooboredtocode/Share-Music/issues>)"
-        }
-    }
+        $(
+            #[inline]
+            pub fn $name(locale: DiscordLocale) -> &'static str {
+                localise(locale, concat!("messages.", stringify!($name)))
+            }
+        )*
+    };
 }
 
-#[inline]
-pub const fn youtube_shorts_not_supported(locale: DiscordLocale) -> &'static str {
-    match locale {
-        DiscordLocale::German => {
-            "Leider unterstütze ich keine YouTube Shorts, bitte teile einen einzelnen Song oder ein Album"
-        }
-        _ => {
-            "Unfortunately, I don't support YouTube Shorts, please share a single song or album instead"
-        }
-    }
-}
-
-#[inline]
-pub const fn no_links_found(locale: DiscordLocale) -> &'static str {
-    match locale {
-        DiscordLocale::German => {
-            "Es konnten keine uns bekannten links in der Nachricht gefunden werden"
-        }
-        _ => "Couldn't find any links in the message",
-    }
-}
-
-#[inline]
-pub const fn select_menu_with_depreciated_lookup_link(locale: DiscordLocale) -> &'static str {
-    match locale {
-        DiscordLocale::German => {
-            "Embedded Player für diese Plattform kann leider nicht angezeigt werden..."
-        }
-        _ => "Embedded player can unfortunately not be shown for this platform...",
-    }
+messages! {
+    invalid_url,
+    playlist_not_supported,
+    playlist_empty,
+    playlist_partial_result,
+    previous_page,
+    next_page,
+    artist_not_supported,
+    top_track,
+    youtube_shorts_not_supported,
+    no_links_found,
+    find_links_expired,
+    show_embedded_player,
+    select_menu_with_depreciated_lookup_link,
+    powered_by_odesli,
+    unknown_entity,
+    error,
+    busy,
+    links_skipped,
+    skipped_unsupported_domain,
+    skipped_playlist,
+    skipped_artist,
+    skipped_youtube_short,
+    skipped_over_limit,
+    auto_share_enabled_channel,
+    auto_share_disabled_channel,
+    auto_share_enabled_guild,
+    auto_share_disabled_guild,
+    auto_share_mention_only,
+    settings_updated,
+    settings_disabled_platforms,
+    settings_platform_order,
+    settings_player,
+    settings_visibility,
+    settings_none,
+    settings_alphabetical,
+    settings_shown,
+    settings_hidden,
+    settings_public,
+    settings_private,
+    unknown_platform,
+    open_in_my_platform,
+    preferred_platform_set,
+    preferred_platform_current,
+    no_preferred_platform,
+    not_on_preferred_platform,
+    share_expired,
+    no_search_results,
+    stats_top_tracks,
+    stats_top_artists,
+    stats_top_sharers,
+    stats_my_shares,
+    stats_period_week,
+    stats_period_month,
+    stats_period_year,
+    stats_period_all_time,
+    stats_empty,
+    stats_guild_only,
 }
 
 #[inline]
 pub fn api_client_error_message(err: ApiClientErr, locale: DiscordLocale) -> &'static str {
-    match (err, locale) {
-        (ApiClientErr::UnknownEntity, DiscordLocale::German) => {
-            "Zu diesem Link konnte leider kein passender Inhalt gefunden werden, bitte überprüfe den Link und versuche es erneut\n\
-            -# Wenn du denkst, dass dies ein Fehler ist, öffne einen Report [hier](<https://github.com/tooboredtocode/Share-Music/issues>)"
-        }
-        (ApiClientErr::UnknownEntity, _) => {
-            "Unfortunately, no matching content could be found for this link, please check the link and try again\n\
-            -# If you think this is a mistake, open an issue [here](<https://github.com/tooboredtocode/Share-Music/issues>)"
-        }
+    match err {
+        ApiClientErr::UnknownEntity => unknown_entity(locale),
         _ => error(locale),
     }
 }
-
-#[inline]
-pub const fn error(locale: DiscordLocale) -> &'static str {
-    match locale {
-        DiscordLocale::German => {
-            "Ein unerwarteter Fehler ist aufgetreten, wenn du sicher bist, dass du einen gültigen Link geteilt hast, versuche es später erneut\n\
-            -# Wenn das Problem weiterhin besteht, öffne einen Report [hier](<https://github.com/tooboredtocode/Share-Music/issues>)"
-        }
-        _ => {
-            "An unexpected error occurred, if you are sure you shared a valid link, please try again later\n\
-             -# If the issue persists, open an issue [here](<https://github.com/tooboredtocode/Share-Music/issues>)"
-        }
-    }
-}
@@ -1,23 +1,44 @@
 use std::mem;
 use tracing::debug;
 use twilight_interactions::command::CreateCommand;
-use twilight_model::application::interaction::InteractionData;
 use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::application::interaction::{InteractionData, InteractionType};
 use twilight_model::channel::message::component::ComponentType;
 
+use crate::interactions::commands::auto_share::AutoShareCommand;
+use crate::interactions::commands::cache::CacheCommand;
 use crate::interactions::commands::find_links::FindLinksCommand;
+use crate::interactions::commands::preferences::PreferencesCommand;
+use crate::interactions::commands::search::SearchCommand;
+use crate::interactions::commands::settings::SettingsCommand;
 use crate::interactions::commands::share::ShareCommand;
+use crate::interactions::commands::stats::StatsCommand;
 use crate::interactions::commands::test_colour_consts::TestColorConstsCommand;
 use crate::interactions::{CommandData, Interaction, InteractionsHandler, instrument};
 use crate::util::message_command::MessageCommand;
 
+mod artist;
+mod auto_share;
+mod cache;
 mod common;
 mod find_links;
+mod link_extraction;
 mod messages;
+mod playlist;
+mod preferences;
+mod search;
+mod settings;
 mod share;
 mod show_player;
+mod stats;
 mod test_colour_consts;
 
+pub(super) use auto_share::AutoShareState;
+pub use common::is_supported_domain;
+pub(super) use messages::KEYS as MESSAGE_KEYS;
+pub(super) use preferences::UserPreferencesCache;
+pub(super) use settings::GuildSettingsCache;
+
 impl InteractionsHandler {
     #[instrument(
         name = "interaction_handler",
@@ -32,6 +53,11 @@ impl InteractionsHandler {
     )]
     pub async fn handle(&self, mut inter: Interaction) {
         match mem::take(&mut inter.data) {
+            Some(InteractionData::ApplicationCommand(command_data))
+                if inter.kind == InteractionType::ApplicationCommandAutocomplete =>
+            {
+                self.handle_autocomplete(inter, *command_data).await;
+            }
             Some(InteractionData::ApplicationCommand(command_data)) => {
                 self.handle_application_commands(inter, *command_data).await;
             }
@@ -54,7 +80,13 @@ impl InteractionsHandler {
             TestColorConstsCommand::NAME => {
                 self.handle_test_colour_consts(inter, command_data).await;
             }
+            SearchCommand::NAME => self.handle_search(inter, command_data).await,
             FindLinksCommand::NAME => self.handle_find_links(inter, command_data).await,
+            AutoShareCommand::NAME => self.handle_auto_share(inter, command_data).await,
+            SettingsCommand::NAME => self.handle_settings(inter, command_data).await,
+            PreferencesCommand::NAME => self.handle_preferences(inter, command_data).await,
+            StatsCommand::NAME => self.handle_stats(inter, command_data).await,
+            CacheCommand::NAME => self.handle_cache(inter, command_data).await,
             name => debug!(
                 "Unknown {} Application Command Interaction: {}",
                 command_data.kind.kind(),
@@ -63,6 +95,16 @@ impl InteractionsHandler {
         }
     }
 
+    async fn handle_autocomplete(&self, inter: Interaction, command_data: CommandData) {
+        debug!("Received Autocomplete Interaction");
+
+        match command_data.name.as_str() {
+            ShareCommand::NAME => self.handle_share_autocomplete(inter, command_data).await,
+            SearchCommand::NAME => self.handle_search_autocomplete(inter, command_data).await,
+            name => debug!("Unknown Autocomplete Interaction: {}", name),
+        }
+    }
+
     async fn handle_message_components(
         &self,
         inter: Interaction,
@@ -82,6 +124,40 @@ impl InteractionsHandler {
             }
         }
 
+        if component_data.component_type == ComponentType::Button
+            && component_data
+                .custom_id
+                .starts_with(playlist::PAGE_BUTTON_ID)
+        {
+            debug!("Handling Playlist Page Button Interaction");
+            return self.handle_playlist_page(inter, component_data).await;
+        }
+
+        if component_data.component_type == ComponentType::Button
+            && component_data
+                .custom_id
+                .starts_with(preferences::MY_PLATFORM_BUTTON_ID)
+        {
+            debug!("Handling My Platform Button Interaction");
+            return self.handle_my_platform(inter, component_data).await;
+        }
+
+        if component_data.component_type == ComponentType::Button
+            && component_data
+                .custom_id
+                .starts_with(find_links::PAGE_BUTTON_ID)
+        {
+            debug!("Handling Find Links Page Button Interaction");
+            return self.handle_find_links_page(inter, component_data).await;
+        }
+
+        if component_data.component_type == ComponentType::Button
+            && component_data.custom_id.starts_with(stats::PAGE_BUTTON_ID)
+        {
+            debug!("Handling Stats Page Button Interaction");
+            return self.handle_stats_page(inter, component_data).await;
+        }
+
         debug!(
             "Unknown {} Application Command Interaction: {}",
             component_data.component_type.name(),
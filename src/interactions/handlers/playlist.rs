@@ -0,0 +1,325 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::fmt;
+use std::fmt::Write;
+use std::future::IntoFuture;
+
+use futures_util::future::join_all;
+use tracing::{Instrument, debug, debug_span, instrument, warn};
+use twilight_model::application::interaction::Interaction;
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::channel::message::component::ButtonStyle;
+use twilight_model::channel::message::{Component, MessageFlags};
+use twilight_util::builder::message::{
+    ActionRowBuilder, ButtonBuilder, ContainerBuilder, SeparatorBuilder, TextDisplayBuilder,
+};
+use url::Url;
+
+use crate::clients::odesli::{ApiErr, OdesliClientResponse, Platform};
+use crate::clients::platforms::{PlatformApiErr, PlaylistId, PlaylistPage, PlaylistTrack};
+use crate::db::GuildSettings;
+use crate::interactions::InteractionsHandler;
+use crate::interactions::handlers::messages;
+use crate::util::EmptyResult;
+use crate::util::discord_locales::DiscordLocale;
+use crate::util::error::expect_warn;
+
+pub const PAGE_BUTTON_ID: &str = "playlist_page";
+
+/// The number of tracks shown per page, kept low to stay below the text limit of a message
+const PAGE_SIZE: usize = 5;
+
+/// The platforms linked for each track, all platforms would exceed the text limit of a message
+const PLAYLIST_PLATFORMS: &[Platform] = &[
+    Platform::Spotify,
+    Platform::AppleMusic,
+    Platform::YouTubeMusic,
+    Platform::Deezer,
+    Platform::Tidal,
+    Platform::AmazonMusic,
+];
+
+#[derive(Debug)]
+pub enum PlaylistPageErr {
+    Api(PlatformApiErr),
+    Empty,
+}
+
+enum TrackResolution {
+    Resolved(OdesliClientResponse),
+    /// The track wasn't cached and no rate limit token was available
+    BudgetExhausted,
+    Unresolved,
+}
+
+impl fmt::Display for PlaylistPageErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlaylistPageErr::Api(err) => write!(f, "Failed to fetch playlist: {}", err),
+            PlaylistPageErr::Empty => f.write_str("The playlist is empty"),
+        }
+    }
+}
+
+impl PlaylistPageErr {
+    pub fn message(&self, locale: DiscordLocale) -> &'static str {
+        match self {
+            PlaylistPageErr::Empty => messages::playlist_empty(locale),
+            PlaylistPageErr::Api(_) => messages::error(locale),
+        }
+    }
+}
+
+fn page_custom_id(page: usize, id: &PlaylistId) -> String {
+    format!("{}:{}:{}", PAGE_BUTTON_ID, page, id)
+}
+
+fn parse_page_custom_id(custom_id: &str) -> Option<(usize, PlaylistId)> {
+    let rest = custom_id.strip_prefix(PAGE_BUTTON_ID)?.strip_prefix(':')?;
+    let (page, id) = rest.split_once(':')?;
+
+    Some((page.parse().ok()?, id.parse().ok()?))
+}
+
+impl InteractionsHandler {
+    /// Returns the playlist ID if the link is a playlist we are able to expand
+    pub(super) fn supported_playlist(&self, url: &Url) -> Option<PlaylistId> {
+        PlaylistId::parse_url(url).filter(|id| self.platforms().supports_playlist(id))
+    }
+
+    /// Builds the components for the given page of the playlist, resolving the tracks on it
+    #[instrument(level = "debug", skip(self, locale, settings))]
+    pub(super) async fn build_playlist_page(
+        &self,
+        id: &PlaylistId,
+        page: usize,
+        locale: DiscordLocale,
+        settings: &GuildSettings,
+    ) -> Result<Vec<Component>, PlaylistPageErr> {
+        let mut playlist = self
+            .platforms()
+            .fetch_playlist_page(id, page * PAGE_SIZE, PAGE_SIZE)
+            .await
+            .map_err(PlaylistPageErr::Api)?;
+
+        if playlist.total == 0 {
+            return Err(PlaylistPageErr::Empty);
+        }
+
+        let page_count = playlist.total.div_ceil(PAGE_SIZE);
+        let page = if page >= page_count {
+            // The playlist shrunk since the buttons were created, show the last page instead
+            let last_page = page_count - 1;
+            playlist = self
+                .platforms()
+                .fetch_playlist_page(id, last_page * PAGE_SIZE, PAGE_SIZE)
+                .await
+                .map_err(PlaylistPageErr::Api)?;
+            last_page
+        } else {
+            page
+        };
+
+        debug!("Resolving {} tracks of the playlist", playlist.tracks.len());
+        let resolutions = join_all(
+            playlist
+                .tracks
+                .iter()
+                .map(|track| self.resolve_track(track)),
+        );
+        let resolutions = resolutions.await;
+
+        Ok(build_components(
+            &playlist,
+            &resolutions,
+            id,
+            page,
+            page_count,
+            locale,
+            settings,
+        ))
+    }
+
+    async fn resolve_track(&self, track: &PlaylistTrack) -> TrackResolution {
+        let Some(url) = track.url.as_deref().and_then(|url| Url::parse(url).ok()) else {
+            return TrackResolution::Unresolved;
+        };
+
+        // Don't wait for the rate limiter, a playlist could otherwise use up the whole budget
+        match self.resolver().try_resolve(&url).await {
+            Ok(data) => TrackResolution::Resolved(data),
+            Err(ApiErr::RateLimitBudgetExhausted) => TrackResolution::BudgetExhausted,
+            Err(e) => {
+                debug!("Failed to resolve playlist track {}: {}", url, e);
+                TrackResolution::Unresolved
+            }
+        }
+    }
+
+    pub(super) async fn handle_playlist_page(
+        &self,
+        inter: Interaction,
+        data: MessageComponentInteractionData,
+    ) {
+        // use an inner function to make splitting the code easier
+        let _ = handle_page_inner(self, inter, data).await;
+    }
+}
+
+#[instrument(name = "playlist_page_handler", level = "debug", skip_all)]
+async fn handle_page_inner(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    data: MessageComponentInteractionData,
+) -> EmptyResult<()> {
+    debug!("Received Playlist Page Button Interaction");
+
+    let Some((page, id)) = parse_page_custom_id(&data.custom_id) else {
+        warn!(
+            custom_id = data.custom_id,
+            "Received playlist page button with invalid custom_id"
+        );
+        this.respond_with(&inter, messages::error((&inter.locale).into()))
+            .await;
+        return Err(());
+    };
+
+    let defer_future = this.defer_update(&inter);
+    let settings = this.guild_settings(inter.guild_id).await;
+
+    let components = match this
+        .build_playlist_page(&id, page, (&inter.locale).into(), &settings)
+        .await
+    {
+        Ok(components) => components,
+        Err(e) => {
+            warn!("Failed to build playlist page: {}", e);
+            defer_future
+                .await
+                .map_err(expect_warn!("Failed to join the defer future"))?;
+            this.followup_with_error(&inter, e.message((&inter.locale).into()))
+                .await;
+            return Err(());
+        }
+    };
+
+    defer_future
+        .await
+        .map_err(expect_warn!("Failed to join the defer future"))?;
+
+    this.discord()
+        .interaction_client()
+        .update_response(inter.token.as_str())
+        .flags(MessageFlags::IS_COMPONENTS_V2)
+        .components(Some(&components))
+        .into_future()
+        .instrument(debug_span!("sending_response"))
+        .await
+        .map_err(expect_warn!("Failed to send the response to the user"))?;
+
+    Ok(())
+}
+
+fn build_components(
+    playlist: &PlaylistPage,
+    resolutions: &[TrackResolution],
+    id: &PlaylistId,
+    page: usize,
+    page_count: usize,
+    locale: DiscordLocale,
+    settings: &GuildSettings,
+) -> Vec<Component> {
+    let mut container = ContainerBuilder::new().component(
+        TextDisplayBuilder::new(format!(
+            "## {}\n-# {}/{}",
+            playlist.name,
+            page + 1,
+            page_count
+        ))
+        .build(),
+    );
+    container = container.component(SeparatorBuilder::new().build());
+
+    let offset = page * PAGE_SIZE;
+    for (i, (track, resolution)) in playlist.tracks.iter().zip(resolutions).enumerate() {
+        container = container.component(
+            TextDisplayBuilder::new(track_details(offset + i, track, resolution, settings)).build(),
+        );
+    }
+
+    if resolutions
+        .iter()
+        .any(|r| matches!(r, TrackResolution::BudgetExhausted))
+    {
+        container = container
+            .component(TextDisplayBuilder::new(messages::playlist_partial_result(locale)).build());
+    }
+
+    if page_count > 1 {
+        let previous = ButtonBuilder::new(ButtonStyle::Secondary)
+            .custom_id(page_custom_id(page.saturating_sub(1), id))
+            .label(messages::previous_page(locale))
+            .disabled(page == 0)
+            .build();
+        let next = ButtonBuilder::new(ButtonStyle::Secondary)
+            .custom_id(page_custom_id(page + 1, id))
+            .label(messages::next_page(locale))
+            .disabled(page + 1 >= page_count)
+            .build();
+
+        container = container.component(
+            ActionRowBuilder::new()
+                .component(previous)
+                .component(next)
+                .build(),
+        );
+    }
+
+    container =
+        container.component(TextDisplayBuilder::new(messages::powered_by_odesli(locale)).build());
+
+    vec![container.build().into()]
+}
+
+fn track_details(
+    idx: usize,
+    track: &PlaylistTrack,
+    resolution: &TrackResolution,
+    settings: &GuildSettings,
+) -> String {
+    let mut details = match (&track.url, resolution) {
+        (_, TrackResolution::Resolved(data)) => {
+            format!("**{}. [{}]({})**", idx + 1, track.title, data.page_url)
+        }
+        (Some(url), _) => format!("**{}. [{}]({})**", idx + 1, track.title, url),
+        (None, _) => format!("**{}. {}**", idx + 1, track.title),
+    };
+
+    if let Some(artist) = &track.artist {
+        write!(details, " \u{2014} {}", artist).expect("Writing to string should not fail");
+    }
+
+    let TrackResolution::Resolved(data) = resolution else {
+        return details;
+    };
+
+    let links = PLAYLIST_PLATFORMS
+        .iter()
+        .filter(|platform| settings.is_platform_enabled(platform))
+        .filter_map(|platform| Some((platform, data.links_by_platform.get(platform)?)));
+    for (i, (platform, links)) in links.enumerate() {
+        if i == 0 {
+            details.push('\n');
+        } else {
+            details.push_str("  \u{2022}  "); // 2 tabs with a bullet in the middle
+        }
+
+        write!(details, "[{}]({})", platform, links.url)
+            .expect("Writing to string should not fail");
+    }
+
+    details
+}
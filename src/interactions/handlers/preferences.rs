@@ -0,0 +1,223 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use dashmap::DashMap;
+use sea_orm::DbErr;
+use tracing::{debug, instrument, warn};
+use twilight_model::application::interaction::Interaction;
+use twilight_model::application::interaction::application_command::CommandData;
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::channel::message::Component;
+use twilight_model::channel::message::component::ButtonStyle;
+use twilight_model::id::Id;
+use twilight_model::id::marker::UserMarker;
+use twilight_util::builder::message::{ActionRowBuilder, ButtonBuilder};
+
+use crate::clients::odesli::OdesliClientResponse;
+use crate::clients::odesli::Platform;
+use crate::clients::odesli::provider_id::ProviderId;
+use crate::interactions::InteractionsHandler;
+use crate::interactions::commands::preferences::PreferencesCommand;
+use crate::interactions::handlers::messages;
+use crate::util::EmptyResult;
+use crate::util::discord_locales::DiscordLocale;
+
+pub const MY_PLATFORM_BUTTON_ID: &str = "my_platform";
+
+/// In memory cache of the preferred platform of each user, `None` if the user didn't set one.
+#[derive(Debug, Default)]
+pub struct UserPreferencesCache {
+    preferred_platforms: DashMap<Id<UserMarker>, Option<Platform>>,
+}
+
+/// Builds the button opening the song on the preferred platform of whoever clicks it.
+///
+/// The custom id references the cached Odesli response, the index keeps it unique if multiple
+/// cards are part of the same message.
+pub fn build_my_platform_button(
+    data: &OdesliClientResponse,
+    idx: Option<u16>,
+    locale: DiscordLocale,
+) -> Option<Component> {
+    let Some(provider_id) = data.provider_id() else {
+        debug!("Response has no provider ID, not sending my platform button");
+        return None;
+    };
+
+    let custom_id = format!(
+        "{}:{}:{}",
+        MY_PLATFORM_BUTTON_ID,
+        idx.unwrap_or_default(),
+        provider_id
+    );
+    if custom_id.len() > 100 {
+        debug!(
+            custom_id,
+            "Custom id is too long, not sending my platform button"
+        );
+        return None;
+    }
+
+    Some(
+        ActionRowBuilder::new()
+            .component(
+                ButtonBuilder::new(ButtonStyle::Primary)
+                    .custom_id(custom_id)
+                    .label(messages::open_in_my_platform(locale))
+                    .build(),
+            )
+            .build()
+            .into(),
+    )
+}
+
+fn parse_my_platform_custom_id(custom_id: &str) -> Option<ProviderId> {
+    let rest = custom_id
+        .strip_prefix(MY_PLATFORM_BUTTON_ID)?
+        .strip_prefix(':')?;
+    let (_idx, provider_id) = rest.split_once(':')?;
+
+    provider_id.parse().ok()
+}
+
+impl InteractionsHandler {
+    pub(super) async fn handle_preferences(&self, inter: Interaction, data: CommandData) {
+        // use an inner function to make splitting the code easier
+        let _ = handle_command(self, inter, data).await;
+    }
+
+    pub(super) async fn handle_my_platform(
+        &self,
+        inter: Interaction,
+        data: MessageComponentInteractionData,
+    ) {
+        // use an inner function to make splitting the code easier
+        let _ = handle_button(self, inter, data).await;
+    }
+
+    /// Returns the preferred platform of the user, only successfully loaded preferences are cached
+    async fn preferred_platform(&self, user_id: Id<UserMarker>) -> Result<Option<Platform>, DbErr> {
+        let cache = &self.preferences_cache().preferred_platforms;
+        if let Some(platform) = cache.get(&user_id) {
+            return Ok(platform.clone());
+        }
+
+        let platform = self.db().load_preferred_platform(user_id).await?;
+        Ok(cache.entry(user_id).or_insert(platform).clone())
+    }
+}
+
+#[instrument(name = "preferences_command_handler", level = "debug", skip_all)]
+async fn handle_command(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    data: CommandData,
+) -> EmptyResult<()> {
+    debug!("Received Preferences Command Interaction");
+
+    let command: PreferencesCommand = this.parse_command(data)?;
+    let locale: DiscordLocale = (&inter.locale).into();
+    let Some(user_id) = inter.author_id() else {
+        warn!("Received Preferences Command without an author");
+        return Err(());
+    };
+
+    let Some(platform) = command.platform.map(Platform::from) else {
+        let preferred_platform = match this.preferred_platform(user_id).await {
+            Ok(platform) => platform,
+            Err(e) => {
+                warn!("Failed to load preferred platform, informing user: {}", e);
+                this.respond_with(&inter, messages::error(locale)).await;
+                return Err(());
+            }
+        };
+        let message = match preferred_platform {
+            Some(platform) => format!(
+                "{} **{}**",
+                messages::preferred_platform_current(locale),
+                platform
+            ),
+            None => messages::no_preferred_platform(locale).to_string(),
+        };
+        this.respond_with(&inter, &message).await;
+        return Ok(());
+    };
+
+    if let Err(e) = this.db().save_preferred_platform(user_id, &platform).await {
+        warn!("Failed to save preferred platform, informing user: {}", e);
+        this.respond_with(&inter, messages::error(locale)).await;
+        return Err(());
+    }
+
+    debug!(%platform, "Updated preferred platform");
+    let message = format!(
+        "{} **{}**",
+        messages::preferred_platform_set(locale),
+        platform
+    );
+    this.preferences_cache()
+        .preferred_platforms
+        .insert(user_id, Some(platform));
+
+    this.respond_with(&inter, &message).await;
+    Ok(())
+}
+
+#[instrument(name = "my_platform_button_handler", level = "debug", skip_all)]
+async fn handle_button(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    data: MessageComponentInteractionData,
+) -> EmptyResult<()> {
+    debug!("Received My Platform Button Interaction");
+    let locale: DiscordLocale = (&inter.locale).into();
+
+    let Some(provider_id) = parse_my_platform_custom_id(&data.custom_id) else {
+        warn!(
+            custom_id = data.custom_id,
+            "Received my platform button with invalid custom_id"
+        );
+        this.respond_with(&inter, messages::error(locale)).await;
+        return Err(());
+    };
+
+    let Some(user_id) = inter.author_id() else {
+        warn!("Received My Platform Button without an author");
+        return Err(());
+    };
+
+    let preferred_platform = match this.preferred_platform(user_id).await {
+        Ok(platform) => platform,
+        Err(e) => {
+            warn!("Failed to load preferred platform, informing user: {}", e);
+            this.respond_with(&inter, messages::error(locale)).await;
+            return Err(());
+        }
+    };
+    let Some(platform) = preferred_platform else {
+        debug!("User has no preferred platform, informing user");
+        this.respond_with(&inter, messages::no_preferred_platform(locale))
+            .await;
+        return Ok(());
+    };
+
+    let Some(response) = this.odesli().fetch_cached(&provider_id).await else {
+        debug!("Response is no longer cached, informing user");
+        this.respond_with(&inter, messages::share_expired(locale))
+            .await;
+        return Ok(());
+    };
+
+    match response.links_by_platform.get(&platform) {
+        Some(links) => this.respond_with(&inter, &links.url).await,
+        None => {
+            debug!(%platform, "Song is not available on the preferred platform, informing user");
+            this.respond_with(&inter, messages::not_on_preferred_platform(locale))
+                .await;
+        }
+    }
+
+    Ok(())
+}
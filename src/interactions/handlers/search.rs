@@ -0,0 +1,284 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use tracing::{debug, instrument, warn};
+use twilight_interactions::command::AutocompleteValue;
+use twilight_model::application::command::CommandOptionChoice;
+use twilight_model::application::interaction::Interaction;
+use twilight_model::application::interaction::application_command::CommandData;
+use url::Url;
+
+use crate::clients::platforms::PlatformApiErr;
+use crate::clients::search::{SearchClient, SearchQuery};
+use crate::interactions::InteractionsHandler;
+use crate::interactions::commands::search::{SearchCommand, SearchCommandPartial};
+use crate::interactions::handlers::common::{additional_link_validation, is_supported_domain};
+use crate::interactions::handlers::messages;
+use crate::interactions::handlers::share::share_link;
+use crate::interactions::utils::autocomplete_choice;
+use crate::util::EmptyResult;
+use crate::util::discord_locales::DiscordLocale;
+
+/// The number of results the command picks from, the first one that can be shared is picked
+const COMMAND_LIMIT: usize = 5;
+/// The number of suggestions shown while typing, Discord allows at most 25
+const AUTOCOMPLETE_LIMIT: usize = 10;
+/// Don't search for very short titles, these would only return noise
+const AUTOCOMPLETE_MIN_LEN: usize = 3;
+
+impl InteractionsHandler {
+    pub(super) async fn handle_search(&self, inter: Interaction, data: CommandData) {
+        // use an inner function to make splitting the code easier
+        let _ = handle_command(self, inter, data).await;
+    }
+
+    pub(super) async fn handle_search_autocomplete(&self, inter: Interaction, data: CommandData) {
+        let _ = handle_autocomplete(self, inter, data).await;
+    }
+}
+
+#[instrument(name = "search_command_handler", level = "debug", skip_all)]
+async fn handle_command(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    data: CommandData,
+) -> EmptyResult<()> {
+    debug!("Received Search Command Interaction");
+
+    let command: SearchCommand = this.parse_command(data)?;
+    let locale: DiscordLocale = (&inter.locale).into();
+
+    // Picking a suggestion fills in its link instead of the title
+    if let Some(url) = suggested_link(&command.title) {
+        debug!(url = %url, "User picked a suggestion, sharing it");
+        return share_link(this, inter, url, false).await;
+    }
+
+    let query = SearchQuery {
+        title: command.title,
+        artist: command.artist,
+        album: command.album,
+    };
+
+    let url = match find_song(this.search(), &query).await {
+        Ok(url) => url,
+        Err(e) => {
+            warn!("Search request failed, informing user: {}", e);
+            this.respond_with(&inter, messages::error(locale)).await;
+            return Err(());
+        }
+    };
+
+    let Some(url) = url else {
+        debug!("Search returned no results, informing user");
+        this.respond_with(&inter, messages::no_search_results(locale))
+            .await;
+        return Ok(());
+    };
+
+    debug!(url = %url, "Found matching song, sharing it");
+    share_link(this, inter, url, false).await
+}
+
+#[instrument(name = "search_autocomplete_handler", level = "debug", skip_all)]
+async fn handle_autocomplete(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    data: CommandData,
+) -> EmptyResult<()> {
+    let command: SearchCommandPartial = this.parse_command(data)?;
+
+    let choices = match command.title {
+        AutocompleteValue::Focused(title) if title.trim().len() >= AUTOCOMPLETE_MIN_LEN => {
+            let query = SearchQuery {
+                title,
+                artist: command.artist,
+                album: command.album,
+            };
+
+            match suggestions(this.search(), &query).await {
+                Ok(choices) => choices,
+                Err(e) => {
+                    warn!("Search request failed, not suggesting anything: {}", e);
+                    Vec::new()
+                }
+            }
+        }
+        _ => Vec::new(),
+    };
+
+    this.respond_with_choices(&inter, choices).await
+}
+
+/// Returns the link of the best result that can be shared, if any
+async fn find_song(
+    search: &SearchClient,
+    query: &SearchQuery,
+) -> Result<Option<Url>, PlatformApiErr> {
+    let results = search.search(query, COMMAND_LIMIT).await?;
+
+    Ok(results
+        .into_iter()
+        .find_map(|result| suggested_link(&result.url)))
+}
+
+/// Returns the results as choices, picking one fills in its link as the title
+async fn suggestions(
+    search: &SearchClient,
+    query: &SearchQuery,
+) -> Result<Vec<CommandOptionChoice>, PlatformApiErr> {
+    let results = search.search(query, AUTOCOMPLETE_LIMIT).await?;
+
+    Ok(results
+        .into_iter()
+        .filter_map(|result| autocomplete_choice(result.label(), result.url))
+        .collect())
+}
+
+/// Returns the link if the title is one we can share, which is the case for picked suggestions
+fn suggested_link(title: &str) -> Option<Url> {
+    let url = Url::parse(title.trim()).ok()?;
+
+    (is_supported_domain(&url) && additional_link_validation(&url).is_ok()).then_some(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use twilight_model::application::command::CommandOptionChoiceValue;
+
+    use super::*;
+    use crate::clients::search::StubSearch;
+
+    fn query(title: &str, artist: Option<&str>) -> SearchQuery {
+        SearchQuery {
+            title: title.to_string(),
+            artist: artist.map(str::to_string),
+            album: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn command_shares_the_first_result() {
+        let stub = Arc::new(StubSearch::with_results(&[
+            (
+                "Song",
+                "Artist",
+                "https://music.apple.com/us/song/1440833098",
+            ),
+            (
+                "Song (Live)",
+                "Artist",
+                "https://music.apple.com/us/song/1440833099",
+            ),
+        ]));
+        let search = SearchClient::with_backend(stub.clone());
+
+        let url = find_song(&search, &query("Song", Some("Artist")))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            url.as_ref().map(Url::as_str),
+            Some("https://music.apple.com/us/song/1440833098")
+        );
+        assert_eq!(
+            *stub.queries.lock().unwrap(),
+            [("Song Artist".to_string(), COMMAND_LIMIT)]
+        );
+    }
+
+    #[tokio::test]
+    async fn command_skips_results_that_cant_be_shared() {
+        let search = SearchClient::with_backend(Arc::new(StubSearch::with_results(&[
+            ("Invalid", "Artist", "not a link"),
+            ("Unsupported", "Artist", "https://example.com/song/1"),
+            ("Artist", "Artist", "https://music.apple.com/us/artist/1"),
+            (
+                "Song",
+                "Artist",
+                "https://music.apple.com/us/song/1440833098",
+            ),
+        ])));
+
+        let url = find_song(&search, &query("Song", None)).await.unwrap();
+
+        assert_eq!(
+            url.as_ref().map(Url::as_str),
+            Some("https://music.apple.com/us/song/1440833098")
+        );
+    }
+
+    #[tokio::test]
+    async fn command_only_considers_the_best_results() {
+        let mut results = vec![("Invalid", "Artist", "not a link"); COMMAND_LIMIT];
+        results.push((
+            "Song",
+            "Artist",
+            "https://music.apple.com/us/song/1440833098",
+        ));
+        let search = SearchClient::with_backend(Arc::new(StubSearch::with_results(&results)));
+
+        let url = find_song(&search, &query("Song", None)).await.unwrap();
+
+        assert_eq!(url, None);
+    }
+
+    #[tokio::test]
+    async fn command_without_results() {
+        let search = SearchClient::with_backend(Arc::new(StubSearch::with_results(&[])));
+
+        let url = find_song(&search, &query("Song", None)).await.unwrap();
+
+        assert_eq!(url, None);
+    }
+
+    #[tokio::test]
+    async fn command_returns_backend_errors() {
+        let search = SearchClient::with_backend(Arc::new(StubSearch::failing()));
+
+        let result = find_song(&search, &query("Song", None)).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn autocomplete_suggests_links_by_label() {
+        let long_link = format!("https://music.apple.com/us/song/{}", "1".repeat(100));
+        let stub = Arc::new(StubSearch::with_results(&[
+            (
+                "Song",
+                "Artist",
+                "https://music.apple.com/us/song/1440833098",
+            ),
+            ("Song", "Artist", &long_link),
+        ]));
+        let search = SearchClient::with_backend(stub.clone());
+
+        let choices = suggestions(&search, &query("Song", None)).await.unwrap();
+
+        assert_eq!(choices.len(), 1);
+        assert_eq!(choices[0].name, "Song \u{2013} Artist");
+        assert_eq!(
+            choices[0].value,
+            CommandOptionChoiceValue::String(
+                "https://music.apple.com/us/song/1440833098".to_string()
+            )
+        );
+        assert_eq!(
+            *stub.queries.lock().unwrap(),
+            [("Song".to_string(), AUTOCOMPLETE_LIMIT)]
+        );
+    }
+
+    #[test]
+    fn picked_suggestions_are_shared_directly() {
+        assert!(suggested_link(" https://music.apple.com/us/song/1440833098 ").is_some());
+        assert!(suggested_link("https://example.com/song/1").is_none());
+        assert!(suggested_link("https://open.spotify.com/artist/1").is_none());
+        assert!(suggested_link("Never Gonna Give You Up").is_none());
+    }
+}
@@ -0,0 +1,250 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::fmt::Write;
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use itertools::Itertools;
+use sea_orm::DbErr;
+use tracing::{debug, instrument, warn};
+use twilight_model::application::interaction::Interaction;
+use twilight_model::application::interaction::application_command::CommandData;
+use twilight_model::id::Id;
+use twilight_model::id::marker::GuildMarker;
+
+use crate::clients::odesli::Platform;
+use crate::db::GuildSettings;
+use crate::interactions::InteractionsHandler;
+use crate::interactions::commands::settings::SettingsCommand;
+use crate::interactions::handlers::messages;
+use crate::util::EmptyResult;
+use crate::util::discord_locales::DiscordLocale;
+
+/// In memory cache of the guild settings, entries are replaced whenever the settings are updated.
+#[derive(Debug, Default)]
+pub struct GuildSettingsCache {
+    settings: DashMap<Id<GuildMarker>, Arc<GuildSettings>>,
+}
+
+impl InteractionsHandler {
+    pub(super) async fn handle_settings(&self, inter: Interaction, data: CommandData) {
+        // use an inner function to make splitting the code easier
+        let _ = handle_inner(self, inter, data).await;
+    }
+
+    /// Returns the settings of the guild, or the defaults outside of guilds or if they can't be
+    /// loaded
+    pub(super) async fn guild_settings(
+        &self,
+        guild_id: Option<Id<GuildMarker>>,
+    ) -> Arc<GuildSettings> {
+        let Some(guild_id) = guild_id else {
+            return Arc::default();
+        };
+
+        self.try_guild_settings(guild_id).await.unwrap_or_else(|e| {
+            warn!("Failed to load guild settings, using the defaults: {}", e);
+            Arc::default()
+        })
+    }
+
+    /// Returns the settings of the guild, only successfully loaded settings are cached
+    async fn try_guild_settings(
+        &self,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<Arc<GuildSettings>, DbErr> {
+        if let Some(settings) = self.settings_cache().settings.get(&guild_id) {
+            return Ok(settings.clone());
+        }
+
+        let settings = Arc::new(self.db().load_guild_settings(guild_id).await?);
+        Ok(self
+            .settings_cache()
+            .settings
+            .entry(guild_id)
+            .or_insert(settings)
+            .clone())
+    }
+
+    /// Applies the update to the settings of the guild, persists them and invalidates the cache.
+    ///
+    /// Fails without saving anything if the current settings can't be loaded, as saving the
+    /// updated defaults would overwrite them.
+    async fn update_guild_settings(
+        &self,
+        guild_id: Id<GuildMarker>,
+        update: impl FnOnce(&mut GuildSettings),
+    ) -> Result<Arc<GuildSettings>, DbErr> {
+        let mut settings = GuildSettings::clone(&*self.try_guild_settings(guild_id).await?);
+        update(&mut settings);
+
+        self.db()
+            .save_guild_settings(guild_id, settings.clone())
+            .await?;
+
+        let settings = Arc::new(settings);
+        self.settings_cache()
+            .settings
+            .insert(guild_id, settings.clone());
+
+        Ok(settings)
+    }
+}
+
+#[instrument(name = "settings_command_handler", level = "debug", skip_all)]
+async fn handle_inner(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    data: CommandData,
+) -> EmptyResult<()> {
+    debug!("Received Settings Command Interaction");
+
+    let command: SettingsCommand = this.parse_command(data)?;
+    let Some(guild_id) = inter.guild_id else {
+        warn!("Received Settings Command outside of a guild");
+        return Err(());
+    };
+    let locale: DiscordLocale = (&inter.locale).into();
+
+    let result = match command {
+        SettingsCommand::Show(_) => {
+            let settings = this.guild_settings(Some(guild_id)).await;
+            this.respond_with(&inter, &settings_overview(&settings, locale))
+                .await;
+            return Ok(());
+        }
+        SettingsCommand::Platform(cmd) => {
+            let platform = Platform::from(cmd.platform);
+            this.update_guild_settings(guild_id, |settings| {
+                settings.disabled_platforms.retain(|p| *p != platform);
+                if !cmd.enabled {
+                    settings.disabled_platforms.push(platform);
+                }
+            })
+            .await
+        }
+        SettingsCommand::Order(cmd) => {
+            let order = match parse_platform_order(cmd.platforms.as_deref().unwrap_or_default()) {
+                Ok(order) => order,
+                Err(name) => {
+                    debug!(name, "User passed an unknown platform, informing user");
+                    let message = format!("{}: {}", messages::unknown_platform(locale), name);
+                    this.respond_with(&inter, &message).await;
+                    return Err(());
+                }
+            };
+
+            this.update_guild_settings(guild_id, |settings| settings.platform_order = order)
+                .await
+        }
+        SettingsCommand::Player(cmd) => {
+            this.update_guild_settings(guild_id, |settings| settings.show_player = cmd.enabled)
+                .await
+        }
+        SettingsCommand::Visibility(cmd) => {
+            this.update_guild_settings(guild_id, |settings| {
+                settings.ephemeral_replies = cmd.private
+            })
+            .await
+        }
+        SettingsCommand::Reset(_) => {
+            this.update_guild_settings(guild_id, |settings| *settings = GuildSettings::default())
+                .await
+        }
+    };
+
+    let settings = match result {
+        Ok(settings) => settings,
+        Err(e) => {
+            warn!("Failed to update guild settings, informing user: {}", e);
+            this.respond_with(&inter, messages::error(locale)).await;
+            return Err(());
+        }
+    };
+
+    debug!(?settings, "Updated guild settings");
+    let message = format!(
+        "{}\n{}",
+        messages::settings_updated(locale),
+        settings_overview(&settings, locale)
+    );
+    this.respond_with(&inter, &message).await;
+
+    Ok(())
+}
+
+/// Parses a comma separated list of platform names, returning the first unknown name on failure
+fn parse_platform_order(platforms: &str) -> Result<Vec<Platform>, &str> {
+    let mut order = Vec::new();
+    for name in platforms.split(',').map(str::trim) {
+        if name.is_empty() {
+            continue;
+        }
+
+        let platform = Platform::from_name(name).ok_or(name)?;
+        if !order.contains(&platform) {
+            order.push(platform);
+        }
+    }
+
+    Ok(order)
+}
+
+fn settings_overview(settings: &GuildSettings, locale: DiscordLocale) -> String {
+    let join_platforms = |platforms: &[Platform], empty: &str| {
+        if platforms.is_empty() {
+            empty.to_string()
+        } else {
+            platforms.iter().join(", ")
+        }
+    };
+
+    let mut overview = String::new();
+    writeln!(
+        overview,
+        "**{}:** {}",
+        messages::settings_disabled_platforms(locale),
+        join_platforms(
+            &settings.disabled_platforms,
+            messages::settings_none(locale)
+        )
+    )
+    .expect("Writing to string should not fail");
+    writeln!(
+        overview,
+        "**{}:** {}",
+        messages::settings_platform_order(locale),
+        join_platforms(
+            &settings.platform_order,
+            messages::settings_alphabetical(locale)
+        )
+    )
+    .expect("Writing to string should not fail");
+    writeln!(
+        overview,
+        "**{}:** {}",
+        messages::settings_player(locale),
+        if settings.show_player {
+            messages::settings_shown(locale)
+        } else {
+            messages::settings_hidden(locale)
+        }
+    )
+    .expect("Writing to string should not fail");
+    write!(
+        overview,
+        "**{}:** {}",
+        messages::settings_visibility(locale),
+        if settings.ephemeral_replies {
+            messages::settings_private(locale)
+        } else {
+            messages::settings_public(locale)
+        }
+    )
+    .expect("Writing to string should not fail");
+
+    overview
+}
@@ -6,27 +6,39 @@
 use std::future::IntoFuture;
 
 use tracing::{Instrument, debug, debug_span, instrument, warn};
+use twilight_interactions::command::AutocompleteValue;
 use twilight_model::application::interaction::Interaction;
 use twilight_model::application::interaction::application_command::CommandData;
 use twilight_model::channel::message::MessageFlags;
 use url::Url;
 
-use crate::clients::odesli::ApiErr;
+use crate::clients::odesli::provider_id::ProviderId;
+use crate::clients::odesli::{AcquirePolicy, ApiErr, RequestPriority};
+use crate::clients::platforms::PlaylistId;
 use crate::db::UsageData;
 use crate::interactions::InteractionsHandler;
-use crate::interactions::commands::share::ShareCommand;
+use crate::interactions::commands::share::{ShareCommand, ShareCommandPartial};
+use crate::interactions::handlers::artist::handle_artist;
 use crate::interactions::handlers::common::{
-    InvalidLink, VALID_DOMAINS_REGEX, additional_link_validation, build_components,
+    InvalidLink, additional_link_validation, build_components,
 };
 use crate::interactions::handlers::messages;
+use crate::interactions::utils::{autocomplete_choice, interaction_deadline};
 use crate::util::EmptyResult;
 use crate::util::error::expect_warn;
 
+/// The number of recent shares suggested, Discord allows at most 25
+const AUTOCOMPLETE_LIMIT: usize = 10;
+
 impl InteractionsHandler {
     pub(super) async fn handle_share(&self, inter: Interaction, data: CommandData) {
         // use an inner function to make splitting the code easier
         let _ = handle_inner(self, inter, data).await;
     }
+
+    pub(super) async fn handle_share_autocomplete(&self, inter: Interaction, data: CommandData) {
+        let _ = handle_autocomplete(self, inter, data).await;
+    }
 }
 
 #[instrument(name = "share_command_handler", level = "debug", skip_all)]
@@ -38,12 +50,57 @@ async fn handle_inner(
     debug!("Received Share Command Interaction");
 
     let command = this.parse_command(data)?;
-    let url = validate_url(this, &inter, &command).await?;
+    let url = match validate_url(this, &inter, &command).await? {
+        ShareTarget::Link(url) => url,
+        ShareTarget::Playlist(id) => return handle_playlist(this, inter, id).await,
+        ShareTarget::Artist(url, id) => return handle_artist(this, inter, url, id).await,
+    };
 
     debug!("User passed valid arguments, deferring Response");
-    let defer_future = this.defer(&inter);
+    share_link(this, inter, url, true).await
+}
+
+/// Suggests links recently shared by the user or in the guild matching what was typed so far
+#[instrument(name = "share_autocomplete_handler", level = "debug", skip_all)]
+async fn handle_autocomplete(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    data: CommandData,
+) -> EmptyResult<()> {
+    let command: ShareCommandPartial = this.parse_command(data)?;
+
+    let (AutocompleteValue::Focused(input), Some(user_id)) = (command.url, inter.author_id())
+    else {
+        return this.respond_with_choices(&inter, Vec::new()).await;
+    };
+
+    let choices = this
+        .db()
+        .load_recent_shares(user_id, inter.guild_id, &input, AUTOCOMPLETE_LIMIT)
+        .await
+        .into_iter()
+        .filter_map(|share| autocomplete_choice(share.label(), share.original_url))
+        .collect();
+
+    this.respond_with_choices(&inter, choices).await
+}
 
-    let (data, entity, color) = match this.data_routine(&url).await {
+/// Fetches the links for the song or album and replies with the shared card.
+///
+/// The usage data is only recorded for links the user passed in directly.
+#[instrument(level = "debug", skip(this, inter))]
+pub(super) async fn share_link(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    url: Url,
+    record_usage: bool,
+) -> EmptyResult<()> {
+    let settings = this.guild_settings(inter.guild_id).await;
+    let defer_future = this.defer(&inter, settings.ephemeral_replies);
+
+    let policy =
+        AcquirePolicy::wait_until(RequestPriority::Interactive, interaction_deadline(&inter));
+    let (data, entity, color) = match this.data_routine(&url, policy).await {
         Ok(data) => data,
         Err(e) => {
             let message = match e {
@@ -54,6 +111,10 @@ async fn handle_inner(
                     );
                     messages::api_client_error_message(err, (&inter.locale).into())
                 }
+                ApiErr::RateLimitDeadlineExceeded => {
+                    warn!("Odesli rate limit token could not be acquired in time, informing user");
+                    messages::busy((&inter.locale).into())
+                }
                 _ => {
                     warn!("Odesli API request failed, informing user: {}", e);
                     messages::error((&inter.locale).into())
@@ -64,11 +125,18 @@ async fn handle_inner(
         }
     };
 
-    let usage_data =
-        UsageData::from_share_command(&inter, url, &data.page_url, &entity, data.is_cached);
+    let usage_data = UsageData::from_share_command(&inter, url, &data, &entity);
 
     // No need to pass an index since we only have one link, and thus one component
-    let components = build_components(&data, entity, color, None);
+    let components = build_components(
+        &data,
+        &this.page_url(&data),
+        entity,
+        color,
+        None,
+        (&inter.locale).into(),
+        &settings,
+    );
 
     defer_future
         .await
@@ -84,17 +152,73 @@ async fn handle_inner(
         .await
         .map_err(expect_warn!("Failed to send the response to the user"))?;
 
-    debug!("Successfully sent Response, spawning task to save command usage data to the database");
-    this.db().spawn_save_to_db(usage_data);
+    if record_usage {
+        debug!(
+            "Successfully sent Response, spawning task to save command usage data to the database"
+        );
+        this.db().spawn_save_to_db(usage_data);
+    } else {
+        debug!("Successfully sent Response");
+    }
 
     Ok(())
 }
 
+#[instrument(level = "debug", skip(this, inter))]
+async fn handle_playlist(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    id: PlaylistId,
+) -> EmptyResult<()> {
+    debug!("User shared a playlist, deferring Response");
+    let settings = this.guild_settings(inter.guild_id).await;
+    let defer_future = this.defer(&inter, settings.ephemeral_replies);
+
+    let components = match this
+        .build_playlist_page(&id, 0, (&inter.locale).into(), &settings)
+        .await
+    {
+        Ok(components) => components,
+        Err(e) => {
+            warn!("Failed to build playlist page, informing user: {}", e);
+            defer_future
+                .await
+                .map_err(expect_warn!("Failed to join the defer future"))?;
+            this.update_defer_with_error(&inter, e.message((&inter.locale).into()))
+                .await;
+            return Err(());
+        }
+    };
+
+    defer_future
+        .await
+        .map_err(expect_warn!("Failed to join the defer future"))?;
+
+    this.discord()
+        .interaction_client()
+        .update_response(inter.token.as_str())
+        .flags(MessageFlags::IS_COMPONENTS_V2)
+        .components(Some(&components))
+        .into_future()
+        .instrument(debug_span!("sending_response"))
+        .await
+        .map_err(expect_warn!("Failed to send the response to the user"))?;
+
+    debug!("Successfully sent Response");
+    Ok(())
+}
+
+enum ShareTarget {
+    Link(Url),
+    Playlist(PlaylistId),
+    Artist(Url, ProviderId),
+}
+
 async fn validate_url(
     this: &InteractionsHandler,
     inter: &Interaction,
     cmd: &ShareCommand,
-) -> EmptyResult<Url> {
+) -> EmptyResult<ShareTarget> {
     let url = match Url::parse(cmd.url.as_str()) {
         Ok(url) => url,
         Err(_) => {
@@ -105,20 +229,22 @@ async fn validate_url(
         }
     };
 
-    match url.domain() {
-        Some(domain) if VALID_DOMAINS_REGEX.is_match(domain) => (),
-        _ => {
-            debug!("URL domain is not supported, informing user");
-            this.respond_with(inter, messages::invalid_url((&inter.locale).into()))
-                .await;
-            return Err(());
-        }
-    }
+    let Some(url) = this.expand_link(&url).await else {
+        debug!("URL domain is not supported, informing user");
+        this.respond_with(inter, messages::invalid_url((&inter.locale).into()))
+            .await;
+        return Err(());
+    };
 
     if let Err(reason) = additional_link_validation(&url) {
         match reason {
             InvalidLink::Playlist => {
-                debug!("URL is a playlist, informing user");
+                if let Some(id) = this.supported_playlist(&url) {
+                    debug!(playlist = %id, "URL is a supported playlist");
+                    return Ok(ShareTarget::Playlist(id));
+                }
+
+                debug!("URL is an unsupported playlist, informing user");
                 this.respond_with(
                     inter,
                     messages::playlist_not_supported((&inter.locale).into()),
@@ -126,7 +252,12 @@ async fn validate_url(
                 .await;
             }
             InvalidLink::Artist => {
-                debug!("URL is an artist, informing user");
+                if let Some(id) = this.supported_artist(&url) {
+                    debug!(artist = %id, "URL is a supported artist");
+                    return Ok(ShareTarget::Artist(url, id));
+                }
+
+                debug!("URL is an unsupported artist, informing user");
                 this.respond_with(
                     inter,
                     messages::artist_not_supported((&inter.locale).into()),
@@ -146,5 +277,5 @@ async fn validate_url(
     }
 
     debug!(url = %url, "Successfully validated URL, proceeding to fetch data from Odesli API");
-    Ok(url)
+    Ok(ShareTarget::Link(url))
 }
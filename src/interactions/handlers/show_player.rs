@@ -13,9 +13,11 @@ use twilight_util::builder::message::{
 };
 
 use crate::clients::odesli::{OdesliResponse, Platform};
+use crate::db::GuildSettings;
 use crate::interactions::InteractionsHandler;
 use crate::interactions::handlers::messages;
 use crate::util::EmptyResult;
+use crate::util::discord_locales::DiscordLocale;
 
 pub const SELECT_ID: &str = "odesli_select";
 
@@ -26,19 +28,25 @@ pub const EMBEDDABLE_PLATFORMS: &[Platform] = &[
     Platform::YouTube,
 ];
 
-pub fn build_select_menu(data: &OdesliResponse, idx: Option<u16>) -> Option<Component> {
+pub fn build_select_menu(
+    data: &OdesliResponse,
+    idx: Option<u16>,
+    locale: DiscordLocale,
+    settings: &GuildSettings,
+) -> Option<Component> {
     let custom_id = idx
         .map(|i| format!("{}_{}", SELECT_ID, i))
         .unwrap_or_else(|| SELECT_ID.to_string());
 
-    let mut select_menu =
-        SelectMenuBuilder::new(custom_id, SelectMenuType::Text).placeholder("Show Embedded Player");
+    let mut select_menu = SelectMenuBuilder::new(custom_id, SelectMenuType::Text)
+        .placeholder(messages::show_embedded_player(locale));
 
     let mut has_options = false;
     for (platform, links) in data
         .links_by_platform
         .iter()
         .filter(|(platform, _)| EMBEDDABLE_PLATFORMS.contains(platform))
+        .filter(|(platform, _)| settings.is_platform_enabled(platform))
     {
         let value = if links.url.len() <= 100 {
             links.url.clone()
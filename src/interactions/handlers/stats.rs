@@ -0,0 +1,453 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::fmt::Write;
+use std::future::IntoFuture;
+
+use tracing::{Instrument, debug, debug_span, instrument, warn};
+use twilight_model::application::interaction::Interaction;
+use twilight_model::application::interaction::application_command::CommandData;
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::channel::message::component::ButtonStyle;
+use twilight_model::channel::message::{AllowedMentions, Component, MessageFlags};
+use twilight_model::id::Id;
+use twilight_util::builder::message::{
+    ActionRowBuilder, ButtonBuilder, ContainerBuilder, SeparatorBuilder, TextDisplayBuilder,
+};
+
+use crate::db::{StatsEntry, StatsKind, StatsScope, StatsSubject};
+use crate::interactions::InteractionsHandler;
+use crate::interactions::commands::stats::{StatsCommand, StatsPeriodOption};
+use crate::interactions::handlers::messages;
+use crate::util::EmptyResult;
+use crate::util::discord_locales::DiscordLocale;
+use crate::util::error::expect_warn;
+
+pub const PAGE_BUTTON_ID: &str = "stats_page";
+
+/// The number of entries shown per page
+const PAGE_SIZE: usize = 10;
+
+/// Everything needed to rebuild a page of statistics, encoded in the custom ID of the buttons
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StatsView {
+    kind: StatsKind,
+    scope: StatsScope,
+    period: StatsPeriodOption,
+}
+
+impl StatsView {
+    fn title(&self, locale: DiscordLocale) -> &'static str {
+        match (self.scope, self.kind) {
+            (StatsScope::User(_), _) => messages::stats_my_shares(locale),
+            (_, StatsKind::Tracks) => messages::stats_top_tracks(locale),
+            (_, StatsKind::Artists) => messages::stats_top_artists(locale),
+            (_, StatsKind::Sharers) => messages::stats_top_sharers(locale),
+        }
+    }
+}
+
+fn period_name(period: StatsPeriodOption, locale: DiscordLocale) -> &'static str {
+    match period {
+        StatsPeriodOption::Week => messages::stats_period_week(locale),
+        StatsPeriodOption::Month => messages::stats_period_month(locale),
+        StatsPeriodOption::Year => messages::stats_period_year(locale),
+        StatsPeriodOption::AllTime => messages::stats_period_all_time(locale),
+    }
+}
+
+fn page_custom_id(page: usize, view: &StatsView) -> String {
+    let kind = match view.kind {
+        StatsKind::Tracks => "tracks",
+        StatsKind::Artists => "artists",
+        StatsKind::Sharers => "sharers",
+    };
+    let scope = match view.scope {
+        StatsScope::Guild(id) => format!("g{}", id),
+        StatsScope::User(id) => format!("u{}", id),
+    };
+
+    format!(
+        "{}:{}:{}:{}:{}",
+        PAGE_BUTTON_ID,
+        page,
+        kind,
+        view.period.value(),
+        scope
+    )
+}
+
+fn parse_page_custom_id(custom_id: &str) -> Option<(usize, StatsView)> {
+    let rest = custom_id.strip_prefix(PAGE_BUTTON_ID)?.strip_prefix(':')?;
+    let mut parts = rest.split(':');
+
+    let page = parts.next()?.parse().ok()?;
+    let kind = match parts.next()? {
+        "tracks" => StatsKind::Tracks,
+        "artists" => StatsKind::Artists,
+        "sharers" => StatsKind::Sharers,
+        _ => return None,
+    };
+    let period = match parts.next()? {
+        "week" => StatsPeriodOption::Week,
+        "month" => StatsPeriodOption::Month,
+        "year" => StatsPeriodOption::Year,
+        "all" => StatsPeriodOption::AllTime,
+        _ => return None,
+    };
+    let scope = parts.next()?;
+    let scope = if let Some(id) = scope.strip_prefix('g') {
+        StatsScope::Guild(Id::new_checked(id.parse().ok()?)?)
+    } else {
+        StatsScope::User(Id::new_checked(scope.strip_prefix('u')?.parse().ok()?)?)
+    };
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some((
+        page,
+        StatsView {
+            kind,
+            scope,
+            period,
+        },
+    ))
+}
+
+impl InteractionsHandler {
+    pub(super) async fn handle_stats(&self, inter: Interaction, data: CommandData) {
+        // use an inner function to make splitting the code easier
+        let _ = handle_inner(self, inter, data).await;
+    }
+
+    pub(super) async fn handle_stats_page(
+        &self,
+        inter: Interaction,
+        data: MessageComponentInteractionData,
+    ) {
+        let _ = handle_page_inner(self, inter, data).await;
+    }
+
+    /// Builds the components for the given page of statistics, `None` if there is nothing to show
+    #[instrument(level = "debug", skip(self, locale))]
+    async fn build_stats_page(
+        &self,
+        view: &StatsView,
+        page: usize,
+        locale: DiscordLocale,
+    ) -> EmptyResult<Option<Vec<Component>>> {
+        let stats = self
+            .db()
+            .load_stats(
+                view.kind,
+                view.scope,
+                view.period.max_age(),
+                page * PAGE_SIZE,
+                PAGE_SIZE,
+            )
+            .await
+            .map_err(expect_warn!("Failed to load the statistics"))?;
+
+        if stats.entries.is_empty() && page == 0 {
+            return Ok(None);
+        }
+
+        let mut container = ContainerBuilder::new().component(
+            TextDisplayBuilder::new(format!(
+                "## {}\n-# {}",
+                view.title(locale),
+                period_name(view.period, locale)
+            ))
+            .build(),
+        );
+        container = container.component(SeparatorBuilder::new().build());
+
+        let mut list = String::new();
+        for (i, entry) in stats.entries.iter().enumerate() {
+            if i > 0 {
+                list.push('\n');
+            }
+            write!(
+                list,
+                "**{}.** {}",
+                page * PAGE_SIZE + i + 1,
+                entry_details(entry)
+            )
+            .expect("Writing to string should not fail");
+        }
+        if !list.is_empty() {
+            container = container.component(TextDisplayBuilder::new(list).build());
+        }
+
+        if page > 0 || stats.has_next {
+            let previous = ButtonBuilder::new(ButtonStyle::Secondary)
+                .custom_id(page_custom_id(page.saturating_sub(1), view))
+                .label(messages::previous_page(locale))
+                .disabled(page == 0)
+                .build();
+            let next = ButtonBuilder::new(ButtonStyle::Secondary)
+                .custom_id(page_custom_id(page + 1, view))
+                .label(messages::next_page(locale))
+                .disabled(!stats.has_next)
+                .build();
+
+            container = container.component(
+                ActionRowBuilder::new()
+                    .component(previous)
+                    .component(next)
+                    .build(),
+            );
+        }
+
+        Ok(Some(vec![container.build().into()]))
+    }
+
+    async fn send_stats_page(
+        &self,
+        inter: &Interaction,
+        components: &[Component],
+    ) -> EmptyResult<()> {
+        // Sharers are listed as mentions, these must not ping anyone
+        self.discord()
+            .interaction_client()
+            .update_response(inter.token.as_str())
+            .flags(MessageFlags::IS_COMPONENTS_V2)
+            .components(Some(components))
+            .allowed_mentions(Some(&AllowedMentions::default()))
+            .into_future()
+            .instrument(debug_span!("sending_response"))
+            .await
+            .map_err(expect_warn!("Failed to send the response to the user"))?;
+
+        Ok(())
+    }
+}
+
+#[instrument(name = "stats_command_handler", level = "debug", skip_all)]
+async fn handle_inner(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    data: CommandData,
+) -> EmptyResult<()> {
+    debug!("Received Stats Command Interaction");
+
+    let command: StatsCommand = this.parse_command(data)?;
+    let locale: DiscordLocale = (&inter.locale).into();
+
+    let (kind, period) = match command {
+        StatsCommand::Tracks(cmd) => (Some(StatsKind::Tracks), cmd.period),
+        StatsCommand::Artists(cmd) => (Some(StatsKind::Artists), cmd.period),
+        StatsCommand::Sharers(cmd) => (Some(StatsKind::Sharers), cmd.period),
+        StatsCommand::Me(cmd) => (None, cmd.period),
+    };
+    let period = period.unwrap_or(StatsPeriodOption::Month);
+
+    let (view, ephemeral) = match kind {
+        Some(kind) => {
+            let Some(guild_id) = inter.guild_id else {
+                debug!("Received guild Stats Command outside of a guild, informing user");
+                this.respond_with(&inter, messages::stats_guild_only(locale))
+                    .await;
+                return Ok(());
+            };
+
+            let settings = this.guild_settings(Some(guild_id)).await;
+            let view = StatsView {
+                kind,
+                scope: StatsScope::Guild(guild_id),
+                period,
+            };
+            (view, settings.ephemeral_replies)
+        }
+        None => {
+            let Some(user_id) = inter.author_id() else {
+                warn!("Received Stats Command without an author");
+                return Err(());
+            };
+
+            let view = StatsView {
+                kind: StatsKind::Tracks,
+                scope: StatsScope::User(user_id),
+                period,
+            };
+            (view, true)
+        }
+    };
+
+    let defer_future = this.defer(&inter, ephemeral);
+    let result = this.build_stats_page(&view, 0, locale).await;
+
+    defer_future
+        .await
+        .map_err(expect_warn!("Failed to join the defer future"))?;
+
+    match result {
+        Ok(Some(components)) => this.send_stats_page(&inter, &components).await,
+        Ok(None) => {
+            debug!("No statistics available, informing user");
+            this.update_defer_with_error(&inter, messages::stats_empty(locale))
+                .await;
+            Ok(())
+        }
+        Err(()) => {
+            this.update_defer_with_error(&inter, messages::error(locale))
+                .await;
+            Err(())
+        }
+    }
+}
+
+#[instrument(name = "stats_page_handler", level = "debug", skip_all)]
+async fn handle_page_inner(
+    this: &InteractionsHandler,
+    inter: Interaction,
+    data: MessageComponentInteractionData,
+) -> EmptyResult<()> {
+    debug!("Received Stats Page Button Interaction");
+    let locale: DiscordLocale = (&inter.locale).into();
+
+    let Some((page, view)) = parse_page_custom_id(&data.custom_id) else {
+        warn!(
+            custom_id = data.custom_id,
+            "Received stats page button with invalid custom_id"
+        );
+        this.respond_with(&inter, messages::error(locale)).await;
+        return Err(());
+    };
+
+    let defer_future = this.defer_update(&inter);
+    let result = this.build_stats_page(&view, page, locale).await;
+
+    defer_future
+        .await
+        .map_err(expect_warn!("Failed to join the defer future"))?;
+
+    match result {
+        Ok(Some(components)) => this.send_stats_page(&inter, &components).await,
+        Ok(None) => {
+            this.followup_with_error(&inter, messages::stats_empty(locale))
+                .await;
+            Ok(())
+        }
+        Err(()) => {
+            this.followup_with_error(&inter, messages::error(locale))
+                .await;
+            Err(())
+        }
+    }
+}
+
+fn entry_details(entry: &StatsEntry) -> String {
+    let subject = match &entry.subject {
+        StatsSubject::Track { url, title, artist } => match (title, artist) {
+            (Some(title), Some(artist)) => format!(
+                "[{} \u{2014} {}]({})",
+                escape_markdown(title),
+                escape_markdown(artist),
+                url
+            ),
+            (Some(title), None) => format!("[{}]({})", escape_markdown(title), url),
+            _ => format!("<{}>", url),
+        },
+        StatsSubject::Artist(artist) => escape_markdown(artist),
+        StatsSubject::Sharer(user_id) => format!("<@{}>", user_id),
+    };
+
+    format!("{} ({})", subject, entry.shares)
+}
+
+/// Escapes the characters Discord interprets as markdown, so titles and artists are shown as is
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '*' | '_' | '`' | '~' | '|' | '[' | ']' | '(' | ')' | '<' | '>' | '#'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_custom_id_round_trip() {
+        let guild = StatsScope::Guild(Id::new(81384788765712384));
+        let user = StatsScope::User(Id::new(80351110224678912));
+
+        let kinds = [StatsKind::Tracks, StatsKind::Artists, StatsKind::Sharers];
+        let periods = [
+            StatsPeriodOption::Week,
+            StatsPeriodOption::Month,
+            StatsPeriodOption::Year,
+            StatsPeriodOption::AllTime,
+        ];
+        let views = kinds.into_iter().flat_map(|kind| {
+            periods.into_iter().flat_map(move |period| {
+                [guild, user].map(|scope| StatsView {
+                    kind,
+                    scope,
+                    period,
+                })
+            })
+        });
+
+        for view in views {
+            for page in [0, 1, 42] {
+                let custom_id = page_custom_id(page, &view);
+                assert!(custom_id.len() <= 100, "{custom_id}");
+                assert_eq!(parse_page_custom_id(&custom_id), Some((page, view)));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_page_custom_ids() {
+        for custom_id in [
+            "stats_page",
+            "stats_page:1:tracks:week",
+            "stats_page:x:tracks:week:g1",
+            "stats_page:1:songs:week:g1",
+            "stats_page:1:tracks:day:g1",
+            "stats_page:1:tracks:week:x1",
+            "stats_page:1:tracks:week:g0",
+            "stats_page:1:tracks:week:g1:extra",
+            "find_links_page:1:tracks:week:g1",
+        ] {
+            assert_eq!(parse_page_custom_id(custom_id), None, "{custom_id}");
+        }
+    }
+
+    #[test]
+    fn escapes_markdown_in_entries() {
+        let entry = StatsEntry {
+            subject: StatsSubject::Track {
+                url: "https://open.spotify.com/track/1".to_string(),
+                title: Some("[Intro] *Live* (feat. _someone_)".to_string()),
+                artist: Some("`Artist` | Band".to_string()),
+            },
+            shares: 3,
+        };
+        assert_eq!(
+            entry_details(&entry),
+            "[\\[Intro\\] \\*Live\\* \\(feat. \\_someone\\_\\) \u{2014} \\`Artist\\` \\| Band]\
+             (https://open.spotify.com/track/1) (3)"
+        );
+
+        let entry = StatsEntry {
+            subject: StatsSubject::Artist("**NSYNC".to_string()),
+            shares: 1,
+        };
+        assert_eq!(entry_details(&entry), "\\*\\*NSYNC (1)");
+    }
+}
@@ -13,6 +13,7 @@ use twilight_util::builder::embed::{EmbedBuilder, ImageSource};
 use crate::clients::colour::RGBPixel;
 use crate::interactions::InteractionsHandler;
 use crate::interactions::commands::test_colour_consts::TestColorConstsCommand;
+use crate::interactions::utils::interaction_deadline;
 use crate::util::EmptyResult;
 use crate::util::error::expect_warn;
 
@@ -44,12 +45,16 @@ async fn handle_inner(
     };
 
     debug!("Deferring Response");
-    let defer_future = this.defer(&inter);
+    let defer_future = this.defer(&inter, false);
 
     debug!("Fetching Dominant Colour of Image");
     let colour = this
         .image()
-        .get_dominant_colour_from_url(&command.url, (&command).into())
+        .get_dominant_colour_from_url(
+            &command.url,
+            (&command).into(),
+            Some(interaction_deadline(&inter)),
+        )
         .await
         .ok();
 
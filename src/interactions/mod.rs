@@ -1,5 +1,6 @@
 use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
 
 use metronomos_pulse::error::BuildDependencyError;
 use metronomos_pulse::value::{ArcValue, PulseValue};
@@ -10,14 +11,32 @@ use twilight_model::application::interaction::application_command::CommandData;
 use crate::args::Args;
 use crate::clients::colour::ImageClient;
 use crate::clients::discord::DiscordClient;
+use crate::clients::expander::LinkExpander;
 use crate::clients::odesli::OdesliClient;
+use crate::clients::platforms::PlatformsClient;
+use crate::clients::resolver::ResolverClient;
+use crate::clients::search::SearchClient;
 use crate::db::Database;
+use crate::interactions::handlers::{AutoShareState, GuildSettingsCache, UserPreferencesCache};
 use crate::util::error::ExpectErr;
 
 mod commands;
 mod handlers;
+
+pub use handlers::is_supported_domain;
 mod utils;
 
+/// The keys that have to be defined in the localisation catalogue, grouped by where they are used
+pub const LOCALISATION_KEYS: &[&[&str]] = &[
+    handlers::MESSAGE_KEYS,
+    commands::auto_share::KEYS,
+    commands::preferences::KEYS,
+    commands::search::KEYS,
+    commands::settings::KEYS,
+    commands::share::KEYS,
+    commands::stats::KEYS,
+];
+
 #[derive(Clone, PulseValue)]
 pub struct InteractionsHandler {
     inner: Arc<InteractionsHandlerInner>,
@@ -29,7 +48,14 @@ struct InteractionsHandlerInner {
     db: Database,
     discord: DiscordClient,
     odesli: OdesliClient,
+    resolver: ResolverClient,
+    platforms: PlatformsClient,
+    search: SearchClient,
     image: ImageClient,
+    expander: LinkExpander,
+    auto_share: AutoShareState,
+    settings: GuildSettingsCache,
+    preferences: UserPreferencesCache,
 }
 
 impl fmt::Debug for InteractionsHandler {
@@ -39,26 +65,46 @@ impl fmt::Debug for InteractionsHandler {
             .field("db", &self.inner.db)
             .field("discord", &self.inner.discord)
             .field("odesli", &self.inner.odesli)
+            .field("resolver", &self.inner.resolver)
+            .field("platforms", &self.inner.platforms)
+            .field("search", &self.inner.search)
             .field("image", &self.inner.image)
+            .field("expander", &self.inner.expander)
+            .field("auto_share", &self.inner.auto_share)
+            .field("settings", &self.inner.settings)
+            .field("preferences", &self.inner.preferences)
             .finish()
     }
 }
 
 impl InteractionsHandler {
     #[instrument(name = "init_interactions_handler", skip_all)]
+    #[allow(clippy::too_many_arguments)] // all of these are injected dependencies
     pub async fn init(
         args: ArcValue<Args>,
         db: Database,
         discord: DiscordClient,
         odesli: OdesliClient,
+        resolver: ResolverClient,
+        platforms: PlatformsClient,
+        search: SearchClient,
         image: ImageClient,
+        expander: LinkExpander,
     ) -> Result<Self, BuildDependencyError> {
+        let auto_share = AutoShareState::new(Duration::from_secs(args.auto_share_cooldown));
         let inner = InteractionsHandlerInner {
             args,
             db,
             discord,
             odesli,
+            resolver,
+            platforms,
+            search,
             image,
+            expander,
+            auto_share,
+            settings: GuildSettingsCache::default(),
+            preferences: UserPreferencesCache::default(),
         };
 
         let res = Self {
@@ -90,8 +136,43 @@ impl InteractionsHandler {
         &self.inner.odesli
     }
 
+    #[inline]
+    fn resolver(&self) -> &ResolverClient {
+        &self.inner.resolver
+    }
+
+    #[inline]
+    fn platforms(&self) -> &PlatformsClient {
+        &self.inner.platforms
+    }
+
+    #[inline]
+    fn search(&self) -> &SearchClient {
+        &self.inner.search
+    }
+
     #[inline]
     fn image(&self) -> &ImageClient {
         &self.inner.image
     }
+
+    #[inline]
+    fn expander(&self) -> &LinkExpander {
+        &self.inner.expander
+    }
+
+    #[inline]
+    fn auto_share(&self) -> &AutoShareState {
+        &self.inner.auto_share
+    }
+
+    #[inline]
+    fn settings_cache(&self) -> &GuildSettingsCache {
+        &self.inner.settings
+    }
+
+    #[inline]
+    fn preferences_cache(&self) -> &UserPreferencesCache {
+        &self.inner.preferences
+    }
 }
@@ -1,20 +1,45 @@
 use std::future::pending;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use tokio::task::JoinHandle;
 use tokio::time;
-use tracing::{Instrument, debug_span, warn};
+use tokio::time::Instant;
+use tracing::{Instrument, debug, debug_span, warn};
 use twilight_interactions::command::CommandModel;
+use twilight_model::application::command::{CommandOptionChoice, CommandOptionChoiceValue};
 use twilight_model::application::interaction::Interaction;
 use twilight_model::application::interaction::application_command::CommandData;
 use twilight_model::channel::message::MessageFlags;
 use twilight_model::http::interaction::{InteractionResponse, InteractionResponseType};
 use twilight_util::builder::InteractionResponseDataBuilder;
 
+use crate::constants::DISCORD_EPOCH;
 use crate::interactions::InteractionsHandler;
 use crate::util::EmptyResult;
 use crate::util::error::expect_warn;
 
+/// Discord limits the length of choice names and values
+const CHOICE_MAX_LEN: usize = 100;
+
+/// Interaction tokens can be used for 15 minutes after the interaction was created
+const INTERACTION_TOKEN_LIFETIME: Duration = Duration::from_mins(15);
+/// Time left to build and send the response once the data was fetched
+const RESPONSE_MARGIN: Duration = Duration::from_secs(30);
+
+/// The point in time the data for the response has to be fetched by, so it can still be sent
+/// with the token of the interaction
+pub fn interaction_deadline(inter: &Interaction) -> Instant {
+    let created = UNIX_EPOCH + Duration::from_millis((inter.id.get() >> 22) + DISCORD_EPOCH);
+    let elapsed = SystemTime::now()
+        .duration_since(created)
+        .unwrap_or_default();
+
+    Instant::now()
+        + INTERACTION_TOKEN_LIFETIME
+            .saturating_sub(RESPONSE_MARGIN)
+            .saturating_sub(elapsed)
+}
+
 impl InteractionsHandler {
     pub fn parse_command<C: CommandModel>(&self, data: CommandData) -> EmptyResult<C> {
         match C::from_interaction(data.into()).map_err(expect_warn!(
@@ -32,7 +57,28 @@ impl InteractionsHandler {
         }
     }
 
-    pub fn defer(&self, inter: &Interaction) -> JoinHandle<()> {
+    /// Defers the response, if `ephemeral` is set the response is only visible to the user
+    pub fn defer(&self, inter: &Interaction, ephemeral: bool) -> JoinHandle<()> {
+        let flags = ephemeral.then_some(MessageFlags::EPHEMERAL);
+        self.defer_with(
+            inter,
+            InteractionResponseType::DeferredChannelMessageWithSource,
+            flags,
+        )
+    }
+
+    /// Defers a component interaction, the original message can then be edited with
+    /// `update_response`.
+    pub fn defer_update(&self, inter: &Interaction) -> JoinHandle<()> {
+        self.defer_with(inter, InteractionResponseType::DeferredUpdateMessage, None)
+    }
+
+    fn defer_with(
+        &self,
+        inter: &Interaction,
+        kind: InteractionResponseType,
+        flags: Option<MessageFlags>,
+    ) -> JoinHandle<()> {
         let inter_id = inter.id;
         let inter_token = inter.token.clone();
         let this = self.clone();
@@ -46,8 +92,10 @@ impl InteractionsHandler {
                         inter_id,
                         inter_token.as_str(),
                         &InteractionResponse {
-                            kind: InteractionResponseType::DeferredChannelMessageWithSource,
-                            data: None,
+                            kind,
+                            data: flags.map(|flags| {
+                                InteractionResponseDataBuilder::new().flags(flags).build()
+                            }),
                         },
                     )
                     .await
@@ -79,6 +127,49 @@ impl InteractionsHandler {
             .map_err(expect_warn!("Failed to respond to the Interaction"));
     }
 
+    /// Responds to an autocomplete interaction with the suggestions
+    pub async fn respond_with_choices(
+        &self,
+        inter: &Interaction,
+        choices: Vec<CommandOptionChoice>,
+    ) -> EmptyResult<()> {
+        self.discord()
+            .interaction_client()
+            .create_response(
+                inter.id,
+                inter.token.as_str(),
+                &InteractionResponse {
+                    kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+                    data: Some(
+                        InteractionResponseDataBuilder::new()
+                            .choices(choices)
+                            .build(),
+                    ),
+                },
+            )
+            .await
+            .map_err(expect_warn!(
+                "Failed to respond to the Autocomplete Interaction"
+            ))?;
+
+        Ok(())
+    }
+
+    /// Informs the user of an error with an ephemeral followup message, used for deferred
+    /// component interactions where the original message should stay untouched.
+    pub async fn followup_with_error(&self, inter: &Interaction, msg: &str) {
+        let _ = self
+            .discord()
+            .interaction_client()
+            .create_followup(inter.token.as_str())
+            .content(msg)
+            .flags(MessageFlags::EPHEMERAL)
+            .into_future()
+            .instrument(debug_span!("sending_error_message"))
+            .await
+            .map_err(expect_warn!("Failed to inform user of the error"));
+    }
+
     pub async fn update_defer_with_error(&self, inter: &Interaction, msg: &str) {
         if self
             .discord()
@@ -111,3 +202,27 @@ impl InteractionsHandler {
         }
     }
 }
+
+/// Builds an autocomplete suggestion, truncating the name to the length Discord allows.
+///
+/// Returns `None` if the value is too long, since it can't be truncated without changing it.
+pub fn autocomplete_choice(name: String, value: String) -> Option<CommandOptionChoice> {
+    if value.len() > CHOICE_MAX_LEN {
+        debug!(value, "Value is too long for a choice, skipping");
+        return None;
+    }
+
+    let name = if name.chars().count() > CHOICE_MAX_LEN {
+        let mut name: String = name.chars().take(CHOICE_MAX_LEN - 1).collect();
+        name.push('\u{2026}');
+        name
+    } else {
+        name
+    };
+
+    Some(CommandOptionChoice {
+        name,
+        name_localizations: None,
+        value: CommandOptionChoiceValue::String(value),
+    })
+}
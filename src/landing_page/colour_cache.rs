@@ -0,0 +1,86 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+
+/// Small LRU cache of the dominant colours of thumbnails, so rendering a page doesn't download
+/// its thumbnail every time
+pub struct ColourCache {
+    capacity: usize,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// The colour of each thumbnail URL and the tick it was last used at
+    entries: HashMap<String, (u32, u64)>,
+    tick: u64,
+}
+
+impl ColourCache {
+    pub fn new(capacity: usize) -> Self {
+        ColourCache {
+            capacity,
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    pub fn get(&self, thumbnail_url: &str) -> Option<u32> {
+        let mut inner = self.inner.lock();
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        let (colour, last_used) = inner.entries.get_mut(thumbnail_url)?;
+        *last_used = tick;
+        Some(*colour)
+    }
+
+    pub fn insert(&self, thumbnail_url: String, colour: u32) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut inner = self.inner.lock();
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        if inner.entries.len() >= self.capacity
+            && !inner.entries.contains_key(&thumbnail_url)
+            && let Some(oldest) = inner
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(url, _)| url.clone())
+        {
+            inner.entries.remove(&oldest);
+        }
+        inner.entries.insert(thumbnail_url, (colour, tick));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_the_least_recently_used_colour() {
+        let cache = ColourCache::new(2);
+        cache.insert("a".to_string(), 1);
+        cache.insert("b".to_string(), 2);
+        assert_eq!(cache.get("a"), Some(1));
+
+        cache.insert("c".to_string(), 3);
+        assert_eq!(cache.get("a"), Some(1));
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("c"), Some(3));
+
+        // Replacing an entry doesn't evict another one
+        cache.insert("c".to_string(), 4);
+        assert_eq!(cache.get("a"), Some(1));
+        assert_eq!(cache.get("c"), Some(4));
+    }
+}
@@ -0,0 +1,307 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::{Path, State as AxumState};
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::MethodRouter;
+use metronomos_pulse::value::{ArcValue, ValueGroupEntry};
+use parking_lot::Mutex;
+use tracing::{debug, instrument, warn};
+use url::Url;
+
+use crate::args::Args;
+use crate::clients::colour::ImageClient;
+use crate::clients::odesli::OdesliClient;
+use crate::clients::odesli::provider_id::ProviderId;
+use crate::db::Database;
+use crate::http_server::HttpServeRoute;
+use crate::landing_page::colour_cache::ColourCache;
+use crate::landing_page::render::{LandingPage, PageLink};
+
+mod colour_cache;
+mod render;
+
+/// How many pages per second may be looked up in the database when their response isn't cached
+const DB_LOOKUPS_PER_SECOND: u32 = 10;
+/// How many thumbnail colours are kept in memory
+const COLOUR_CACHE_CAPACITY: usize = 512;
+
+struct LandingPageState {
+    args: ArcValue<Args>,
+    odesli: OdesliClient,
+    database: Database,
+    image: ImageClient,
+    colours: ColourCache,
+    db_lookups: LookupBudget,
+}
+
+/// Limits the number of database lookups per window, since anyone can request the page of
+/// arbitrary keys
+struct LookupBudget {
+    limit: u32,
+    window: Duration,
+    /// The start of the current window and the lookups made in it
+    used: Mutex<(Instant, u32)>,
+}
+
+impl LookupBudget {
+    fn new(limit: u32, window: Duration) -> Self {
+        LookupBudget {
+            limit,
+            window,
+            used: Mutex::new((Instant::now(), 0)),
+        }
+    }
+
+    fn try_acquire(&self) -> bool {
+        let mut used = self.used.lock();
+        let now = Instant::now();
+        if now.duration_since(used.0) >= self.window {
+            *used = (now, 0);
+        }
+
+        if used.1 >= self.limit {
+            return false;
+        }
+        used.1 += 1;
+        true
+    }
+}
+
+enum PageErr {
+    NotFound,
+    Busy,
+}
+
+impl LandingPageState {
+    /// Builds the page from the cached response, falling back to the last time the entity was
+    /// shared if the response is no longer cached
+    async fn build_page(&self, provider_id: &ProviderId) -> Result<LandingPage, PageErr> {
+        if let Some(response) = self.odesli.fetch_cached(provider_id).await {
+            debug!("Rendering landing page from cached response");
+            let data = response.get_data();
+
+            let colour = match &data.thumbnail_url {
+                Some(url) => self.thumbnail_colour(url).await,
+                None => None,
+            };
+
+            let mut links = response
+                .links_by_platform
+                .iter()
+                .filter(|(platform, _)| platform.is_enabled())
+                .map(|(platform, link)| PageLink {
+                    platform: platform.to_string(),
+                    url: link.url.clone(),
+                })
+                .collect::<Vec<_>>();
+            links.sort_by(|a, b| a.platform.cmp(&b.platform));
+
+            return Ok(LandingPage {
+                kind: data.kind,
+                title: data.title,
+                artist_name: data.artist_name,
+                thumbnail_url: data.thumbnail_url,
+                colour,
+                links,
+                page_url: self.page_url(provider_id),
+            });
+        }
+
+        if !self.db_lookups.try_acquire() {
+            warn!("Too many landing page lookups in the database, rejecting request");
+            return Err(PageErr::Busy);
+        }
+        let share = self
+            .database
+            .find_share(provider_id)
+            .await
+            .ok_or(PageErr::NotFound)?;
+        debug!("Rendering landing page from usage data");
+
+        // The entity may have been shared with a link to any platform
+        let original_platform = Url::parse(&share.original_url)
+            .ok()
+            .and_then(|url| ProviderId::parse_url(&url).ok())
+            .map_or_else(|| provider_id.platform(), |id| id.platform());
+
+        Ok(LandingPage {
+            kind: share.kind,
+            title: Some(share.title),
+            artist_name: share.artist,
+            thumbnail_url: None,
+            colour: None,
+            links: vec![
+                PageLink {
+                    platform: original_platform.to_string(),
+                    url: share.original_url,
+                },
+                PageLink {
+                    platform: "Songlink".to_string(),
+                    url: share.song_link_url,
+                },
+            ],
+            page_url: self.page_url(provider_id),
+        })
+    }
+
+    /// The dominant colour of the thumbnail, only downloaded if it isn't cached yet
+    async fn thumbnail_colour(&self, url: &String) -> Option<u32> {
+        if let Some(colour) = self.colours.get(url) {
+            return Some(colour);
+        }
+
+        let colour = self
+            .image
+            .get_dominant_colour_from_url(url, Default::default(), None)
+            .await
+            .ok()?
+            .to_hex();
+        self.colours.insert(url.to_string(), colour);
+        Some(colour)
+    }
+
+    fn page_url(&self, provider_id: &ProviderId) -> Option<String> {
+        self.args
+            .public_url
+            .as_ref()
+            .map(|base| landing_page_url(base, provider_id))
+    }
+}
+
+/// The path of the landing pages, relative to the public URL
+const LANDING_PAGE_PATH: &str = "/l/{key}";
+
+/// The public URL of the landing page of the entity
+///
+/// The key is a single, percent-encoded path segment, since the slugs of some platforms contain
+/// slashes
+pub fn landing_page_url(base: &Url, provider_id: &ProviderId) -> String {
+    let mut url = base.clone();
+    match url.path_segments_mut() {
+        Ok(mut segments) => {
+            segments
+                .pop_if_empty()
+                .extend(["l", &provider_id.to_string()]);
+        }
+        Err(()) => return format!("{}/l/{}", base.as_str().trim_end_matches('/'), provider_id),
+    }
+    url.into()
+}
+
+#[instrument(level = "debug", skip(state))]
+async fn landing_page_handler(
+    AxumState(state): AxumState<Arc<LandingPageState>>,
+    Path(key): Path<String>,
+) -> Response {
+    let Ok(provider_id) = key.parse::<ProviderId>() else {
+        return (StatusCode::NOT_FOUND, "Unknown link").into_response();
+    };
+
+    match state.build_page(&provider_id).await {
+        Ok(page) => Html(page.render()).into_response(),
+        Err(PageErr::NotFound) => (StatusCode::NOT_FOUND, "Link not found").into_response(),
+        Err(PageErr::Busy) => (StatusCode::TOO_MANY_REQUESTS, "Too many requests").into_response(),
+    }
+}
+
+pub fn init_landing_page_route(
+    args: ArcValue<Args>,
+    odesli: OdesliClient,
+    database: Database,
+    image: ImageClient,
+) -> ValueGroupEntry<HttpServeRoute> {
+    let state = LandingPageState {
+        args,
+        odesli,
+        database,
+        image,
+        colours: ColourCache::new(COLOUR_CACHE_CAPACITY),
+        db_lookups: LookupBudget::new(DB_LOOKUPS_PER_SECOND, Duration::from_secs(1)),
+    };
+
+    let router = MethodRouter::new()
+        .get(landing_page_handler)
+        .with_state(Arc::new(state));
+
+    ValueGroupEntry(HttpServeRoute {
+        path: LANDING_PAGE_PATH,
+        router,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::Router;
+    use axum::routing::get;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// Serves the landing page route with a handler that echoes the extracted key
+    async fn serve_echo() -> Url {
+        let router = Router::new().route(
+            LANDING_PAGE_PATH,
+            get(|Path(key): Path<String>| async move { key }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        Url::parse(&format!("http://{}/", addr)).unwrap()
+    }
+
+    #[test]
+    fn landing_page_url_keeps_base_path() {
+        let id: ProviderId = "spotify:track:4cOdK2wGLETKBW3PvgPWqT".parse().unwrap();
+
+        for base in ["https://example.com", "https://example.com/"] {
+            assert_eq!(
+                landing_page_url(&Url::parse(base).unwrap(), &id),
+                "https://example.com/l/spotify:track:4cOdK2wGLETKBW3PvgPWqT"
+            );
+        }
+        assert_eq!(
+            landing_page_url(&Url::parse("https://example.com/bot/").unwrap(), &id),
+            "https://example.com/bot/l/spotify:track:4cOdK2wGLETKBW3PvgPWqT"
+        );
+    }
+
+    #[test]
+    fn lookup_budget_resets_every_window() {
+        let budget = LookupBudget::new(2, Duration::from_secs(3600));
+        assert!(budget.try_acquire());
+        assert!(budget.try_acquire());
+        assert!(!budget.try_acquire());
+
+        let budget = LookupBudget::new(1, Duration::ZERO);
+        assert!(budget.try_acquire());
+        assert!(budget.try_acquire());
+    }
+
+    #[tokio::test]
+    async fn keys_reach_the_handler_unchanged() {
+        let base = serve_echo().await;
+
+        for key in [
+            "spotify:track:4cOdK2wGLETKBW3PvgPWqT",
+            "amazon_music:track:B00TF1DUIA:B00TF1E3RO",
+            "soundcloud:track:artist/some-song",
+            "bandcamp:album:artist/100%-real",
+            "audius:track:artist/song%3fwith%23symbols",
+        ] {
+            let id: ProviderId = key.parse().unwrap();
+            let url = landing_page_url(&base, &id);
+
+            let response = reqwest::get(&url).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{url}");
+            assert_eq!(response.text().await.unwrap(), key, "{url}");
+        }
+    }
+}
@@ -0,0 +1,141 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+use std::fmt::Write;
+
+use crate::constants::NAME;
+
+pub struct PageLink {
+    pub platform: String,
+    pub url: String,
+}
+
+/// Everything shown on the landing page of a song or album
+pub struct LandingPage {
+    pub kind: Option<String>,
+    pub title: Option<String>,
+    pub artist_name: Option<String>,
+    pub thumbnail_url: Option<String>,
+    pub colour: Option<u32>,
+    pub links: Vec<PageLink>,
+    /// The public URL of the page itself, if one is configured
+    pub page_url: Option<String>,
+}
+
+impl LandingPage {
+    pub fn render(&self) -> String {
+        let title = self.title.as_deref().unwrap_or("Unknown");
+        let description = match &self.artist_name {
+            Some(artist) => format!("{} by {}", title, artist),
+            None => title.to_string(),
+        };
+        let og_type = match self.kind.as_deref() {
+            Some("album") => "music.album",
+            _ => "music.song",
+        };
+        let colour = format!("#{:06x}", self.colour.unwrap_or(0x2b2d31));
+
+        let mut html = String::new();
+        let w = &mut html;
+        w.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+        w.push_str("<meta charset=\"utf-8\">\n");
+        w.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+        write_tag(w, "title", &format!("{} - {}", description, NAME));
+        write_meta(w, "name", "description", &description);
+        write_meta(w, "name", "theme-color", &colour);
+        write_meta(w, "property", "og:site_name", NAME);
+        write_meta(w, "property", "og:type", og_type);
+        write_meta(w, "property", "og:title", title);
+        write_meta(w, "property", "og:description", &description);
+        if let Some(thumbnail_url) = &self.thumbnail_url {
+            write_meta(w, "property", "og:image", thumbnail_url);
+        }
+        if let Some(page_url) = &self.page_url {
+            write_meta(w, "property", "og:url", page_url);
+        }
+        writeln!(w, "<style>{}</style>", STYLE.replace("{accent}", &colour))
+            .expect("Writing to string should not fail");
+        w.push_str("</head>\n<body>\n<main>\n");
+
+        if let Some(thumbnail_url) = &self.thumbnail_url {
+            writeln!(
+                w,
+                "<img class=\"cover\" src=\"{}\" alt=\"{}\">",
+                escape(thumbnail_url),
+                escape(title)
+            )
+            .expect("Writing to string should not fail");
+        }
+        write_tag(w, "h1", title);
+        if let Some(artist) = &self.artist_name {
+            write_tag(w, "h2", artist);
+        }
+
+        w.push_str("<ul>\n");
+        for link in &self.links {
+            writeln!(
+                w,
+                "<li><a href=\"{}\" rel=\"noopener\">{}</a></li>",
+                escape(&link.url),
+                escape(&link.platform)
+            )
+            .expect("Writing to string should not fail");
+        }
+        w.push_str("</ul>\n");
+
+        writeln!(
+            w,
+            "<footer>Powered by {} and Songlink/Odesli</footer>",
+            NAME
+        )
+        .expect("Writing to string should not fail");
+        w.push_str("</main>\n</body>\n</html>\n");
+
+        html
+    }
+}
+
+const STYLE: &str = "\
+body{margin:0;min-height:100vh;display:flex;align-items:center;justify-content:center;\
+font-family:system-ui,sans-serif;color:#fff;background:linear-gradient(160deg,{accent},#111 70%)}\
+main{max-width:420px;width:100%;padding:24px;text-align:center}\
+.cover{width:100%;border-radius:12px;box-shadow:0 8px 32px rgba(0,0,0,.5)}\
+h1{margin:16px 0 4px;font-size:1.5em}h2{margin:0 0 16px;font-weight:400;opacity:.8;font-size:1.1em}\
+ul{list-style:none;padding:0;margin:0}\
+li a{display:block;margin:8px 0;padding:12px;border-radius:8px;background:rgba(255,255,255,.1);\
+color:#fff;text-decoration:none}li a:hover{background:rgba(255,255,255,.2)}\
+footer{margin-top:24px;font-size:.8em;opacity:.6}";
+
+fn write_tag(w: &mut String, tag: &str, content: &str) {
+    writeln!(w, "<{tag}>{}</{tag}>", escape(content)).expect("Writing to string should not fail");
+}
+
+fn write_meta(w: &mut String, attr: &str, name: &str, content: &str) {
+    writeln!(
+        w,
+        "<meta {}=\"{}\" content=\"{}\">",
+        attr,
+        name,
+        escape(content)
+    )
+    .expect("Writing to string should not fail");
+}
+
+/// Escapes the text for use in HTML content and attribute values
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,256 @@
+/*
+ * Copyright (c) 2021-2026 tooboredtocode
+ * All Rights Reserved
+ */
+
+//! Catalogue based localisation of all user facing strings.
+//!
+//! Each locale is a YAML bundle in `locales/`, which is embedded into the binary and loaded once
+//! at startup. Nested keys are flattened with dots, e.g. `commands.share.description`.
+//! Lookups fall back along [`fallback_chain`], ending at [`BASE_LOCALE`], which has to define
+//! every key. Every other shipped bundle has to define the exact same keys, which is checked when
+//! the catalogue is loaded and by the tests of this module.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::sync::OnceLock;
+
+use serde_yaml::Value;
+use tracing::warn;
+use twilight_interactions::command::{DescLocalizations, NameLocalizations};
+
+use crate::util::discord_locales::DiscordLocale;
+
+/// The locale all other bundles are checked against and everything falls back to
+pub const BASE_LOCALE: DiscordLocale = DiscordLocale::EnglishUS;
+
+/// The shipped bundles, the base locale has to be part of them
+const BUNDLES: &[(DiscordLocale, &str)] = &[
+    (DiscordLocale::EnglishUS, include_str!("locales/en-US.yaml")),
+    (DiscordLocale::German, include_str!("locales/de.yaml")),
+];
+
+static CATALOGUE: OnceLock<Catalogue> = OnceLock::new();
+
+type Bundle = HashMap<String, String>;
+
+#[derive(Debug)]
+struct Catalogue {
+    bundles: HashMap<DiscordLocale, Bundle>,
+}
+
+#[derive(Debug)]
+pub enum LocalisationErr {
+    Parse(DiscordLocale, serde_yaml::Error),
+    InvalidValue(DiscordLocale, String),
+    MissingBase,
+    MissingKeys(DiscordLocale, Vec<String>),
+    UnknownKeys(DiscordLocale, Vec<String>),
+}
+
+impl fmt::Display for LocalisationErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(locale, err) => write!(f, "Failed to parse bundle {}: {}", locale, err),
+            Self::InvalidValue(locale, key) => write!(
+                f,
+                "Bundle {} contains a value that is not a string at {}",
+                locale, key
+            ),
+            Self::MissingBase => write!(f, "The base bundle {} is not shipped", BASE_LOCALE),
+            Self::MissingKeys(locale, keys) => write!(
+                f,
+                "Bundle {} is missing the keys: {}",
+                locale,
+                keys.join(", ")
+            ),
+            Self::UnknownKeys(locale, keys) => write!(
+                f,
+                "Bundle {} contains unknown keys: {}",
+                locale,
+                keys.join(", ")
+            ),
+        }
+    }
+}
+
+/// Loads and validates the catalogue, failing if any of the shipped bundles is invalid.
+pub fn init(required_keys: &[&[&str]]) -> Result<(), LocalisationErr> {
+    let catalogue = Catalogue::load(required_keys)?;
+
+    let _ = CATALOGUE.set(catalogue);
+    Ok(())
+}
+
+/// The locales tried in order when looking up a key for the given locale
+pub fn fallback_chain(locale: DiscordLocale) -> impl Iterator<Item = DiscordLocale> {
+    let parent = match locale {
+        DiscordLocale::EnglishGB => Some(DiscordLocale::EnglishUS),
+        _ => None,
+    };
+
+    [Some(locale), parent, Some(BASE_LOCALE)]
+        .into_iter()
+        .flatten()
+}
+
+/// Returns the string for the key in the given locale, falling back along the [`fallback_chain`].
+pub fn localise(locale: DiscordLocale, key: &'static str) -> &'static str {
+    let catalogue = catalogue();
+
+    let found = fallback_chain(locale).find_map(|locale| {
+        catalogue
+            .bundles
+            .get(&locale)
+            .and_then(|bundle| bundle.get(key))
+    });
+
+    match found {
+        Some(value) => value.as_str(),
+        None => {
+            warn!(
+                key,
+                "Missing localisation key, falling back to the key itself"
+            );
+            key
+        }
+    }
+}
+
+/// Builds the description localisations of a command or option from the key.
+pub fn desc_localizations(key: &'static str) -> DescLocalizations {
+    DescLocalizations::new(localise(BASE_LOCALE, key), translations(key))
+}
+
+/// Builds the name localisations of a command option choice from the key.
+pub fn name_localizations(key: &'static str) -> NameLocalizations {
+    NameLocalizations::new(translations(key))
+}
+
+/// All translations of the key, except for the base locale which Discord uses as the default
+fn translations(key: &'static str) -> impl Iterator<Item = (&'static str, &'static str)> {
+    catalogue()
+        .bundles
+        .iter()
+        .filter(|(locale, _)| **locale != BASE_LOCALE)
+        .filter_map(move |(locale, bundle)| {
+            bundle
+                .get(key)
+                .map(|value| (locale.to_str(), value.as_str()))
+        })
+}
+
+fn catalogue() -> &'static Catalogue {
+    CATALOGUE
+        .get()
+        .expect("The localisation catalogue should be loaded at startup")
+}
+
+impl Catalogue {
+    fn load(required_keys: &[&[&str]]) -> Result<Self, LocalisationErr> {
+        let mut bundles = HashMap::with_capacity(BUNDLES.len());
+        for (locale, source) in BUNDLES {
+            let value: Value =
+                serde_yaml::from_str(source).map_err(|e| LocalisationErr::Parse(*locale, e))?;
+
+            let mut bundle = Bundle::new();
+            flatten(*locale, None, value, &mut bundle)?;
+            bundles.insert(*locale, bundle);
+        }
+
+        let base = bundles
+            .get(&BASE_LOCALE)
+            .ok_or(LocalisationErr::MissingBase)?;
+
+        let missing: Vec<String> = required_keys
+            .iter()
+            .copied()
+            .flatten()
+            .filter(|key| !base.contains_key(**key))
+            .map(|key| key.to_string())
+            .collect();
+        if !missing.is_empty() {
+            return Err(LocalisationErr::MissingKeys(BASE_LOCALE, missing));
+        }
+
+        let base_keys: BTreeSet<&String> = base.keys().collect();
+        for (locale, bundle) in &bundles {
+            let keys: BTreeSet<&String> = bundle.keys().collect();
+
+            let missing: Vec<String> = base_keys.difference(&keys).map(|k| k.to_string()).collect();
+            if !missing.is_empty() {
+                return Err(LocalisationErr::MissingKeys(*locale, missing));
+            }
+
+            let unknown: Vec<String> = keys.difference(&base_keys).map(|k| k.to_string()).collect();
+            if !unknown.is_empty() {
+                return Err(LocalisationErr::UnknownKeys(*locale, unknown));
+            }
+        }
+
+        Ok(Self { bundles })
+    }
+}
+
+/// Flattens the nested YAML maps into dot separated keys
+fn flatten(
+    locale: DiscordLocale,
+    prefix: Option<&str>,
+    value: Value,
+    bundle: &mut Bundle,
+) -> Result<(), LocalisationErr> {
+    let key = prefix.unwrap_or_default();
+
+    match value {
+        Value::String(value) => {
+            bundle.insert(key.to_string(), value);
+        }
+        Value::Mapping(mapping) => {
+            for (child, value) in mapping {
+                let Value::String(child) = child else {
+                    return Err(LocalisationErr::InvalidValue(locale, key.to_string()));
+                };
+
+                let child = match prefix {
+                    Some(prefix) => format!("{}.{}", prefix, child),
+                    None => child,
+                };
+                flatten(locale, Some(&child), value, bundle)?;
+            }
+        }
+        _ => return Err(LocalisationErr::InvalidValue(locale, key.to_string())),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interactions::LOCALISATION_KEYS;
+
+    #[test]
+    fn shipped_bundles_are_valid() {
+        if let Err(err) = Catalogue::load(LOCALISATION_KEYS) {
+            panic!("{}", err);
+        }
+    }
+
+    #[test]
+    fn every_bundle_defines_every_message() {
+        for (locale, source) in BUNDLES {
+            let value: Value = serde_yaml::from_str(source).expect("Bundle should be valid YAML");
+            let mut bundle = Bundle::new();
+            flatten(*locale, None, value, &mut bundle).expect("Bundle should only contain strings");
+
+            let missing: Vec<&str> = LOCALISATION_KEYS
+                .iter()
+                .copied()
+                .flatten()
+                .copied()
+                .filter(|key| !bundle.contains_key(*key))
+                .collect();
+            assert!(missing.is_empty(), "{} is missing {:?}", locale, missing);
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::process::exit;
 use std::time::Duration;
 
 use metronomos::Runtime;
@@ -9,6 +10,7 @@ use crate::http_server::provide_http_server;
 use crate::util::EmptyResult;
 use crate::util::setup_logger::setup_logger;
 
+mod api;
 mod args;
 mod clients;
 mod color_config;
@@ -17,6 +19,8 @@ mod db;
 mod event_handler;
 mod http_server;
 mod interactions;
+mod landing_page;
+mod localisation;
 mod metrics;
 mod shard_runners;
 mod util;
@@ -28,6 +32,10 @@ fn main() {
         .as_ref()
         .map(|path| ColorConfig::from_file(path))
         .unwrap_or_default();
+    if let Err(err) = localisation::init(interactions::LOCALISATION_KEYS) {
+        eprintln!("Failed to load the localisation catalogue: {}", err);
+        exit(1)
+    }
 
     setup_logger(&args.log, args.log_format);
 
@@ -58,6 +66,8 @@ async fn async_main(args: Args, color_config: ColorConfig) -> EmptyResult<()> {
         b.provide_async(interactions::InteractionsHandler::init)?;
         b.provide(event_handler::EventHandler::init)?;
 
+        b.provide_with(api::provide_api)?;
+        b.provide(landing_page::init_landing_page_route)?;
         b.provide(provide_http_server)?;
         b.provide(shard_runners::provide_shard_runners)?;
 
@@ -57,7 +57,7 @@ impl GuildMetrics {
 
     fn update_shard_stats(&self, shard_id: u32) {
         let lock = self.inner.read();
-        if lock.guild_states.get(&shard_id).is_none() {
+        if !lock.guild_states.contains_key(&shard_id) {
             return;
         };
 
@@ -59,6 +59,68 @@ pub struct ThirdPartyLabels {
     pub status: u16,
 }
 
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+pub enum CacheLookupResult {
+    Hit,
+    Miss,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct CacheLookupLabels {
+    pub result: CacheLookupResult,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+pub enum EvictionReason {
+    /// The cache was over its capacity
+    Capacity,
+    /// The entry was not accessed for too long
+    Expired,
+    /// The entry was removed on request
+    Manual,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct CacheEvictionLabels {
+    pub reason: EvictionReason,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+pub enum RateLimitState {
+    /// Tokens are handed out at the configured rate
+    Normal,
+    /// No tokens are handed out after the API responded with a 429
+    Paused,
+    /// Tokens are handed out at a reduced rate after a pause
+    Recovering,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct RateLimitStateLabels {
+    pub state: RateLimitState,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+pub enum ThirdPartyApi {
+    Odesli,
+    Thumbnail,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+pub enum AttemptOutcome {
+    Success,
+    /// The attempt failed with a transient error and another one follows
+    Retried,
+    /// The attempt failed and no further one follows
+    Failure,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct ThirdPartyAttemptLabels {
+    pub api: ThirdPartyApi,
+    pub outcome: AttemptOutcome,
+}
+
 impl From<&GuildCreate> for GuildState {
     fn from(create: &GuildCreate) -> Self {
         match create {
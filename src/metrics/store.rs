@@ -14,7 +14,8 @@ use prometheus_client::registry::{Registry, Unit};
 use crate::constants::{GIT_BRANCH, GIT_REVISION, NAME, RUST_VERSION, VERSION};
 use crate::metrics::guild_metrics::GuildMetrics;
 use crate::metrics::labels::{
-    EventLabels, ShardLatencyLabels, ThirdPartyLabels, ThirdPartyRateLimitLabels,
+    CacheEvictionLabels, CacheLookupLabels, EventLabels, RateLimitStateLabels, ShardLatencyLabels,
+    ThirdPartyAttemptLabels, ThirdPartyLabels, ThirdPartyRateLimitLabels,
 };
 use crate::metrics::shard_states::ShardStates;
 use crate::util::metric_utils::HasHistogramFamily;
@@ -155,9 +156,33 @@ make_metrics_store!(
             ])
         }),
 
+        #[name = "3rd_party_api_attempts"]
+        #[help = "Number of request attempts for the various APIs used by the bots, by outcome"]
+        third_party_api_attempts: Family<ThirdPartyAttemptLabels, Counter>,
+
         #[name = "odesli_rate_limit_tokens"]
         #[help = "Number of tokens currently available in the Odesli rate limiter"]
         odesli_rate_limit_tokens: Gauge<u64, AtomicU64>,
+        #[name = "odesli_rate_limit_state"]
+        #[help = "State of the Odesli rate limiter, 1 for the current state"]
+        odesli_rate_limit_state: Family<RateLimitStateLabels, Gauge>,
+
+        #[name = "odesli_cache_lookups"]
+        #[help = "Number of lookups in the in-memory Odesli cache"]
+        odesli_cache_lookups: Family<CacheLookupLabels, Counter>,
+        #[name = "odesli_cache_evictions"]
+        #[help = "Number of responses evicted from the in-memory Odesli cache"]
+        odesli_cache_evictions: Family<CacheEvictionLabels, Counter>,
+        #[name = "odesli_negative_cache_hits"]
+        #[help = "Number of Odesli API requests saved by remembering links that failed to resolve"]
+        odesli_negative_cache_hits: Counter,
+        #[name = "odesli_cache_responses"]
+        #[help = "Number of responses in the in-memory Odesli cache"]
+        odesli_cache_responses: Gauge<u64, AtomicU64>,
+        #[name = "odesli_cache_size"]
+        #[help = "Approximate size of the in-memory Odesli cache"]
+        #[unit = Unit::Bytes]
+        odesli_cache_size: Gauge<u64, AtomicU64>,
     }
 );
 
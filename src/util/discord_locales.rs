@@ -6,7 +6,7 @@
 use std::fmt::Display;
 
 #[non_exhaustive]
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum DiscordLocale {
     Danish,
     German,
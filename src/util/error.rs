@@ -17,9 +17,7 @@ impl std::fmt::Display for ExpectErr {
 impl std::error::Error for ExpectErr {}
 
 impl From<ExpectErr> for () {
-    fn from(_: ExpectErr) -> Self {
-        ()
-    }
+    fn from(_: ExpectErr) -> Self {}
 }
 
 macro_rules! expect_err {